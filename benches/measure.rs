@@ -0,0 +1,78 @@
+//! Benchmarks of the operations of measures and the fits, run with
+//! `cargo bench`. Compare them with the feature `rayon` for long measures, and
+//! with the feature `perf-stats` to also print the work counted.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ferrilab::{CurveFit, LinearFit, Measure};
+
+const LENGTHS: [usize; 3] = [100, 10_000, 1_000_000];
+
+fn measure(n: usize) -> Measure {
+    (0..n).map(|i| (1.0 + i as f64 / n as f64, 0.01)).collect()
+}
+
+fn arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arithmetic");
+    for n in LENGTHS {
+        let (a, b) = (measure(n), measure(n));
+        group.bench_with_input(BenchmarkId::new("borrowed", n), &n, |bench, _| {
+            bench.iter(|| &(&(&a * &b) + &a) / 2.0)
+        });
+        group.bench_with_input(BenchmarkId::new("owned", n), &n, |bench, _| {
+            bench.iter(|| (a.clone() * &b + &a) / 2.0)
+        });
+    }
+    group.finish();
+}
+
+fn functions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("functions");
+    for n in LENGTHS {
+        let a = measure(n);
+        group.bench_with_input(BenchmarkId::new("sin", n), &n, |bench, _| {
+            bench.iter(|| black_box(&a).sin())
+        });
+        group.bench_with_input(BenchmarkId::new("sqrt", n), &n, |bench, _| {
+            bench.iter(|| black_box(&a).sqrt())
+        });
+        group.bench_with_input(BenchmarkId::new("estimation", n), &n, |bench, _| {
+            bench.iter(|| black_box(&a).estimation())
+        });
+    }
+    group.finish();
+}
+
+fn fits(c: &mut Criterion) {
+    let x: Vec<f64> = (0..200).map(|i| i as f64 / 20.0).collect();
+    let y: Vec<f64> = x
+        .iter()
+        .map(|x| 3.0 * (-x / 2.0).exp() + 0.01 * (7.0 * x).sin())
+        .collect();
+    let mut group = c.benchmark_group("fits");
+    group.bench_function("linear", |bench| {
+        bench.iter(|| LinearFit::new(x.clone(), y.clone()).fit())
+    });
+    group.bench_function("curve", |bench| {
+        let fit = CurveFit::new(
+            |x: &f64, par: &[f64]| par[0] * (-x / par[1]).exp(),
+            x.clone(),
+            y.clone(),
+        )
+        .initial_point([2.0, 1.0]);
+        bench.iter(|| fit.fit())
+    });
+    group.finish();
+
+    #[cfg(feature = "perf-stats")]
+    {
+        ferrilab::reset_perf_stats();
+        let a = measure(10_000);
+        let _ = (a.clone() * &a + &a) / 2.0;
+        let _ = CurveFit::new(|x: &f64, par: &[f64]| par[0] * (-x / par[1]).exp(), x, y)
+            .initial_point([2.0, 1.0])
+            .fit();
+        println!("{:?}", ferrilab::perf_stats());
+    }
+}
+
+criterion_group!(benches, arithmetic, functions, fits);
+criterion_main!(benches);
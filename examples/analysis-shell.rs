@@ -0,0 +1,9 @@
+use ferrilab::shell::Shell;
+use std::io;
+
+// Interactive analysis from the terminal, run it with
+// cargo run --example analysis-shell --features shell
+fn main() -> io::Result<()> {
+    println!("FerriLab analysis shell, type \"help\" to list the commands.");
+    Shell::new().run(io::stdin().lock(), io::stdout())
+}
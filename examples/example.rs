@@ -1,7 +1,6 @@
 use ferrilab::*;
-use pyo3::prelude::*;
 
-fn main() -> PyResult<()> {
+fn main() -> Result<(), PlotError> {
     // First let's create some measures manually from data obtained in laboratory.
     let time = measure!([0.0001346, 0.1134, 0.22734, 0.312324, 0.4019256, 0.5127634], [0.0000123, 0.0154, 0.012, 0.02943, 0.02544, 0.04872]; true);
 
@@ -27,7 +26,7 @@ fn main() -> PyResult<()> {
     Scatter::new(&time, &position).color("red").scatter()?;
 
     // Plots the line obtanied from the linear fit.
-    Plot::new(&time, &time * &slope + &intercept)
+    Plot::new(&time, &time * slope + intercept)
         .color("black")
         .plot()?;
 
@@ -0,0 +1,91 @@
+//! Conversions between measures and the vectors and matrices of nalgebra, so
+//! the measures and fits can be used in other linear algebra code. Requires the
+//! feature `nalgebra`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use ferrilab::{measure, Measure, LinearFit};
+//! # use nalgebra::DVector;
+//! let x = DVector::from_vec(vec![0.0, 1.0, 2.0, 3.0]);
+//! let y = &x * 2.0 + DVector::from_element(4, 1.0);
+//!
+//! let (parameters, errors) = LinearFit::from_dvectors(&x, &y).fit_dvectors();
+//! assert!((parameters[0] - 2.0).abs() < 1e-12);
+//!
+//! let position = Measure::from_dvectors(&y, &DVector::from_element(1, 0.1)).unwrap();
+//! assert_eq!(position.error_dvector(), DVector::from_element(4, 0.1));
+//! ```
+use {
+    crate::{objects::MyError, CurveFit, LinearFit, Measure, ScalarMeasure},
+    nalgebra::{DMatrix, DVector},
+};
+
+impl Measure {
+    /// Constructs a measure from vectors of values and errors, with one error
+    /// for all values or one for each value.
+    pub fn from_dvectors(value: &DVector<f64>, error: &DVector<f64>) -> Result<Measure, MyError> {
+        Measure::new(value.as_slice().to_vec(), error.as_slice().to_vec(), false)
+    }
+    /// Returns the values as a vector.
+    pub fn value_dvector(&self) -> DVector<f64> {
+        DVector::from_column_slice(self.value())
+    }
+    /// Returns the errors as a vector.
+    pub fn error_dvector(&self) -> DVector<f64> {
+        DVector::from_column_slice(self.error())
+    }
+    /// Returns the covariance matrix of the values, which is diagonal with the
+    /// squared errors as the values of a measure are independent.
+    pub fn covariance_dmatrix(&self) -> DMatrix<f64> {
+        DMatrix::from_diagonal(&self.error_dvector().map(|err| err.powi(2)))
+    }
+}
+
+impl From<&Measure> for DVector<f64> {
+    /// Vector of the values of the measure.
+    fn from(measure: &Measure) -> DVector<f64> {
+        measure.value_dvector()
+    }
+}
+
+impl From<DVector<f64>> for Measure {
+    /// Measure of the values of the vector without errors.
+    fn from(vector: DVector<f64>) -> Measure {
+        let n = vector.len();
+        Measure::new(vector.data.into(), vec![0.0; n], false).unwrap()
+    }
+}
+
+// Vectors of the values and errors of the parameters of a fit.
+fn parameters_dvectors(parameters: &[ScalarMeasure]) -> (DVector<f64>, DVector<f64>) {
+    (
+        DVector::from_iterator(parameters.len(), parameters.iter().map(|par| par.value)),
+        DVector::from_iterator(parameters.len(), parameters.iter().map(|par| par.error)),
+    )
+}
+
+impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
+    /// Constructs a new CurveFit from vectors of x and y values.
+    pub fn from_dvectors(model: F, x_values: &DVector<f64>, y_values: &DVector<f64>) -> Self {
+        CurveFit::new(model, x_values.as_slice(), y_values.as_slice())
+    }
+    /// Fits the curve, returning the vectors of the values and errors of the
+    /// parameters.
+    pub fn fit_dvectors(&self) -> (DVector<f64>, DVector<f64>) {
+        parameters_dvectors(&self.fit())
+    }
+}
+
+impl LinearFit {
+    /// Constructs a new LinearFit from vectors of x and y values.
+    pub fn from_dvectors(x_values: &DVector<f64>, y_values: &DVector<f64>) -> Self {
+        LinearFit::new(x_values.as_slice(), y_values.as_slice())
+    }
+    /// Fits the line, returning the vectors of the values and errors of the
+    /// slope and the intercept, in that order.
+    pub fn fit_dvectors(&self) -> (DVector<f64>, DVector<f64>) {
+        let (slope, intercept) = self.fit();
+        parameters_dvectors(&[slope, intercept])
+    }
+}
@@ -0,0 +1,66 @@
+//! Approximate comparisons of measures with the traits of the crate approx, so
+//! tests can use `assert_relative_eq!` with computed errors instead of exact
+//! equality. Requires the feature `approx`.
+//!
+//! Two measures are approximately equal if they have the same length and unit
+//! and every value and error is approximately equal to the other one. The
+//! style, name, metadata and provenance are not compared.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use ferrilab::{measure, Measure};
+//! # use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne};
+//! let side = measure!([0.1, 0.2], 0.01);
+//! let perimeter = &side * 3.0;
+//!
+//! assert_ne!(perimeter.value(), &vec![0.3, 0.6]);
+//! assert_relative_eq!(perimeter, measure!([0.3, 0.6], 0.03; false));
+//! assert_abs_diff_eq!(perimeter, measure!([0.3, 0.6], 0.03; false), epsilon = 1e-12);
+//! assert_relative_ne!(perimeter, measure!([0.3, 0.6], 0.02; false));
+//! ```
+use {
+    crate::objects::Measure,
+    approx::{AbsDiffEq, RelativeEq},
+};
+
+impl Measure {
+    // Checks that both measures have the same length and unit and compares
+    // every pair of values and errors.
+    fn all_approx_eq(&self, other: &Measure, eq: impl Fn(&f64, &f64) -> bool) -> bool {
+        self.len() == other.len()
+            && self.unit() == other.unit()
+            && self
+                .value()
+                .iter()
+                .zip(other.value())
+                .all(|(a, b)| eq(a, b))
+            && self
+                .error()
+                .iter()
+                .zip(other.error())
+                .all(|(a, b)| eq(a, b))
+    }
+}
+
+impl AbsDiffEq for Measure {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Measure, epsilon: f64) -> bool {
+        self.all_approx_eq(other, |a, b| a.abs_diff_eq(b, epsilon))
+    }
+}
+
+impl RelativeEq for Measure {
+    fn default_max_relative() -> f64 {
+        f64::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Measure, epsilon: f64, max_relative: f64) -> bool {
+        self.all_approx_eq(other, |a, b| a.relative_eq(b, epsilon, max_relative))
+    }
+}
@@ -0,0 +1,211 @@
+//! Binary format to store processed measures and reopen them instantly.
+//!
+//! The file starts with the magic bytes `FLAB`, the version and the number of
+//! measures as little endian integers. Then, for each measure, its length, the
+//! byte offset of its data and the exponents of its unit. The data of every
+//! measure are its values followed by its errors, as little endian `f64`. Every
+//! field is aligned to 8 bytes, so the data can be used without copying it
+//! from a memory-mapped file.
+use {
+    crate::{Measure, Unit},
+    std::{
+        fs,
+        io::{Error, ErrorKind},
+        path::Path,
+    },
+};
+
+const MAGIC: &[u8; 4] = b"FLAB";
+const VERSION: u32 = 1;
+const HEADER: usize = 16;
+// Length, offset and the 7 exponents of the unit.
+const ENTRY: usize = 8 * 9;
+
+/// Position of a measure in a binary file.
+#[derive(Debug, Clone)]
+struct Entry {
+    len: usize,
+    offset: usize,
+    unit: Unit,
+}
+
+/// Writes measures to a file in the binary format of FerriLab, keeping their
+/// values, errors and the dimensions of their units.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use ferrilab::{measure, Measure, read_binary, write_binary};
+/// let time = measure!([0.2, 0.3, 0.4], 0.01);
+/// let position = measure!([2.4, 3.4, 5.1], 0.2);
+///
+/// write_binary("run.flab", &[time, position]).unwrap();
+/// let measures = read_binary("run.flab").unwrap();
+/// ```
+pub fn write_binary(path: impl AsRef<Path>, measures: &[Measure]) -> Result<(), Error> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(measures.len() as u64).to_le_bytes());
+
+    let mut offset = HEADER + ENTRY * measures.len();
+    for measure in measures {
+        bytes.extend_from_slice(&(measure.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(offset as u64).to_le_bytes());
+        for exponent in measure.unit().dimension() {
+            bytes.extend_from_slice(&exponent.to_le_bytes());
+        }
+        offset += 16 * measure.len();
+    }
+    for measure in measures {
+        let (value, error) = measure.unpack();
+        for num in value.iter().chain(error.iter()) {
+            bytes.extend_from_slice(&num.to_le_bytes());
+        }
+    }
+    fs::write(path, bytes)
+}
+
+/// Reads to memory the measures of a file written with [write_binary].
+pub fn read_binary(path: impl AsRef<Path>) -> Result<Vec<Measure>, Error> {
+    let bytes = fs::read(path)?;
+    Ok(entries(&bytes)?
+        .into_iter()
+        .map(|entry| {
+            let values = floats(&bytes, entry.offset, entry.len);
+            let errors = floats(&bytes, entry.offset + 8 * entry.len, entry.len);
+            Measure::new(values, errors, false)
+                .unwrap()
+                .with_unit(entry.unit)
+        })
+        .collect())
+}
+
+fn entries(bytes: &[u8]) -> Result<Vec<Entry>, Error> {
+    let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+    if bytes.len() < HEADER || &bytes[..4] != MAGIC {
+        return Err(invalid("Not a FerriLab binary file."));
+    }
+    let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+    if version != VERSION {
+        return Err(invalid(&format!("Unsupported binary version {}.", version)));
+    }
+    let count = word(bytes, 8) as usize;
+    let entries: Vec<Entry> = (0..count)
+        .map(|i| HEADER + ENTRY * i)
+        .take_while(|start| start + ENTRY <= bytes.len())
+        .map(|start| {
+            let mut dimension = [0.0; 7];
+            for (j, exponent) in dimension.iter_mut().enumerate() {
+                *exponent = f64::from_bits(word(bytes, start + 16 + 8 * j));
+            }
+            Entry {
+                len: word(bytes, start) as usize,
+                offset: word(bytes, start + 8) as usize,
+                unit: Unit::from_dimension(dimension),
+            }
+        })
+        .collect();
+    let truncated = entries.len() < count
+        || entries.iter().any(|entry| {
+            entry.offset % 8 != 0
+                || entry
+                    .len
+                    .checked_mul(16)
+                    .and_then(|size| size.checked_add(entry.offset))
+                    .is_none_or(|end| end > bytes.len())
+        });
+    if truncated {
+        return Err(invalid("The binary file is truncated."));
+    }
+    Ok(entries)
+}
+
+fn word(bytes: &[u8], start: usize) -> u64 {
+    u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap())
+}
+
+fn floats(bytes: &[u8], start: usize, len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| f64::from_bits(word(bytes, start + 8 * i)))
+        .collect()
+}
+
+/// Measures of a binary file mapped into memory, whose values and errors are
+/// read directly from the file without copying them. Requires the feature
+/// `mmap` and a little endian target.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use ferrilab::MappedMeasures;
+/// let measures = MappedMeasures::open("run.flab").unwrap();
+///
+/// let mean = measures.value(0).iter().sum::<f64>() / measures.value(0).len() as f64;
+/// let position = measures.to_measure(1);
+/// ```
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MappedMeasures {
+    map: memmap2::Mmap,
+    entries: Vec<Entry>,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedMeasures {
+    /// Maps a file written with [write_binary] into memory.
+    pub fn open(path: impl AsRef<Path>) -> Result<MappedMeasures, Error> {
+        if cfg!(target_endian = "big") {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "Mapped measures are only supported on little endian targets.",
+            ));
+        }
+        let file = fs::File::open(path)?;
+        // SAFETY: the file must not be modified while it is mapped, as with any
+        // memory-mapped file.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let entries = entries(&map)?;
+        Ok(MappedMeasures { map, entries })
+    }
+    /// Returns the number of measures of the file.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns true if the file has no measures.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Returns the values of a measure. Panics if the index is out of bounds.
+    pub fn value(&self, index: usize) -> &[f64] {
+        let entry = &self.entries[index];
+        self.slice(entry.offset, entry.len)
+    }
+    /// Returns the errors of a measure. Panics if the index is out of bounds.
+    pub fn error(&self, index: usize) -> &[f64] {
+        let entry = &self.entries[index];
+        self.slice(entry.offset + 8 * entry.len, entry.len)
+    }
+    /// Returns the unit of a measure. Panics if the index is out of bounds.
+    pub fn unit(&self, index: usize) -> &Unit {
+        &self.entries[index].unit
+    }
+    /// Copies a measure of the file into memory. Panics if the index is out of
+    /// bounds.
+    pub fn to_measure(&self, index: usize) -> Measure {
+        Measure::new(
+            self.value(index).to_vec(),
+            self.error(index).to_vec(),
+            false,
+        )
+        .unwrap()
+        .with_unit(self.unit(index).clone())
+    }
+    fn slice(&self, offset: usize, len: usize) -> &[f64] {
+        let bytes = &self.map[offset..offset + 8 * len];
+        // SAFETY: the map is page aligned and the offsets are multiples of 8,
+        // checked when opening the file, and the target is little endian, so
+        // the bytes are valid f64.
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const f64, len) }
+    }
+}
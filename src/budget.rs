@@ -0,0 +1,309 @@
+//! Contains the struct SourcedMeasure, used to carry the errors of several named
+//! sources separately, and its error budget.
+use {
+    crate::objects::{unit_suffix, Measure, MyError},
+    crate::units::Unit,
+    std::{
+        fmt::Display,
+        ops::{Add, Div, Mul, Sub},
+    },
+};
+
+/// Measure whose errors from several named sources, like the statistical,
+/// calibration or resolution errors, are propagated separately through the
+/// operations and combined on demand, as a generalization of
+/// [TaggedMeasure](crate::TaggedMeasure).
+///
+/// Every source is stored as a [Measure] with the same values and only the
+/// errors of that source, so any operation of the measures can be applied with
+/// [SourcedMeasure::map]. The propagation is to first order, so the total
+/// error is the sum in quadrature of the errors of all sources, and the
+/// operations between two sourced measures combine the errors of the sources
+/// with the same name as independent.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, SourcedMeasure};
+/// let length = measure!([10.0], 0.3; false)
+///     .source("statistical")
+///     .with_source("calibration", vec![0.4])
+///     .unwrap();
+/// let double = &length * 2.0;
+///
+/// assert_eq!(double.component("calibration").unwrap().error(), &vec![0.8]);
+/// assert_eq!(double.total().error(), &vec![1.0]);
+/// assert_eq!(double.to_string(), "20 ± 0.6 (statistical) ± 0.8 (calibration)");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedMeasure {
+    values: Measure,
+    sources: Vec<(String, Measure)>,
+}
+
+impl SourcedMeasure {
+    /// Constructs a SourcedMeasure from the values of a measure, without any
+    /// source of error.
+    pub fn new(measure: Measure) -> SourcedMeasure {
+        SourcedMeasure {
+            values: measure.with_error(0.0),
+            sources: Vec::new(),
+        }
+    }
+    /// Adds a source of error, with one error for all values or one for each
+    /// value. The errors of a source that already exists are added in
+    /// quadrature.
+    pub fn with_source(mut self, name: &str, error: Vec<f64>) -> Result<Self, MyError> {
+        let component =
+            Measure::new(self.values.value().clone(), error, false)?.with_unit(self.unit().clone());
+        match self.sources.iter_mut().find(|(source, _)| source == name) {
+            Some((_, existing)) => {
+                for (i, (_, err)) in existing.into_iter().enumerate() {
+                    *err = err.hypot(component.error()[i]);
+                }
+            }
+            None => self.sources.push((name.to_string(), component)),
+        }
+        Ok(self)
+    }
+
+    /// Removes a source of error, if it exists.
+    pub(crate) fn without_source(&self, name: &str) -> SourcedMeasure {
+        let mut sourced = self.clone();
+        sourced.sources.retain(|(source, _)| source != name);
+        sourced
+    }
+
+    /// Returns the values.
+    pub fn value(&self) -> &Vec<f64> {
+        self.values.value()
+    }
+    /// Returns the names of the sources, in the order they were added.
+    pub fn source_names(&self) -> Vec<&str> {
+        self.sources.iter().map(|(name, _)| name.as_str()).collect()
+    }
+    /// Returns the measure with only the errors of a source, or None if there
+    /// is no source with that name.
+    pub fn component(&self, name: &str) -> Option<&Measure> {
+        self.sources
+            .iter()
+            .find(|(source, _)| source == name)
+            .map(|(_, component)| component)
+    }
+    /// Returns the unit.
+    pub fn unit(&self) -> &Unit {
+        self.values.unit()
+    }
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the measure with the errors of all sources added in quadrature.
+    pub fn total(&self) -> Measure {
+        let mut total = self.values.clone();
+        for (_, component) in &self.sources {
+            for (i, (_, error)) in (&mut total).into_iter().enumerate() {
+                *error = error.hypot(component.error()[i]);
+            }
+        }
+        total
+    }
+    /// Returns the contribution of every source to the total error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, SourcedMeasure};
+    /// let voltage = SourcedMeasure::new(measure!([5.0]))
+    ///     .with_source("resolution", vec![0.3])
+    ///     .unwrap()
+    ///     .with_source("calibration", vec![0.4])
+    ///     .unwrap();
+    ///
+    /// let budget = voltage.error_budget();
+    /// assert!((budget.entries[1].contribution[0] - 0.64).abs() < 1e-12);
+    /// assert_eq!(budget.to_string(), "resolution: 0.3 (36.0%)\ncalibration: 0.4 (64.0%)\ntotal: 0.5");
+    /// ```
+    pub fn error_budget(&self) -> ErrorBudget {
+        let total = self.total().error().clone();
+        let entries = self
+            .sources
+            .iter()
+            .map(|(name, component)| BudgetEntry {
+                source: name.clone(),
+                error: component.error().clone(),
+                contribution: component
+                    .error()
+                    .iter()
+                    .zip(&total)
+                    .map(|(err, tot)| (err / tot).powi(2))
+                    .collect(),
+            })
+            .collect();
+        ErrorBudget { entries, total }
+    }
+
+    /// Applies an operation of measures to the values and every source of
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, SourcedMeasure};
+    /// let area = measure!([4.0], 0.4; false).source("resolution");
+    /// let side = area.map(Measure::sqrt);
+    ///
+    /// assert_eq!(side.component("resolution").unwrap().error(), &vec![0.1]);
+    /// ```
+    pub fn map(&self, function: impl Fn(&Measure) -> Measure) -> SourcedMeasure {
+        SourcedMeasure {
+            values: function(&self.values),
+            sources: self
+                .sources
+                .iter()
+                .map(|(name, component)| (name.clone(), function(component)))
+                .collect(),
+        }
+    }
+    /// Applies an operation of two measures to the values and every source of
+    /// error. The sources missing in one of them are taken without error.
+    pub fn zip_with(
+        &self,
+        other: &SourcedMeasure,
+        function: impl Fn(&Measure, &Measure) -> Measure,
+    ) -> SourcedMeasure {
+        let mut names = self.source_names();
+        for name in other.source_names() {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        SourcedMeasure {
+            values: function(&self.values, &other.values),
+            sources: names
+                .into_iter()
+                .map(|name| {
+                    let component = function(
+                        self.component(name).unwrap_or(&self.values),
+                        other.component(name).unwrap_or(&other.values),
+                    );
+                    (name.to_string(), component)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Measure {
+    /// Assigns all the errors of the measure to a named source, see
+    /// [SourcedMeasure].
+    pub fn source(self, name: &str) -> SourcedMeasure {
+        let error = self.error().clone();
+        SourcedMeasure::new(self).with_source(name, error).unwrap()
+    }
+}
+
+impl Display for SourcedMeasure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = unit_suffix(&self.values, " ", Unit::symbol);
+        for i in 0..self.len() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.value()[i])?;
+            for (name, component) in &self.sources {
+                write!(f, " ± {} ({})", component.error()[i], name)?;
+            }
+            write!(f, "{}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Contribution of a source to the total error of a [SourcedMeasure].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetEntry {
+    /// Name of the source.
+    pub source: String,
+    /// Errors of the source.
+    pub error: Vec<f64>,
+    /// Fraction of the total variance due to the source, `σ²/σ_total²`.
+    pub contribution: Vec<f64>,
+}
+
+/// Report of the contribution of every source to the total error of a
+/// [SourcedMeasure], see [SourcedMeasure::error_budget].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBudget {
+    /// Contribution of every source, in the order they were added.
+    pub entries: Vec<BudgetEntry>,
+    /// Total errors.
+    pub total: Vec<f64>,
+}
+
+impl Display for ErrorBudget {
+    /// One line per source with its errors and their percentage of the total
+    /// variance, and a last line with the total errors.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            let errors: Vec<String> = entry
+                .error
+                .iter()
+                .zip(&entry.contribution)
+                .map(|(err, contribution)| format!("{} ({:.1}%)", err, 100.0 * contribution))
+                .collect();
+            writeln!(f, "{}: {}", entry.source, errors.join(", "))?;
+        }
+        let total: Vec<String> = self.total.iter().map(f64::to_string).collect();
+        write!(f, "total: {}", total.join(", "))
+    }
+}
+
+// Operations between measures with separate errors and with numbers, applied
+// with their `map` and `zip_with` methods. Also used by TaggedMeasure.
+macro_rules! impl_sourced_op {
+    ($type:ty, $trait:ident, $method:ident) => {
+        impl $trait<&$type> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: &$type) -> $type {
+                self.zip_with(other, |a, b| a.$method(b))
+            }
+        }
+
+        impl $trait<$type> for $type {
+            type Output = $type;
+
+            fn $method(self, other: $type) -> $type {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<f64> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: f64) -> $type {
+                self.map(|a| a.$method(other))
+            }
+        }
+
+        impl $trait<f64> for $type {
+            type Output = $type;
+
+            fn $method(self, other: f64) -> $type {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+pub(crate) use impl_sourced_op;
+
+impl_sourced_op!(SourcedMeasure, Add, add);
+impl_sourced_op!(SourcedMeasure, Sub, sub);
+impl_sourced_op!(SourcedMeasure, Mul, mul);
+impl_sourced_op!(SourcedMeasure, Div, div);
@@ -0,0 +1,202 @@
+//! Contains the struct DisplayMismatch, used to verify that printing a measure
+//! keeps its values.
+use {
+    crate::{
+        objects::{Measure, Style, SUPERSCRIPT_DIGITS},
+        units::{accepts_prefix, si_exponent},
+    },
+    std::fmt::Display,
+};
+
+/// Element of a measure whose printed value or error differs from the stored
+/// one in more than half a unit in the last place of the printed error, see
+/// [Measure::check_display].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayMismatch {
+    /// Index of the element in the measure.
+    pub index: usize,
+    /// Text printed for the element.
+    pub text: String,
+    /// Value and error read back from the text, None if it could not be read.
+    pub parsed: Option<(f64, f64)>,
+    /// Value and error stored in the measure.
+    pub expected: (f64, f64),
+}
+
+impl Display for DisplayMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.parsed {
+            Some((value, error)) => write!(
+                f,
+                "Element {} printed as \"{}\" reads {} ± {}, expected {} ± {}.",
+                self.index, self.text, value, error, self.expected.0, self.expected.1
+            ),
+            None => write!(
+                f,
+                "Element {} printed as \"{}\" can't be read, expected {} ± {}.",
+                self.index, self.text, self.expected.0, self.expected.1
+            ),
+        }
+    }
+}
+
+/// Prints every element of a measure with a style and reads it back.
+pub(crate) fn check_display(measure: &Measure, style: Style) -> Vec<DisplayMismatch> {
+    let texts: Vec<String> = match style {
+        // The elements depend on each other, so they are printed together.
+        Style::Aligned => measure
+            .clone()
+            .change_style(style)
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect(),
+        _ => measure
+            .list_of_measures()
+            .into_iter()
+            .map(|element| element.change_style(style).to_string())
+            .collect(),
+    };
+    texts
+        .into_iter()
+        .zip(measure.iter())
+        .enumerate()
+        .filter_map(|(index, (text, (value, error)))| {
+            let parsed = parse(&text, style, measure);
+            let consistent = parsed.is_some_and(|(val, err, tolerance)| {
+                close(val, *value, tolerance) && close(err, *error, tolerance)
+            });
+            (!consistent).then(|| DisplayMismatch {
+                index,
+                parsed: parsed.map(|(val, err, _)| (val, err)),
+                text,
+                expected: (*value, *error),
+            })
+        })
+        .collect()
+}
+
+fn close(parsed: f64, expected: f64, tolerance: f64) -> bool {
+    (parsed.is_nan() && expected.is_nan())
+        || parsed == expected
+        || (parsed - expected).abs() <= tolerance.max(4.0 * f64::EPSILON * expected.abs())
+}
+
+/// Reads the value and error of a printed element, along with half a unit in
+/// the last place of the error, or of the value if the error is zero.
+fn parse(text: &str, style: Style, measure: &Measure) -> Option<(f64, f64, f64)> {
+    let Some((left, right)) = ["±", "\\pm", "plus.minus"]
+        .iter()
+        .find_map(|separator| text.split_once(separator))
+    else {
+        return parse_compact(text);
+    };
+    let (value, value_decimals) = number(left.trim_start_matches(['$', '(', '[', ' ']))?;
+    let (error, error_decimals) = number(right.trim_start_matches(['$', '(', '[', ' ']))?;
+
+    let mut exponent = match right.split_once(")e") {
+        Some((_, rest)) => rest[..rest.find(' ').unwrap_or(rest.len())]
+            .parse::<i32>()
+            .ok()?,
+        None => power_of_ten(right).unwrap_or(0),
+    };
+    if style == Style::SI && !measure.unit().is_dimensionless() {
+        // The prefixes of the mass are applied to the gram.
+        let symbol = match measure.unit().symbol() {
+            symbol if symbol == "kg" => {
+                exponent -= 3;
+                String::from("g")
+            }
+            symbol => symbol,
+        };
+        if accepts_prefix(&symbol) {
+            let prefix = left.split_whitespace().nth(1)?.strip_suffix(&symbol)?;
+            exponent += si_exponent(prefix)?;
+        }
+    }
+
+    let scale = 10.0_f64.powi(exponent);
+    let decimals = if error != 0.0 {
+        error_decimals
+    } else {
+        value_decimals
+    };
+    Some((
+        value * scale,
+        error * scale,
+        0.5 * 10.0_f64.powi(-decimals) * scale,
+    ))
+}
+
+/// Reads an element printed with a compact style, e.g. "1.234(5)e-3", whose
+/// error is in units of the last digit of the value.
+fn parse_compact(text: &str) -> Option<(f64, f64, f64)> {
+    let (left, right) = text.trim_start_matches('$').split_once('(')?;
+    let (digits, rest) = right.split_once(')')?;
+    let (value, decimals) = number(left)?;
+    let error = match digits.parse::<f64>().ok()? {
+        error if error.is_finite() => error * 10.0_f64.powi(-decimals),
+        error => error,
+    };
+    let exponent = match rest.strip_prefix(['e', 'E']) {
+        Some(rest) => rest[..rest.find(['$', ' ']).unwrap_or(rest.len())]
+            .parse::<i32>()
+            .ok()?,
+        None => 0,
+    };
+    let scale = 10.0_f64.powi(exponent);
+    Some((
+        value * scale,
+        error * scale,
+        0.5 * 10.0_f64.powi(-decimals) * scale,
+    ))
+}
+
+/// Reads the power of ten factored out by the scientific styles, written as
+/// "×10⁻³", "\times 10^{-3}" or "times 10^(-3)".
+fn power_of_ten(text: &str) -> Option<i32> {
+    if let Some((_, rest)) = text.split_once("×10") {
+        let power: String = rest
+            .chars()
+            .map_while(
+                |c| match SUPERSCRIPT_DIGITS.iter().position(|digit| *digit == c) {
+                    Some(digit) => char::from_digit(digit as u32, 10),
+                    None => (c == '⁻').then_some('-'),
+                },
+            )
+            .collect();
+        return power.parse().ok();
+    }
+    let (_, rest) = text.split_once("10^")?;
+    let rest = rest.trim_start_matches(['{', '(']);
+    rest[..rest.find(['}', ')'])?].parse().ok()
+}
+
+/// Reads the number at the start of a text, which may be in scientific
+/// notation, returning it along with the power of ten of its last place
+/// negated.
+fn number(text: &str) -> Option<(f64, i32)> {
+    for special in ["NaN", "inf", "-inf"] {
+        if text.starts_with(special) {
+            return Some((special.parse().ok()?, 0));
+        }
+    }
+    let digits = |text: &str| {
+        text.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+            .unwrap_or(text.len())
+    };
+    let mut end = digits(text);
+    let mantissa = &text[..end];
+    let mut exponent = 0;
+    if let Some(rest) = text[end..].strip_prefix('e') {
+        if let Ok(power) = rest[..digits(rest)].parse::<i32>() {
+            exponent = power;
+            end += 1 + digits(rest);
+        }
+    }
+    let decimals = mantissa
+        .split_once('.')
+        .map(|(_, decimals)| decimals.len() as i32)
+        .unwrap_or(0);
+    Some((text[..end].parse().ok()?, decimals - exponent))
+}
@@ -0,0 +1,241 @@
+//! Pretty printing of measures, fit results and tables in the terminal, with
+//! aligned columns and colors to highlight large or invalid errors. Requires
+//! the feature `console`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use ferrilab::{measure, Measure, LinearFit, console::Console};
+//! let time = measure!([0.2, 0.3, 0.4], 0.01).with_name("t");
+//! let position = measure!([2.4, 3.4, 5.1], [0.2, 0.4, 0.9]).with_name("x");
+//!
+//! let console = Console::new();
+//! println!("{}", console.measure(&position));
+//!
+//! let (slope, intercept) = LinearFit::new(&time, &position).fit();
+//! println!("{}", console.fit(&["slope", "intercept"], &[slope, intercept]));
+//! ```
+use {
+    crate::{Measure, ScalarMeasure, Style, Table},
+    std::io::IsTerminal,
+};
+
+const BOLD: &str = "\x1b[1m";
+const ERROR: &str = "\x1b[36m";
+const WARNING: &str = "\x1b[33m";
+const INVALID: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// Printer of measures for the terminal.
+#[derive(Debug, Copy, Clone)]
+pub struct Console {
+    color: bool,
+    warning: f64,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Console {
+    /// Constructs a new Console, using colors only if the standard output is a
+    /// terminal and the variable `NO_COLOR` is not set.
+    pub fn new() -> Self {
+        Console {
+            color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            warning: 0.1,
+        }
+    }
+    /// Enables or disables the colors.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+    /// Relative error above which an error is highlighted as a warning, by
+    /// default 0.1.
+    pub fn warning(mut self, relative_error: f64) -> Self {
+        self.warning = relative_error;
+        self
+    }
+
+    /// Formats a measure with one value per line.
+    pub fn measure(&self, measure: &Measure) -> String {
+        let index = (0..measure.len()).map(|i| i.to_string()).collect();
+        self.render(vec![
+            Column::text("", index),
+            Column::measure(measure.label(), measure.iter()),
+        ])
+    }
+    /// Formats the parameters of a fit along with their names.
+    pub fn fit(&self, names: &[&str], parameters: &[ScalarMeasure]) -> String {
+        assert_eq!(
+            names.len(),
+            parameters.len(),
+            "Expected a name for each parameter, obtained {} and {}.",
+            names.len(),
+            parameters.len()
+        );
+        let names = names.iter().map(|name| name.to_string()).collect();
+        self.render(vec![
+            Column::text("parameter", names),
+            Column::measure(
+                "value",
+                parameters
+                    .iter()
+                    .map(|parameter| (&parameter.value, &parameter.error)),
+            ),
+        ])
+    }
+    /// Formats a table with a column for each measure.
+    pub fn table(&self, table: &Table) -> String {
+        let headers = table.headers(Style::Table);
+        self.render(
+            table
+                .cells()
+                .iter()
+                .enumerate()
+                .map(|(i, measure)| {
+                    Column::measure(headers.get(i).cloned().unwrap_or_default(), measure.iter())
+                })
+                .collect(),
+        )
+    }
+
+    fn render(&self, columns: Vec<Column>) -> String {
+        let rows = columns
+            .iter()
+            .map(|column| column.values.len())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![columns
+            .iter()
+            .map(|column| self.paint(&pad(&column.header, column.width()), BOLD))
+            .collect::<Vec<String>>()];
+        for row in 0..rows {
+            lines.push(
+                columns
+                    .iter()
+                    .map(|column| self.cell(column, row))
+                    .collect(),
+            );
+        }
+        lines
+            .into_iter()
+            .map(|line| line.join("  ").trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    fn cell(&self, column: &Column, row: usize) -> String {
+        let value = column.values.get(row).cloned().unwrap_or_default();
+        let value = format!("{:>1$}", value, column.value_width());
+        let (cell, width) = match column.errors.get(row) {
+            Some((error, quality)) => {
+                let padding = " ".repeat(column.error_width() - error.chars().count());
+                (
+                    format!(
+                        "{} ± {}{}",
+                        value,
+                        self.paint(error, quality.color(self.warning)),
+                        padding
+                    ),
+                    column.value_width() + 3 + column.error_width(),
+                )
+            }
+            None => (value, column.value_width()),
+        };
+        format!("{}{}", cell, " ".repeat(column.width() - width))
+    }
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.color {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// Column of the terminal table, whose values and errors are aligned separately.
+struct Column {
+    header: String,
+    values: Vec<String>,
+    errors: Vec<(String, Quality)>,
+}
+
+impl Column {
+    fn text(header: impl Into<String>, values: Vec<String>) -> Column {
+        Column {
+            header: header.into(),
+            errors: Vec::new(),
+            values,
+        }
+    }
+    fn measure<'a>(
+        header: impl Into<String>,
+        measure: impl Iterator<Item = (&'a f64, &'a f64)>,
+    ) -> Column {
+        let (values, errors) = measure
+            .map(|(val, err)| (val.to_string(), (err.to_string(), Quality::of(*val, *err))))
+            .unzip();
+        Column {
+            header: header.into(),
+            values,
+            errors,
+        }
+    }
+    fn value_width(&self) -> usize {
+        self.values
+            .iter()
+            .map(|value| value.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+    fn error_width(&self) -> usize {
+        self.errors
+            .iter()
+            .map(|error| error.0.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+    fn width(&self) -> usize {
+        let cells = if self.errors.is_empty() {
+            self.value_width()
+        } else {
+            self.value_width() + 3 + self.error_width()
+        };
+        cells.max(self.header.chars().count())
+    }
+}
+
+// How trustworthy is an error, deciding its color.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Quality {
+    Relative(f64),
+    Invalid,
+}
+
+impl Quality {
+    fn of(value: f64, error: f64) -> Quality {
+        if value.is_finite() && error.is_finite() && error >= 0.0 {
+            Quality::Relative(if value == 0.0 {
+                0.0
+            } else {
+                (error / value).abs()
+            })
+        } else {
+            Quality::Invalid
+        }
+    }
+    fn color(self, warning: f64) -> &'static str {
+        match self {
+            Quality::Relative(relative) if relative > warning => WARNING,
+            Quality::Relative(_) => ERROR,
+            Quality::Invalid => INVALID,
+        }
+    }
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!("{:<1$}", text, width)
+}
@@ -0,0 +1,260 @@
+//! Contains the struct Dataset, pairs of x and y measures of a signal.
+use crate::{objects::Measure, random::Rng};
+
+/// Diferent methods to obtain the values of a [Dataset] between its points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResampleMethod {
+    /// Value and error of the closest point.
+    Nearest,
+    /// Straight line between the two surrounding points.
+    Linear,
+    /// Natural cubic spline through every point. Errors are propagated as in
+    /// the linear method.
+    Spline,
+}
+
+/// Signal stored as a measure of x values, usually time, and a measure of y
+/// values of the same length, sorted by x.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, Dataset, ResampleMethod};
+/// let fast = Dataset::new(measure!([0, 1, 2, 3, 4], 0.01), measure!([0, 2, 4, 6, 8], 0.1));
+/// let slow = Dataset::new(measure!([0, 2, 4], 0.01), measure!([1, 2, 3], 0.1));
+///
+/// let fast = fast.resample_to(slow.x(), ResampleMethod::Linear);
+/// let ratio = fast.y() / slow.y();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    x: Measure,
+    y: Measure,
+}
+
+impl Dataset {
+    /// Constructs a new dataset. Panics if the measures have diferent lengths.
+    pub fn new(x: Measure, y: Measure) -> Dataset {
+        assert_eq!(
+            x.len(),
+            y.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            x.len(),
+            y.len()
+        );
+        Dataset { x, y }
+    }
+    /// Length of the dataset.
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+    /// Checks if the dataset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+    /// Returns the x measure.
+    pub fn x(&self) -> &Measure {
+        &self.x
+    }
+    /// Returns the y measure.
+    pub fn y(&self) -> &Measure {
+        &self.y
+    }
+    /// Returns a tuple (x, y).
+    pub fn unpack(&self) -> (&Measure, &Measure) {
+        (&self.x, &self.y)
+    }
+    /// Returns the points with the given indices, keeping the order of the
+    /// dataset.
+    fn select(&self, indices: &mut [usize]) -> Dataset {
+        indices.sort_unstable();
+        Dataset {
+            x: self.x.reorder(indices),
+            y: self.y.reorder(indices),
+        }
+    }
+    /// Splits the dataset randomly in a calibration dataset with a fraction of
+    /// the points, rounded, and a validation dataset with the rest, so a fit
+    /// can be checked against points it has not seen. The same seed always
+    /// gives the same split.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Dataset};
+    /// let data = Dataset::new(measure!([0, 1, 2, 3, 4], 0.1), measure!([1, 3, 5, 7, 9], 0.2));
+    /// let (calibration, validation) = data.split(0.6, 7);
+    ///
+    /// assert_eq!(calibration.len(), 3);
+    /// assert_eq!(validation.len(), 2);
+    /// ```
+    pub fn split(&self, fraction: f64, seed: u64) -> (Dataset, Dataset) {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "The fraction must be between 0 and 1, obtained {}.",
+            fraction
+        );
+        let mut indices = Rng::new(seed).permutation(self.len());
+        let (calibration, validation) =
+            indices.split_at_mut((fraction * self.len() as f64).round() as usize);
+        (self.select(calibration), self.select(validation))
+    }
+    /// Iterates over the k folds of the dataset, returning for each one the
+    /// pair (calibration, validation), where the validation dataset is the fold
+    /// and the calibration dataset the rest of the points. The points are
+    /// assigned randomly to folds of sizes differing at most in one. Panics if
+    /// k is 0 or greater than the length.
+    pub fn k_folds(&self, k: usize, seed: u64) -> impl Iterator<Item = (Dataset, Dataset)> + '_ {
+        assert!(
+            k > 0 && k <= self.len(),
+            "The number of folds must be between 1 and {}, obtained {}.",
+            self.len(),
+            k
+        );
+        let indices = Rng::new(seed).permutation(self.len());
+        (0..k).map(move |fold| {
+            let mut validation: Vec<usize> =
+                indices.iter().skip(fold).step_by(k).copied().collect();
+            let mut calibration: Vec<usize> = indices
+                .iter()
+                .enumerate()
+                .filter(|(position, _)| position % k != fold)
+                .map(|(_, i)| *i)
+                .collect();
+            (self.select(&mut calibration), self.select(&mut validation))
+        })
+    }
+    /// Root mean square of the residuals of every point when predicted by a
+    /// model calibrated without it, with k-fold cross validation. The function
+    /// fits a calibration dataset and returns the model fitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Dataset, LinearFit};
+    /// let data = Dataset::new(
+    ///     measure!([0, 1, 2, 3, 4, 5], 0.1),
+    ///     measure!([1.1, 2.9, 5.2, 6.8, 9.1, 11.0], 0.2),
+    /// );
+    /// let error = data.cross_validate(3, 1, |calibration| {
+    ///     let (slope, intercept) = LinearFit::new(calibration.x(), calibration.y()).fit();
+    ///     move |x| slope.value * x + intercept.value
+    /// });
+    /// assert!(error < 0.5);
+    /// ```
+    pub fn cross_validate<F, G>(&self, k: usize, seed: u64, fit: F) -> f64
+    where
+        F: Fn(&Dataset) -> G,
+        G: Fn(f64) -> f64,
+    {
+        let squares: f64 = self
+            .k_folds(k, seed)
+            .map(|(calibration, validation)| {
+                let model = fit(&calibration);
+                validation
+                    .x
+                    .value()
+                    .iter()
+                    .zip(validation.y.value())
+                    .map(|(x, y)| (y - model(*x)).powi(2))
+                    .sum::<f64>()
+            })
+            .sum();
+        (squares / self.len() as f64).sqrt()
+    }
+    /// Obtains the y values at new x values, so signals recorded at diferent
+    /// rates can be operated element-wise. The x values of the dataset must be
+    /// increasing and the new ones must lie within their range.
+    pub fn resample_to(&self, new_x: &Measure, method: ResampleMethod) -> Dataset {
+        assert!(
+            self.len() >= 2,
+            "Resampling requires at least 2 points, obtained {}.",
+            self.len()
+        );
+        let xs = self.x.value();
+        let ys = self.y.value();
+        let errs = self.y.error();
+        assert!(
+            xs.windows(2).all(|pair| pair[0] < pair[1]),
+            "The x values of the dataset must be increasing."
+        );
+        let (first, last) = (xs[0], xs[xs.len() - 1]);
+        let second_derivatives = match method {
+            ResampleMethod::Spline => spline_second_derivatives(xs, ys),
+            _ => Vec::new(),
+        };
+
+        let y: Measure = new_x
+            .value()
+            .iter()
+            .map(|val| {
+                assert!(
+                    (first..=last).contains(val),
+                    "Resampling points must lie within {} and {}, obtained {}.",
+                    first,
+                    last,
+                    val
+                );
+                let i = xs.partition_point(|x| x <= val).clamp(1, xs.len() - 1) - 1;
+                let h = xs[i + 1] - xs[i];
+                let t = (val - xs[i]) / h;
+                let linear_error = ((1.0 - t) * errs[i]).hypot(t * errs[i + 1]);
+                match method {
+                    ResampleMethod::Nearest => {
+                        let j = if t < 0.5 { i } else { i + 1 };
+                        (ys[j], errs[j])
+                    }
+                    ResampleMethod::Linear => ((1.0 - t) * ys[i] + t * ys[i + 1], linear_error),
+                    ResampleMethod::Spline => {
+                        let (m0, m1) = (second_derivatives[i], second_derivatives[i + 1]);
+                        (
+                            (1.0 - t) * ys[i]
+                                + t * ys[i + 1]
+                                + h.powi(2) / 6.0
+                                    * (((1.0 - t).powi(3) - (1.0 - t)) * m0 + (t.powi(3) - t) * m1),
+                            linear_error,
+                        )
+                    }
+                }
+            })
+            .collect::<Measure>()
+            .with_unit(self.y.unit().clone())
+            .with_metadata(self.y.metadata().clone())
+            .change_style(*self.y.style())
+            .derived("resample", &[&self.y, new_x], &[]);
+
+        Dataset {
+            x: new_x.clone(),
+            y: match self.y.name() {
+                Some(name) => y.with_name(name),
+                None => y,
+            },
+        }
+    }
+}
+
+/// Second derivatives at every point of the natural cubic spline, solving the
+/// tridiagonal system with the Thomas algorithm.
+fn spline_second_derivatives(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut second_derivatives = vec![0.0; n];
+    if n < 3 {
+        return second_derivatives;
+    }
+    let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+        rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+    }
+    for i in 2..n - 1 {
+        let factor = h[i - 1] / diagonal[i - 1];
+        diagonal[i] -= factor * h[i - 1];
+        rhs[i] -= factor * rhs[i - 1];
+    }
+    for i in (1..n - 1).rev() {
+        second_derivatives[i] = (rhs[i] - h[i] * second_derivatives[i + 1]) / diagonal[i];
+    }
+    second_derivatives
+}
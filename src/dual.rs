@@ -0,0 +1,222 @@
+//! Contains the struct Dual, used to differentiate functions automatically.
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Dual number `a + bε` with `ε² = 0`, that carries the derivative of any
+/// expression along with its value. It has the same math functions as `f64`,
+/// so the expression is written as usual, see [Measure::propagate](crate::Measure::propagate).
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::Dual;
+/// let x = Dual::variable(2.0);
+/// let y = x * x.sin() + 1.0;
+///
+/// assert_eq!(y.value, 2.0 * 2.0_f64.sin() + 1.0);
+/// assert_eq!(y.derivative, 2.0_f64.sin() + 2.0 * 2.0_f64.cos());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Dual {
+    /// Value of the expression.
+    pub value: f64,
+    /// Derivative of the expression with respect to the variable.
+    pub derivative: f64,
+}
+
+impl Dual {
+    /// Constructs a new dual number.
+    pub fn new(value: f64, derivative: f64) -> Dual {
+        Dual { value, derivative }
+    }
+    /// Variable to differentiate with respect to, whose derivative is 1.
+    pub fn variable(value: f64) -> Dual {
+        Dual::new(value, 1.0)
+    }
+    /// Constant, whose derivative is 0.
+    pub fn constant(value: f64) -> Dual {
+        Dual::new(value, 0.0)
+    }
+    // Applies a function knowing its derivative at the value.
+    fn chain(self, value: f64, derivative: f64) -> Dual {
+        Dual::new(value, derivative * self.derivative)
+    }
+
+    /// Square root.
+    pub fn sqrt(self) -> Dual {
+        let root = self.value.sqrt();
+        self.chain(root, 0.5 / root)
+    }
+    /// Cube root.
+    pub fn cbrt(self) -> Dual {
+        let root = self.value.cbrt();
+        self.chain(root, 1.0 / (3.0 * root.powi(2)))
+    }
+    /// Raises a dual number to an integer.
+    pub fn powi(self, n: i32) -> Dual {
+        self.chain(self.value.powi(n), n as f64 * self.value.powi(n - 1))
+    }
+    /// Raises a dual number to any number.
+    pub fn powf(self, n: f64) -> Dual {
+        self.chain(self.value.powf(n), n * self.value.powf(n - 1.0))
+    }
+    /// Raises a dual number to another one.
+    pub fn pow(self, other: Dual) -> Dual {
+        (other * self.ln()).exp()
+    }
+    /// Inverse, `1/x`.
+    pub fn recip(self) -> Dual {
+        self.chain(self.value.recip(), -self.value.powi(-2))
+    }
+    /// Absolute value.
+    pub fn abs(self) -> Dual {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+    /// Exponential.
+    pub fn exp(self) -> Dual {
+        let exp = self.value.exp();
+        self.chain(exp, exp)
+    }
+    /// Natural logarithm.
+    pub fn ln(self) -> Dual {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+    /// Logarithm in base 10.
+    pub fn log10(self) -> Dual {
+        self.chain(
+            self.value.log10(),
+            (self.value * std::f64::consts::LN_10).recip(),
+        )
+    }
+    /// Logarithm in base 2.
+    pub fn log2(self) -> Dual {
+        self.chain(
+            self.value.log2(),
+            (self.value * std::f64::consts::LN_2).recip(),
+        )
+    }
+    /// Sine.
+    pub fn sin(self) -> Dual {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+    /// Cosine.
+    pub fn cos(self) -> Dual {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+    /// Tangent.
+    pub fn tan(self) -> Dual {
+        self.chain(self.value.tan(), self.value.cos().powi(-2))
+    }
+    /// Arcsine.
+    pub fn asin(self) -> Dual {
+        self.chain(self.value.asin(), (1.0 - self.value.powi(2)).sqrt().recip())
+    }
+    /// Arccosine.
+    pub fn acos(self) -> Dual {
+        self.chain(
+            self.value.acos(),
+            -(1.0 - self.value.powi(2)).sqrt().recip(),
+        )
+    }
+    /// Arctangent.
+    pub fn atan(self) -> Dual {
+        self.chain(self.value.atan(), (1.0 + self.value.powi(2)).recip())
+    }
+    /// Four quadrant arctangent of `self / other`.
+    pub fn atan2(self, other: Dual) -> Dual {
+        let norm = self.value.powi(2) + other.value.powi(2);
+        Dual::new(
+            self.value.atan2(other.value),
+            (other.value * self.derivative - self.value * other.derivative) / norm,
+        )
+    }
+    /// Hyperbolic sine.
+    pub fn sinh(self) -> Dual {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+    /// Hyperbolic cosine.
+    pub fn cosh(self) -> Dual {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+    /// Hyperbolic tangent.
+    pub fn tanh(self) -> Dual {
+        self.chain(self.value.tanh(), self.value.cosh().powi(-2))
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Dual {
+        Dual::constant(value)
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+
+    fn neg(self) -> Dual {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+
+    fn add(self, other: Dual) -> Dual {
+        Dual::new(self.value + other.value, self.derivative + other.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+
+    fn sub(self, other: Dual) -> Dual {
+        Dual::new(self.value - other.value, self.derivative - other.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+
+    fn mul(self, other: Dual) -> Dual {
+        Dual::new(
+            self.value * other.value,
+            self.derivative * other.value + self.value * other.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+
+    fn div(self, other: Dual) -> Dual {
+        Dual::new(
+            self.value / other.value,
+            (self.derivative * other.value - self.value * other.derivative) / other.value.powi(2),
+        )
+    }
+}
+
+// Operations with numbers, in both orders.
+macro_rules! impl_dual_op_number {
+    ($trait:ident, $method:ident) => {
+        impl $trait<f64> for Dual {
+            type Output = Dual;
+
+            fn $method(self, other: f64) -> Dual {
+                self.$method(Dual::constant(other))
+            }
+        }
+
+        impl $trait<Dual> for f64 {
+            type Output = Dual;
+
+            fn $method(self, other: Dual) -> Dual {
+                Dual::constant(self).$method(other)
+            }
+        }
+    };
+}
+
+impl_dual_op_number!(Add, add);
+impl_dual_op_number!(Sub, sub);
+impl_dual_op_number!(Mul, mul);
+impl_dual_op_number!(Div, div);
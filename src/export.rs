@@ -0,0 +1,191 @@
+//! Export of the tables and figures of a report, keeping an index of every file
+//! written.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use ferrilab::{measure, Measure, Scatter, Table, export::Manifest};
+//! let time = measure!([0.2, 0.3, 0.4], 0.01);
+//! let position = measure!([2.4, 3.4, 5.1], 0.2);
+//! let mut manifest = Manifest::new();
+//!
+//! let table = Table::new(vec![time.clone(), position.clone()], vec!["t/s", "x/m"])
+//!     .caption("Positions")
+//!     .label("tab:positions");
+//! manifest.write_table("report/positions.tex", table).unwrap();
+//!
+//! Scatter::from_measures(&time, &position).scatter().unwrap();
+//! manifest.save_figure("report/positions.png", "Positions", "fig:positions").unwrap();
+//!
+//! manifest.write_latex("report/artifacts.tex").unwrap();
+//! manifest.write_json("report/artifacts.json").unwrap();
+//! ```
+use {
+    crate::{save, PlotError, SavedFigure, Table},
+    std::{
+        fs,
+        io::Error,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Diferent kinds of files of a report.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// Latex or typst table.
+    Table,
+    /// Image of a figure.
+    Figure,
+}
+
+/// File written for a report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifact {
+    /// Kind of the file.
+    pub kind: ArtifactKind,
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Caption to show in the report, if any.
+    pub caption: Option<String>,
+    /// Label to reference it in the report, if any.
+    pub label: Option<String>,
+}
+
+/// Record of the tables and figures written during a run, that can be written
+/// as an index in JSON or latex.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    artifacts: Vec<Artifact>,
+}
+
+impl Manifest {
+    /// Constructs an empty Manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the artifacts recorded, in the order they were written.
+    pub fn artifacts(&self) -> &[Artifact] {
+        &self.artifacts
+    }
+    /// Records a file written without the manifest.
+    pub fn record(&mut self, artifact: Artifact) {
+        self.artifacts.push(artifact);
+    }
+
+    /// Writes a table, in typst format if the extension of the file is `typ`
+    /// and in latex format otherwise, recording its caption and label.
+    pub fn write_table(&mut self, path: impl AsRef<Path>, table: Table) -> Result<(), Error> {
+        let path = path.as_ref();
+        let (caption, label) = (
+            table.get_caption().map(String::from),
+            table.get_label().map(String::from),
+        );
+        let text = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("typ") => table.typst(),
+            _ => table.latex(),
+        };
+        fs::write(path, text)?;
+        self.record(Artifact {
+            kind: ArtifactKind::Table,
+            path: path.to_path_buf(),
+            caption,
+            label,
+        });
+        Ok(())
+    }
+    /// Saves the current figure, recording its caption and label.
+    pub fn save_figure(
+        &mut self,
+        path: &str,
+        caption: &str,
+        label: &str,
+    ) -> Result<SavedFigure, PlotError> {
+        let figure = save(path)?;
+        self.record(Artifact {
+            kind: ArtifactKind::Figure,
+            path: figure.path.clone(),
+            caption: Some(caption.to_string()),
+            label: Some(label.to_string()),
+        });
+        Ok(figure)
+    }
+
+    /// Index of the artifacts as a JSON array.
+    pub fn json(&self) -> String {
+        let items: Vec<String> = self
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                format!(
+                    "  {{\"kind\": \"{}\", \"path\": {}, \"caption\": {}, \"label\": {}}}",
+                    match artifact.kind {
+                        ArtifactKind::Table => "table",
+                        ArtifactKind::Figure => "figure",
+                    },
+                    json_string(Some(&artifact.path.to_string_lossy())),
+                    json_string(artifact.caption.as_deref()),
+                    json_string(artifact.label.as_deref()),
+                )
+            })
+            .collect();
+        if items.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", items.join(",\n"))
+        }
+    }
+    /// Index of the artifacts in latex, with an `\input` for every table and a
+    /// figure environment for every figure.
+    pub fn latex(&self) -> String {
+        self.artifacts
+            .iter()
+            .map(|artifact| {
+                let path = artifact.path.to_string_lossy().replace('\\', "/");
+                match artifact.kind {
+                    ArtifactKind::Table => format!("\\input{{{}}}\n", path),
+                    ArtifactKind::Figure => format!(
+                        "\\begin{{figure}}[ht]\n \\centering\n \\includegraphics[width=\\linewidth]{{{}}}\n{}{}\\end{{figure}}\n",
+                        path,
+                        artifact
+                            .caption
+                            .as_ref()
+                            .map(|caption| format!(" \\caption{{{}}}\n", caption))
+                            .unwrap_or_default(),
+                        artifact
+                            .label
+                            .as_ref()
+                            .map(|label| format!(" \\label{{{}}}\n", label))
+                            .unwrap_or_default(),
+                    ),
+                }
+            })
+            .collect()
+    }
+    /// Writes the index of the artifacts as JSON.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.json())
+    }
+    /// Writes the index of the artifacts in latex.
+    pub fn write_latex(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.latex())
+    }
+}
+
+fn json_string(text: Option<&str>) -> String {
+    let Some(text) = text else {
+        return "null".to_string();
+    };
+    let mut escaped = String::from('"');
+    for char in text.chars() {
+        match char {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\t' => escaped += "\\t",
+            char if char.is_control() => escaped += &format!("\\u{:04x}", char as u32),
+            char => escaped.push(char),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -1,4 +1,27 @@
-use crate::Measure;
+use {
+    crate::{
+        aprox::round,
+        perf::{count, Counter},
+        plot::{xscale, yscale, Plot, PlotError},
+        random::Rng,
+        special::chi_squared_upper_tail,
+        Dataset, Measure, ScalarMeasure,
+    },
+    std::{fmt::Debug, rc::Rc},
+};
+
+/// Algorithms available to minimize the χ² of a [CurveFit].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Optimizer {
+    /// Nelder–Mead simplex, which only evaluates the χ² and is robust far
+    /// from the minimum, see [CurveFit::initial_simplex_scale].
+    #[default]
+    NelderMead,
+    /// BFGS quasi-Newton method with numerical gradients, which converges in
+    /// far fewer evaluations for models with many parameters when the initial
+    /// point is reasonable.
+    Bfgs,
+}
 
 /// Object to create a CurveFit with all required parameters.
 #[derive(Debug, Clone)]
@@ -7,10 +30,13 @@ pub struct CurveFit<F: Fn(&f64, &[f64]) -> f64> {
     x_values: Vec<f64>,
     y_values: Vec<f64>,
     yerr: Vec<f64>,
+    xerr: Option<Vec<f64>>,
     initial_point: Vec<f64>,
     tolerance: f64,
     max_iterations: Option<usize>,
     initial_simplex_scale: f64,
+    fixed: Vec<(usize, f64)>,
+    optimizer: Optimizer,
 }
 
 impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
@@ -23,10 +49,22 @@ impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
             x_values,
             y_values: y_values.into(),
             yerr: vec![1.0; n],
+            xerr: None,
             initial_point: Vec::new(),
             tolerance: 1e-6,
             max_iterations: None,
             initial_simplex_scale: 0.5,
+            fixed: Vec::new(),
+            optimizer: Optimizer::NelderMead,
+        }
+    }
+    /// Constructs a new CurveFit of the points of a dataset, weighted by the
+    /// errors of y when all of them are positive.
+    pub fn from_dataset(model: F, data: &Dataset) -> Self {
+        let fit = CurveFit::new(model, data.x(), data.y());
+        match positive_errors(data.y()) {
+            Some(yerr) => fit.y_error(yerr),
+            None => fit,
         }
     }
     /// Initial points required for calculating the curve fit.
@@ -39,6 +77,28 @@ impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
         self.yerr = yerr;
         self
     }
+    /// If passed, considers the x error with the effective variance method,
+    /// adding to the variance of every y the one of x propagated through the
+    /// slope of the model, `σ² = σy² + (f'(x)·σx)²`, and fitting again until
+    /// the effective errors converge. The y error should be given too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::CurveFit;
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    /// let y = [0.1, 1.1, 3.9, 9.2, 15.9];
+    /// let fit = CurveFit::new(|x: &f64, p: &[f64]| p[0] * x * x, x, y)
+    ///     .y_error(vec![0.1; 5])
+    ///     .x_error(vec![0.05; 5])
+    ///     .initial_ones(1);
+    ///
+    /// assert!((fit.fit()[0].value - 1.0).abs() < 0.05);
+    /// ```
+    pub fn x_error(mut self, xerr: Vec<f64>) -> Self {
+        self.xerr = Some(xerr);
+        self
+    }
 
     /// In case you want the curve fit algorithm to stop at some point, by default None.
     pub fn max_iterations(mut self, max_iterations: impl Into<Option<usize>>) -> Self {
@@ -47,16 +107,12 @@ impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
     }
     /// Set initial points to zero.
     pub fn initial_zeros(mut self, number_of_components: usize) -> Self {
-        self.initial_point = std::iter::repeat(0.0_f64)
-            .take(number_of_components)
-            .collect::<Vec<_>>();
+        self.initial_point = std::iter::repeat_n(0.0_f64, number_of_components).collect::<Vec<_>>();
         self
     }
     /// Set initial points to one.
     pub fn initial_ones(mut self, number_of_components: usize) -> Self {
-        self.initial_point = std::iter::repeat(1.0_f64)
-            .take(number_of_components)
-            .collect();
+        self.initial_point = std::iter::repeat_n(1.0_f64, number_of_components).collect();
         self
     }
     /// Custom precision on the curve fit, by default 1e-6.
@@ -72,21 +128,196 @@ impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
         self.initial_simplex_scale = scale.into();
         self
     }
+    /// Algorithm used to minimize the χ², by default [Optimizer::NelderMead].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{CurveFit, Optimizer};
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    /// let y = [1.0, 2.2, 5.1, 10.3, 16.8];
+    /// let fit = CurveFit::new(|x: &f64, p: &[f64]| p[0] * x * x + p[1] * x + p[2], x, y)
+    ///     .initial_zeros(3)
+    ///     .optimizer(Optimizer::Bfgs);
+    ///
+    /// assert!((fit.fit()[0].value - 1.0).abs() < 0.1);
+    /// ```
+    pub fn optimizer(mut self, optimizer: Optimizer) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+    /// Holds a parameter constant at a value while the rest are optimized,
+    /// e.g. a known background. Its error and covariances are reported as
+    /// zero and it doesn't count for the degrees of freedom.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::CurveFit;
+    /// let x = [0.0, 1.0, 2.0, 3.0];
+    /// let y = [1.1, 3.0, 4.9, 7.1];
+    /// let fit = CurveFit::new(|x: &f64, p: &[f64]| p[0] * x + p[1], x, y)
+    ///     .initial_ones(2)
+    ///     .fix_parameter(1, 1.0);
+    /// let result = fit.fit_result();
+    ///
+    /// assert_eq!(result.parameters()[1].value, 1.0);
+    /// assert_eq!(result.parameters()[1].error, 0.0);
+    /// assert_eq!(result.dof(), 3);
+    /// ```
+    pub fn fix_parameter(mut self, index: usize, value: f64) -> Self {
+        self.fixed.retain(|(fixed, _)| *fixed != index);
+        self.fixed.push((index, value));
+        self
+    }
 
     /// Takes the arbitrary function and aproximates to the curve using
-    /// every parameter established.
-    pub fn fit(&self) -> Vec<Measure> {
-        curve_fit(
-            &self.model,
-            &self.x_values,
-            &self.y_values,
-            &self.yerr,
-            &self.initial_point,
-            self.max_iterations,
-            self.tolerance,
-            self.initial_simplex_scale,
+    /// every parameter established. Shorthand for the parameters of
+    /// [CurveFit::fit_result].
+    pub fn fit(&self) -> Vec<ScalarMeasure> {
+        self.fit_result().parameters().to_vec()
+    }
+    /// Fits the curve, returning the parameters with their covariance matrix
+    /// and the goodness of the fit, see [FitResult].
+    pub fn fit_result(&self) -> FitResult<'_> {
+        self.result_of(self)
+    }
+    /// Fits the curve with RANSAC, see [LinearFit::ransac]. The random
+    /// subsets have as many points as free parameters.
+    pub fn ransac(
+        &self,
+        threshold: f64,
+        iterations: usize,
+        seed: u64,
+    ) -> (FitResult<'_>, Vec<bool>) {
+        let sample = (self.initial_point.len() - self.fixed.len()).max(1);
+        ransac(
+            (&self.x_values, &self.y_values),
+            sample,
+            threshold,
+            (iterations, seed),
+            |indices| self.result_of(&self.select(indices)),
         )
     }
+    // Fits the points of a CurveFit with the same model.
+    fn result_of<G: Fn(&f64, &[f64]) -> f64>(&self, points: &CurveFit<G>) -> FitResult<'_> {
+        let (parameters, covariance, yerr, singular) = points.optimize();
+        let mut result = FitResult::new(
+            |x, par| (self.model)(x, par),
+            scalar_parameters(&parameters, &covariance),
+            covariance,
+            (&points.x_values, &points.y_values, &yerr),
+        );
+        result.fixed = self.fixed.len();
+        result.singular = singular;
+        result
+    }
+    // CurveFit of the points with the given indices.
+    fn select(&self, indices: &[usize]) -> CurveFit<&F> {
+        CurveFit {
+            model: &self.model,
+            x_values: pick(&self.x_values, indices),
+            y_values: pick(&self.y_values, indices),
+            yerr: pick(&self.yerr, indices),
+            xerr: self.xerr.as_ref().map(|xerr| pick(xerr, indices)),
+            initial_point: self.initial_point.clone(),
+            tolerance: self.tolerance,
+            max_iterations: self.max_iterations,
+            initial_simplex_scale: self.initial_simplex_scale,
+            fixed: self.fixed.clone(),
+            optimizer: self.optimizer,
+        }
+    }
+    // Parameters, covariance, the errors of y used as weights, which include
+    // the errors of x with the effective variance method, and whether the
+    // Hessian matrix was singular.
+    fn optimize(&self) -> (Vec<f64>, Vec<Vec<f64>>, Vec<f64>, bool) {
+        let fit = |yerr: &[f64], initial_point: &[f64]| {
+            if self.fixed.is_empty() {
+                return curve_fit(
+                    &self.model,
+                    &self.x_values,
+                    &self.y_values,
+                    yerr,
+                    initial_point,
+                    self.max_iterations,
+                    self.tolerance,
+                    self.initial_simplex_scale,
+                    self.optimizer,
+                );
+            }
+            // Only the free parameters are optimized, the fixed ones are
+            // inserted back before calling the model.
+            let free: Vec<usize> = (0..initial_point.len())
+                .filter(|i| self.fixed.iter().all(|(fixed, _)| fixed != i))
+                .collect();
+            let expand = |values: &[f64]| {
+                let mut parameters = initial_point.to_vec();
+                for (i, value) in free.iter().zip(values) {
+                    parameters[*i] = *value;
+                }
+                parameters
+            };
+            let (values, free_covariance, singular) = curve_fit(
+                &|x: &f64, values: &[f64]| (self.model)(x, &expand(values)),
+                &self.x_values,
+                &self.y_values,
+                yerr,
+                &free.iter().map(|i| initial_point[*i]).collect::<Vec<_>>(),
+                self.max_iterations,
+                self.tolerance,
+                self.initial_simplex_scale,
+                self.optimizer,
+            );
+            let mut covariance = vec![vec![0.0; initial_point.len()]; initial_point.len()];
+            for (row, i) in free.iter().enumerate() {
+                for (column, j) in free.iter().enumerate() {
+                    covariance[*i][*j] = free_covariance[row][column];
+                }
+            }
+            (expand(&values), covariance, singular)
+        };
+        let mut initial_point = self.initial_point.clone();
+        for (index, value) in &self.fixed {
+            assert!(
+                *index < initial_point.len(),
+                "Can't fix the parameter {} of a model with {} parameters.",
+                index,
+                initial_point.len()
+            );
+            initial_point[*index] = *value;
+        }
+        let (mut parameters, mut covariance, mut singular) = fit(&self.yerr, &initial_point);
+        let Some(xerr) = &self.xerr else {
+            return (parameters, covariance, self.yerr.clone(), singular);
+        };
+        let mut yerr = self.yerr.clone();
+        for _ in 0..EFFECTIVE_VARIANCE_ITERATIONS {
+            let effective: Vec<f64> = self
+                .x_values
+                .iter()
+                .zip(xerr)
+                .zip(&self.yerr)
+                .map(|((x, sx), sy)| {
+                    let step = 1e-6 * x.abs().max(1.0);
+                    let slope = ((self.model)(&(x + step), &parameters)
+                        - (self.model)(&(x - step), &parameters))
+                        / (2.0 * step);
+                    sy.hypot(slope * sx)
+                })
+                .collect();
+            let converged = effective
+                .iter()
+                .zip(&yerr)
+                .all(|(new, old)| (new - old).abs() <= self.tolerance * old);
+            yerr = effective;
+            (parameters, covariance, singular) = fit(&yerr, &parameters);
+            if converged {
+                break;
+            }
+        }
+        (parameters, covariance, yerr, singular)
+    }
 
     pub fn r_value(&self) -> f64 {
         let parameters = self.fit();
@@ -97,10 +328,7 @@ impl<F: Fn(&f64, &[f64]) -> f64> CurveFit<F> {
             .map(|(x, y)| {
                 y - (self.model)(
                     x,
-                    &parameters
-                        .iter()
-                        .map(|par| par.value()[0])
-                        .collect::<Vec<_>>(),
+                    &parameters.iter().map(|par| par.value).collect::<Vec<_>>(),
                 )
             })
             .map(|r| r.powi(2))
@@ -124,6 +352,7 @@ pub struct LinearFit {
     x_values: Vec<f64>,
     y_values: Vec<f64>,
     yerr: Option<Vec<f64>>,
+    xerr: Option<Vec<f64>>,
 }
 
 impl LinearFit {
@@ -133,6 +362,26 @@ impl LinearFit {
             x_values: x_values.into(),
             y_values: y_values.into(),
             yerr: None,
+            xerr: None,
+        }
+    }
+    /// Constructs a new LinearFit of the points of a dataset, weighted by the
+    /// errors of y when all of them are positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Dataset, LinearFit};
+    /// let data = Dataset::new(measure!([0, 1, 2, 3]), measure!([1, 3, 5, 7], 0.1));
+    /// let (slope, intercept) = LinearFit::from_dataset(&data).fit();
+    ///
+    /// assert!((slope.value - 2.0).abs() < 1e-12);
+    /// assert!((intercept.value - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn from_dataset(data: &Dataset) -> Self {
+        LinearFit {
+            yerr: positive_errors(data.y()),
+            ..LinearFit::new(data.x(), data.y())
         }
     }
     /// If passed, calculates the weigthed curve fit considaring the y error.
@@ -140,15 +389,123 @@ impl LinearFit {
         self.yerr = Some(yerr);
         self
     }
+    /// If passed, calculates the York regression considering the errors of
+    /// both x and y, which minimizes the distance of the points to the line
+    /// weighted in both directions. The y errors are taken as zero if not
+    /// given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::LinearFit;
+    /// let fit = LinearFit::new([0.0, 1.0, 2.0, 3.0], [1.1, 2.9, 5.2, 6.9])
+    ///     .y_error(vec![0.1; 4])
+    ///     .x_error(vec![0.05; 4]);
+    /// let (slope, intercept) = fit.fit();
+    ///
+    /// assert!((slope.value - 1.97).abs() < 0.01);
+    /// ```
+    pub fn x_error(mut self, xerr: Vec<f64>) -> Self {
+        self.xerr = Some(xerr);
+        self
+    }
 
     /// Given the x and y values returns the slope and the intercept of a
-    /// straight line by least squares method or weighted least squares method
-    /// if yerr is given.
-    pub fn fit(&self) -> (Measure, Measure) {
-        if let Some(yerr) = &self.yerr {
-            wlinear_fit(&self.x_values, &self.y_values, yerr)
-        } else {
-            linear_fit(&self.x_values, &self.y_values)
+    /// straight line by least squares method, weighted least squares method
+    /// if yerr is given or York regression if xerr is given. Shorthand for the
+    /// parameters of [LinearFit::fit_result].
+    pub fn fit(&self) -> (ScalarMeasure, ScalarMeasure) {
+        let result = self.fit_result();
+        (result.parameters[0], result.parameters[1])
+    }
+    // Slope and intercept, with the errors of y used as weights, which include
+    // the errors of x projected with the slope in the York regression.
+    fn line(&self) -> (ScalarMeasure, ScalarMeasure, Vec<f64>) {
+        let n = self.x_values.len();
+        match (&self.xerr, &self.yerr) {
+            (Some(xerr), yerr) => {
+                let yerr = yerr.clone().unwrap_or_else(|| vec![0.0; n]);
+                let (slope, intercept) = york_fit(&self.x_values, &self.y_values, xerr, &yerr);
+                let effective = xerr
+                    .iter()
+                    .zip(&yerr)
+                    .map(|(sx, sy)| sy.hypot(slope.value * sx))
+                    .collect();
+                (slope, intercept, effective)
+            }
+            (None, Some(yerr)) => {
+                let (slope, intercept) = wlinear_fit(&self.x_values, &self.y_values, yerr);
+                (slope, intercept, yerr.clone())
+            }
+            (None, None) => {
+                let (slope, intercept) = linear_fit(&self.x_values, &self.y_values);
+                (slope, intercept, vec![1.0; n])
+            }
+        }
+    }
+    /// Fits the line, returning the slope and the intercept, in that order,
+    /// with their covariance matrix and the goodness of the fit, see
+    /// [FitResult].
+    pub fn fit_result(&self) -> FitResult<'static> {
+        let (slope, intercept, yerr) = self.line();
+        let weights: Vec<f64> = yerr.iter().map(|err| err.powi(-2)).collect();
+        let x_mean = self
+            .x_values
+            .iter()
+            .zip(&weights)
+            .map(|(x, w)| x * w)
+            .sum::<f64>()
+            / weights.iter().sum::<f64>();
+        // Both parameters are anticorrelated through the mean of x.
+        let covariance = -x_mean * slope.error.powi(2);
+        FitResult::new(
+            |x, par| par[0] * x + par[1],
+            vec![slope, intercept],
+            vec![
+                vec![slope.error.powi(2), covariance],
+                vec![covariance, intercept.error.powi(2)],
+            ],
+            (&self.x_values, &self.y_values, &yerr),
+        )
+    }
+    /// Fits the line with RANSAC, for data with gross outliers: lines through
+    /// random pairs of points are tried a number of iterations, keeping the
+    /// one with more points within a threshold of absolute residual, the
+    /// inliers. Returns the fit of the inliers and which points are inliers.
+    /// The same seed always gives the same result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::LinearFit;
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = [1.0, 3.1, 4.9, 30.0, 9.0, 11.1];
+    ///
+    /// let (fit, inliers) = LinearFit::new(x, y).ransac(0.5, 50, 1);
+    /// assert_eq!(inliers, vec![true, true, true, false, true, true]);
+    /// assert!((fit.parameters()[0].value - 2.0).abs() < 0.05);
+    /// ```
+    pub fn ransac(
+        &self,
+        threshold: f64,
+        iterations: usize,
+        seed: u64,
+    ) -> (FitResult<'static>, Vec<bool>) {
+        ransac(
+            (&self.x_values, &self.y_values),
+            2,
+            threshold,
+            (iterations, seed),
+            |indices| self.select(indices).fit_result(),
+        )
+    }
+    // LinearFit of the points with the given indices.
+    fn select(&self, indices: &[usize]) -> LinearFit {
+        LinearFit {
+            x_values: pick(&self.x_values, indices),
+            y_values: pick(&self.y_values, indices),
+            yerr: self.yerr.as_ref().map(|yerr| pick(yerr, indices)),
+            xerr: self.xerr.as_ref().map(|xerr| pick(xerr, indices)),
         }
     }
     /// Calculates the coeficient of linear correlation
@@ -169,9 +526,473 @@ impl LinearFit {
     }
 }
 
+/// Object to fit a power law `y = A·x^k` by a linear fit in log-log space.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::PowerLawFit;
+/// let period = [0.9, 1.27, 1.55, 1.8];
+/// let length = [0.2, 0.4, 0.6, 0.8];
+///
+/// let (exponent, prefactor) = PowerLawFit::new(length, period).fit();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PowerLawFit {
+    x_values: Vec<f64>,
+    y_values: Vec<f64>,
+    yerr: Option<Vec<f64>>,
+}
+
+impl PowerLawFit {
+    /// Constructs a new PowerLawFit. Every value must be positive.
+    pub fn new(x_values: impl Into<Vec<f64>>, y_values: impl Into<Vec<f64>>) -> Self {
+        PowerLawFit {
+            x_values: x_values.into(),
+            y_values: y_values.into(),
+            yerr: None,
+        }
+    }
+    /// If passed, weights each point by its relative error, which is the error
+    /// of its logarithm.
+    pub fn y_error(mut self, yerr: Vec<f64>) -> Self {
+        self.yerr = Some(yerr);
+        self
+    }
+
+    /// Returns the exponent `k` and the prefactor `A`. The prefactor is
+    /// obtained as the exponential of the intercept, so its error is `A·σ`.
+    pub fn fit(&self) -> (ScalarMeasure, ScalarMeasure) {
+        assert!(
+            self.x_values
+                .iter()
+                .chain(self.y_values.iter())
+                .all(|val| *val > 0.0),
+            "A power law fit requires positive values."
+        );
+        let log_x: Vec<f64> = self.x_values.iter().map(|x| x.ln()).collect();
+        let log_y: Vec<f64> = self.y_values.iter().map(|y| y.ln()).collect();
+        let (exponent, intercept) = if let Some(yerr) = &self.yerr {
+            let log_err: Vec<f64> = yerr
+                .iter()
+                .zip(self.y_values.iter())
+                .map(|(err, y)| err / y)
+                .collect();
+            wlinear_fit(&log_x, &log_y, &log_err)
+        } else {
+            linear_fit(&log_x, &log_y)
+        };
+        let prefactor = intercept.value.exp();
+        (
+            exponent,
+            ScalarMeasure::new(prefactor, prefactor * intercept.error),
+        )
+    }
+    /// Evaluates the fitted power law at `points` values of x, evenly spaced
+    /// in log scale over the range of the data.
+    pub fn curve(&self, points: usize) -> (Vec<f64>, Vec<f64>) {
+        let (exponent, prefactor) = self.fit();
+        let (min, max) = self
+            .x_values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                (min.min(*x), max.max(*x))
+            });
+        let step = (max / min).ln() / (points.max(2) - 1) as f64;
+        let x: Vec<f64> = (0..points).map(|i| min * (step * i as f64).exp()).collect();
+        let y = x
+            .iter()
+            .map(|x| prefactor.value * x.powf(exponent.value))
+            .collect();
+        (x, y)
+    }
+    /// Draws the fitted power law over the current figure and sets both axes
+    /// to log scale, where it is a straight line.
+    pub fn plot(&self) -> Result<(), PlotError> {
+        let (x, y) = self.curve(100);
+        Plot::new(x, y).color("tab:red").plot()?;
+        xscale("log")?;
+        yscale("log")
+    }
+}
+
+/// Result of a fit, with the parameters, their covariance matrix, the
+/// residuals and the model, so the fitted curve can be evaluated with the
+/// error propagated from the parameters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{LinearFit, Measure};
+/// let fit = LinearFit::new([0.0, 1.0, 2.0, 3.0], [1.1, 2.9, 5.2, 6.9])
+///     .y_error(vec![0.1; 4])
+///     .fit_result();
+/// let curve: Measure = fit.evaluate(&[0.5, 1.5, 2.5]);
+///
+/// assert_eq!(curve.len(), 3);
+/// assert!(curve.error()[1] < curve.error()[0]);
+/// assert_eq!(fit.dof(), 2);
+/// assert!(fit.chi2() < 10.0);
+/// ```
+#[derive(Clone)]
+pub struct FitResult<'a> {
+    model: Rc<Model<'a>>,
+    parameters: Vec<ScalarMeasure>,
+    covariance: Vec<Vec<f64>>,
+    residuals: Vec<f64>,
+    chi2: f64,
+    fixed: usize,
+    singular: bool,
+}
+
+impl<'a> FitResult<'a> {
+    /// Computes the residuals and the χ² of the fitted parameters over the
+    /// data `(x, y, yerr)`.
+    fn new(
+        model: impl Fn(&f64, &[f64]) -> f64 + 'a,
+        parameters: Vec<ScalarMeasure>,
+        covariance: Vec<Vec<f64>>,
+        (x, y, yerr): (&[f64], &[f64], &[f64]),
+    ) -> Self {
+        let values: Vec<f64> = parameters.iter().map(|par| par.value).collect();
+        let residuals: Vec<f64> = x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| y - model(x, &values))
+            .collect();
+        let chi2 = residuals
+            .iter()
+            .zip(yerr)
+            .map(|(res, err)| (res / err).powi(2))
+            .sum();
+        FitResult {
+            model: Rc::new(model),
+            parameters,
+            covariance,
+            residuals,
+            chi2,
+            fixed: 0,
+            singular: false,
+        }
+    }
+    /// Returns the fitted parameters.
+    pub fn parameters(&self) -> &[ScalarMeasure] {
+        &self.parameters
+    }
+    /// Returns the covariance matrix of the parameters.
+    pub fn covariance(&self) -> &Vec<Vec<f64>> {
+        &self.covariance
+    }
+    /// Returns true if the Hessian matrix of the χ² has no inverse, e.g. when
+    /// the model doesn't depend on some parameter, so the covariance and the
+    /// errors of the free parameters couldn't be computed and are NaN.
+    pub fn singular_covariance(&self) -> bool {
+        self.singular
+    }
+    /// Returns the residuals `y - f(x)` of every point.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+    /// Returns the sum of the squared residuals divided by the variances of
+    /// y, `χ² = Σ((y - f(x))/σ)²`, or without dividing for an unweighted fit.
+    pub fn chi2(&self) -> f64 {
+        self.chi2
+    }
+    /// Returns the χ² divided by the degrees of freedom, close to 1 for a good
+    /// fit with the errors of y well estimated.
+    pub fn reduced_chi2(&self) -> f64 {
+        self.chi2 / self.dof() as f64
+    }
+    /// Returns the probability of obtaining a χ² at least as large if the
+    /// model is correct and the errors of y are gaussian, so a very small
+    /// value rejects the model. It is NaN without degrees of freedom. Only
+    /// meaningful for fits weighted by the errors of y.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::LinearFit;
+    /// let fit = LinearFit::new([0.0, 1.0, 2.0, 3.0, 4.0], [1.1, 2.9, 5.2, 6.9, 9.1])
+    ///     .y_error(vec![0.2; 5])
+    ///     .fit_result();
+    ///
+    /// assert_eq!(fit.dof(), 3);
+    /// assert!((fit.reduced_chi2() - fit.chi2() / 3.0).abs() < 1e-12);
+    /// assert!(fit.p_value() > 0.05);
+    /// ```
+    pub fn p_value(&self) -> f64 {
+        match self.dof() {
+            0 => f64::NAN,
+            dof => chi_squared_upper_tail(self.chi2, dof as f64),
+        }
+    }
+    /// Returns the degrees of freedom, the number of points minus the number
+    /// of free parameters.
+    pub fn dof(&self) -> usize {
+        self.residuals.len().saturating_sub(self.free_parameters())
+    }
+    fn free_parameters(&self) -> usize {
+        self.parameters.len() - self.fixed
+    }
+    /// Returns the Akaike information criterion, `χ² + 2k` with `k` free
+    /// parameters, which is smaller for the model that better balances the
+    /// goodness of the fit and its complexity. Only meaningful for fits
+    /// weighted by the errors of y.
+    pub fn aic(&self) -> f64 {
+        self.chi2 + 2.0 * self.free_parameters() as f64
+    }
+    /// Returns the Bayesian information criterion, `χ² + k·ln(n)` with `k`
+    /// free parameters and `n` points, which penalizes the complexity more
+    /// than the [AIC](FitResult::aic) with many points.
+    pub fn bic(&self) -> f64 {
+        self.chi2 + self.free_parameters() as f64 * (self.residuals.len() as f64).ln()
+    }
+    /// Compares fits of diferent models to the same data with their
+    /// information criteria, returning for every fit, in the same order, its
+    /// difference with the best one and its Akaike weight.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{CurveFit, FitResult, LinearFit};
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = [1.02, 2.96, 5.05, 6.98, 9.01, 10.97];
+    /// let yerr = vec![0.05; 6];
+    ///
+    /// let linear = LinearFit::new(x, y).y_error(yerr.clone()).fit_result();
+    /// let quadratic = CurveFit::new(|x: &f64, p: &[f64]| p[0] * x * x + p[1] * x + p[2], x, y)
+    ///     .y_error(yerr)
+    ///     .initial_ones(3);
+    /// let quadratic = quadratic.fit_result();
+    ///
+    /// let comparison = FitResult::compare(&[linear, quadratic]);
+    /// assert_eq!(comparison[0].delta_aic, 0.0);
+    /// assert!(comparison[0].weight > comparison[1].weight);
+    /// ```
+    pub fn compare(fits: &[FitResult]) -> Vec<ModelComparison> {
+        let best_aic = fits
+            .iter()
+            .map(|fit| fit.aic())
+            .fold(f64::INFINITY, f64::min);
+        let best_bic = fits
+            .iter()
+            .map(|fit| fit.bic())
+            .fold(f64::INFINITY, f64::min);
+        let likelihoods: Vec<f64> = fits
+            .iter()
+            .map(|fit| (-(fit.aic() - best_aic) / 2.0).exp())
+            .collect();
+        let total: f64 = likelihoods.iter().sum();
+        fits.iter()
+            .zip(likelihoods)
+            .map(|(fit, likelihood)| ModelComparison {
+                delta_aic: fit.aic() - best_aic,
+                delta_bic: fit.bic() - best_bic,
+                weight: likelihood / total,
+            })
+            .collect()
+    }
+    /// Returns the correlation matrix of the parameters, `Cᵢⱼ/(σᵢσⱼ)`, with
+    /// 0 for the parameters without error.
+    pub fn correlation(&self) -> Vec<Vec<f64>> {
+        let errors: Vec<f64> = self.parameters.iter().map(|par| par.error).collect();
+        self.covariance
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, cov)| match errors[i] * errors[j] {
+                        norm if norm > 0.0 => cov / norm,
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// Approximates every parameter to the first significant figure of its
+    /// error, and the covariance so it keeps the correlations, rounded to two
+    /// decimals, with the rounded errors. The quoted parameters, errors and
+    /// correlation matrix are then consistent with each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::LinearFit;
+    /// let fit = LinearFit::new([0.0, 1.0, 2.0, 3.0], [1.13, 2.91, 5.22, 6.87])
+    ///     .y_error(vec![0.23; 4])
+    ///     .fit_result()
+    ///     .rounded();
+    ///
+    /// assert_eq!(fit.parameters()[0].error, 0.1);
+    /// assert_eq!(fit.correlation()[0][1], -0.8);
+    /// ```
+    pub fn rounded(&self) -> FitResult<'a> {
+        let correlation = self.correlation();
+        let parameters: Vec<ScalarMeasure> =
+            self.parameters.iter().map(|par| par.aprox()).collect();
+        let covariance = correlation
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, rho)| round(*rho, 2) * parameters[i].error * parameters[j].error)
+                    .collect()
+            })
+            .collect();
+        FitResult {
+            model: self.model.clone(),
+            parameters,
+            covariance,
+            residuals: self.residuals.clone(),
+            chi2: self.chi2,
+            fixed: self.fixed,
+            singular: self.singular,
+        }
+    }
+
+    /// Evaluates the model at the values of x, with the errors propagated
+    /// from the covariance of the parameters, `σ² = J·C·Jᵀ`, where the
+    /// derivatives `J` of the model are computed numerically.
+    pub fn evaluate(&self, x_grid: &[f64]) -> Measure {
+        let values: Vec<f64> = self.parameters.iter().map(|par| par.value).collect();
+        let (value, error) = x_grid
+            .iter()
+            .map(|x| {
+                let jacobian: Vec<f64> = (0..values.len())
+                    .map(|i| {
+                        let step = 1e-6 * (values[i].abs() + self.parameters[i].error);
+                        let step = if step > 0.0 { step } else { 1e-6 };
+                        let (mut up, mut down) = (values.clone(), values.clone());
+                        up[i] += step;
+                        down[i] -= step;
+                        ((self.model)(x, &up) - (self.model)(x, &down)) / (2.0 * step)
+                    })
+                    .collect();
+                let variance: f64 = (0..values.len())
+                    .flat_map(|i| (0..values.len()).map(move |j| (i, j)))
+                    .map(|(i, j)| jacobian[i] * self.covariance[i][j] * jacobian[j])
+                    .sum();
+                ((self.model)(x, &values), variance.max(0.0).sqrt())
+            })
+            .unzip();
+        Measure::new(value, error, false).unwrap()
+    }
+    /// Evaluates the model at one value of x, see [FitResult::evaluate].
+    pub fn predict(&self, x: f64) -> ScalarMeasure {
+        let curve = self.evaluate(&[x]);
+        ScalarMeasure::new(curve.value()[0], curve.error()[0])
+    }
+}
+
+/// Comparison of a fit with others of the same data, see
+/// [FitResult::compare].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ModelComparison {
+    /// Difference between the AIC of the fit and the smallest one.
+    pub delta_aic: f64,
+    /// Difference between the BIC of the fit and the smallest one.
+    pub delta_bic: f64,
+    /// Akaike weight, the probability of the model being the best of the
+    /// ones compared.
+    pub weight: f64,
+}
+
+impl Debug for FitResult<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FitResult")
+            .field("parameters", &self.parameters)
+            .field("covariance", &self.covariance)
+            .field("residuals", &self.residuals)
+            .field("chi2", &self.chi2)
+            .finish_non_exhaustive()
+    }
+}
+
+type Model<'a> = dyn Fn(&f64, &[f64]) -> f64 + 'a;
+
+/// Fits random subsets of `sample` points, keeping the fit with more points
+/// whose absolute residual is within the threshold, and fits again all of
+/// them. Returns the final fit and which points are inliers.
+fn ransac<'a>(
+    (x, y): (&[f64], &[f64]),
+    sample: usize,
+    threshold: f64,
+    (iterations, seed): (usize, u64),
+    fit: impl Fn(&[usize]) -> FitResult<'a>,
+) -> (FitResult<'a>, Vec<bool>) {
+    assert!(
+        x.len() >= sample,
+        "RANSAC requires at least {} points, obtained {}.",
+        sample,
+        x.len()
+    );
+    let mut rng = Rng::new(seed);
+    let mut best: Option<(usize, f64, Vec<bool>)> = None;
+    for _ in 0..iterations {
+        let mut indices = rng.permutation(x.len());
+        indices.truncate(sample);
+        let candidate = fit(&indices);
+        let values: Vec<f64> = candidate.parameters.iter().map(|par| par.value).collect();
+        let residuals: Vec<f64> = x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| (y - (candidate.model)(x, &values)).abs())
+            .collect();
+        let inliers: Vec<bool> = residuals.iter().map(|res| *res <= threshold).collect();
+        let count = inliers.iter().filter(|inlier| **inlier).count();
+        // Ties are broken by the residuals of the inliers.
+        let squares: f64 = residuals
+            .iter()
+            .filter(|res| **res <= threshold)
+            .map(|res| res.powi(2))
+            .sum();
+        let better = match &best {
+            Some((best_count, best_squares, _)) => {
+                count > *best_count || (count == *best_count && squares < *best_squares)
+            }
+            None => true,
+        };
+        if better {
+            best = Some((count, squares, inliers));
+        }
+    }
+    let inliers = match best {
+        Some((_, _, inliers)) => inliers,
+        None => vec![true; x.len()],
+    };
+    let indices: Vec<usize> = (0..x.len()).filter(|i| inliers[*i]).collect();
+    (fit(&indices), inliers)
+}
+
+/// Values with the given indices.
+fn pick(values: &[f64], indices: &[usize]) -> Vec<f64> {
+    indices.iter().map(|i| values[*i]).collect()
+}
+
+/// Errors of a measure to weight a fit, None if any of them is not positive.
+fn positive_errors(measure: &Measure) -> Option<Vec<f64>> {
+    if measure.error().iter().all(|err| *err > 0.0) {
+        Some(measure.error().clone())
+    } else {
+        None
+    }
+}
+
+// Parameters with the errors from the diagonal of the covariance matrix.
+fn scalar_parameters(parameters: &[f64], covariance: &[Vec<f64>]) -> Vec<ScalarMeasure> {
+    parameters
+        .iter()
+        .enumerate()
+        .map(|(i, value)| ScalarMeasure::new(*value, covariance[i][i].sqrt()))
+        .collect()
+}
+
 // ------------- Linear fit and Weigthed linear fit -------------
 
-fn linear_fit(x: &[f64], y: &[f64]) -> (Measure, Measure) {
+fn linear_fit(x: &[f64], y: &[f64]) -> (ScalarMeasure, ScalarMeasure) {
+    count(Counter::FitEvaluations, 1);
     assert_eq!(
         x.len(),
         y.len(),
@@ -199,13 +1020,14 @@ fn linear_fit(x: &[f64], y: &[f64]) -> (Measure, Measure) {
     let sigma_slope = sigma_y * (n / (n * sum_x2 - sum_x.powi(2))).sqrt();
     let sigma_n0 = sigma_y * (sum_x2 / (n * sum_x2 - sum_x.powi(2))).sqrt();
 
-    let slope = Measure::new(vec![slope], vec![sigma_slope], false).unwrap();
-    let n0 = Measure::new(vec![n0], vec![sigma_n0], false).unwrap();
+    let slope = ScalarMeasure::new(slope, sigma_slope);
+    let n0 = ScalarMeasure::new(n0, sigma_n0);
 
     (slope, n0)
 }
 
-fn wlinear_fit(x: &[f64], y: &[f64], yerr: &[f64]) -> (Measure, Measure) {
+fn wlinear_fit(x: &[f64], y: &[f64], yerr: &[f64]) -> (ScalarMeasure, ScalarMeasure) {
+    count(Counter::FitEvaluations, 1);
     assert_eq!(
         x.len(),
         y.len(),
@@ -237,12 +1059,78 @@ fn wlinear_fit(x: &[f64], y: &[f64], yerr: &[f64]) -> (Measure, Measure) {
     let wsigma_slope: f64 = (sum_w / (sum_w * sum_x2w - sum_xw.powi(2))).sqrt();
     let wsigma_n0 = (sum_x2w / (sum_w * sum_x2w - sum_xw.powi(2))).sqrt();
 
-    let wslope = Measure::new(vec![wslope], vec![wsigma_slope], false).unwrap();
-    let wn0 = Measure::new(vec![wn0], vec![wsigma_n0], false).unwrap();
+    let wslope = ScalarMeasure::new(wslope, wsigma_slope);
+    let wn0 = ScalarMeasure::new(wn0, wsigma_n0);
 
     (wslope, wn0)
 }
 
+/// York regression of a straight line with errors in x and y, iterating the
+/// slope from the unweighted one until it converges.
+fn york_fit(x: &[f64], y: &[f64], xerr: &[f64], yerr: &[f64]) -> (ScalarMeasure, ScalarMeasure) {
+    assert!(
+        x.len() == xerr.len() && y.len() == yerr.len(),
+        "Expected the errors to be the same length as the values, got x.len() = {}, xerr.len() = {}, y.len() = {}, yerr.len() = {}",
+        x.len(),
+        xerr.len(),
+        y.len(),
+        yerr.len()
+    );
+    // Weights, weighted means and the β of every point for a given slope.
+    let step = |slope: f64| {
+        let w: Vec<f64> = xerr
+            .iter()
+            .zip(yerr)
+            .map(|(sx, sy)| 1.0 / (sy.powi(2) + (slope * sx).powi(2)))
+            .collect();
+        let sum_w: f64 = w.iter().sum();
+        let x_mean = x.iter().zip(&w).map(|(x, w)| x * w).sum::<f64>() / sum_w;
+        let y_mean = y.iter().zip(&w).map(|(y, w)| y * w).sum::<f64>() / sum_w;
+        let beta: Vec<f64> = (0..x.len())
+            .map(|i| {
+                w[i] * ((x[i] - x_mean) * yerr[i].powi(2)
+                    + slope * (y[i] - y_mean) * xerr[i].powi(2))
+            })
+            .collect();
+        (w, x_mean, y_mean, beta)
+    };
+
+    let mut slope = linear_fit(x, y).0.value;
+    for _ in 0..YORK_ITERATIONS {
+        count(Counter::FitEvaluations, 1);
+        let (w, x_mean, y_mean, beta) = step(slope);
+        let (numerator, denominator) = (0..x.len()).fold((0.0, 0.0), |(num, den), i| {
+            (
+                num + w[i] * beta[i] * (y[i] - y_mean),
+                den + w[i] * beta[i] * (x[i] - x_mean),
+            )
+        });
+        let new_slope = numerator / denominator;
+        let converged = (new_slope - slope).abs() <= 1e-15 * new_slope.abs();
+        slope = new_slope;
+        if converged {
+            break;
+        }
+    }
+
+    let (w, x_mean, y_mean, beta) = step(slope);
+    let sum_w: f64 = w.iter().sum();
+    // Mean of the adjusted points, the points of the line closest to the data.
+    let adjusted_mean = (0..x.len()).map(|i| w[i] * (x_mean + beta[i])).sum::<f64>() / sum_w;
+    let sigma_slope_2 = 1.0
+        / (0..x.len())
+            .map(|i| w[i] * (x_mean + beta[i] - adjusted_mean).powi(2))
+            .sum::<f64>();
+    let sigma_n0 = (1.0 / sum_w + adjusted_mean.powi(2) * sigma_slope_2).sqrt();
+    (
+        ScalarMeasure::new(slope, sigma_slope_2.sqrt()),
+        ScalarMeasure::new(y_mean - slope * x_mean, sigma_n0),
+    )
+}
+
+const YORK_ITERATIONS: usize = 100;
+const EFFECTIVE_VARIANCE_ITERATIONS: usize = 20;
+
 // ------------------------- Curve fit -------------------------
 #[allow(clippy::too_many_arguments)]
 fn curve_fit<F>(
@@ -254,7 +1142,8 @@ fn curve_fit<F>(
     max_iterations: Option<usize>,
     tol: f64,
     scale: f64,
-) -> Vec<Measure>
+    optimizer: Optimizer,
+) -> (Vec<f64>, Vec<Vec<f64>>, bool)
 where
     F: Fn(&f64, &[f64]) -> f64,
 {
@@ -267,32 +1156,34 @@ where
     );
     let n = x.len();
     let objective_function = |coef: &[f64]| {
+        count(Counter::FitEvaluations, 1);
         x.iter()
             .zip(y.iter())
             .zip(yerr.iter())
             .map(|((x, y), ye)| ((y - model(x, coef)) / ye).powi(2))
             .sum()
     };
-    let result = nelder_mead(
-        &objective_function,
-        initial_point,
-        max_iterations,
-        tol,
-        scale,
-    );
+    let result = match optimizer {
+        Optimizer::NelderMead => nelder_mead(
+            &objective_function,
+            initial_point,
+            max_iterations,
+            tol,
+            scale,
+        ),
+        Optimizer::Bfgs => bfgs(&objective_function, initial_point, max_iterations, tol),
+    };
 
     let hessian_matrix = calculate_hessian_matrix(&objective_function, &result);
-    let inverse_hessian = match invert_matrix(
+    let Some(inverse_hessian) = invert_matrix(
         &hessian_matrix
             .iter()
             .map(|x| x.iter().map(|y| y / 2.0).collect())
             .collect::<Vec<_>>(),
-    ) {
-        Some(inverse) => inverse,
-        None => {
-            eprintln!("Matriz Hessiana sin inversa, no pudieron calcularse los errores");
-            vec![vec![0.0; n]; n]
-        }
+    ) else {
+        // The errors can't be computed, see FitResult::singular_covariance.
+        let covariance_matrix = vec![vec![f64::NAN; result.len()]; result.len()];
+        return (result, covariance_matrix, true);
     };
 
     let rss = objective_function(&result);
@@ -303,16 +1194,7 @@ where
         .map(|x| x.iter().map(|y| y * rss / dof).collect::<Vec<_>>())
         .collect::<Vec<_>>();
     // let covariance_matrix = inverse_hessian;
-    let errors: Vec<f64> = covariance_matrix
-        .iter()
-        .enumerate()
-        .map(|(i, x)| x[i].sqrt())
-        .collect();
-    result
-        .into_iter()
-        .zip(errors)
-        .map(|(v, e)| Measure::new(vec![v], vec![e], false).unwrap())
-        .collect()
+    (result, covariance_matrix, false)
 }
 
 fn generate_initial_simplex(initial_point: &[f64], scale: f64) -> Vec<Vec<f64>> {
@@ -344,7 +1226,7 @@ where
     let mut values: Vec<f64> = simplex.iter().map(|point| f(point)).collect();
     let iter: Box<dyn Iterator<Item = ()>>;
     if let Some(max) = max_iterations {
-        iter = Box::new(std::iter::repeat(()).take(max));
+        iter = Box::new(std::iter::repeat_n((), max));
     } else {
         iter = Box::new(std::iter::repeat(()));
     }
@@ -424,12 +1306,98 @@ where
     simplex[0].clone()
 }
 
+/// BFGS minimization, updating an approximation of the inverse of the Hessian
+/// with the numerical gradients and searching along every direction by
+/// backtracking until the Armijo condition holds. Stops when the function
+/// changes less than the tolerance.
+fn bfgs<F>(f: &F, initial_point: &[f64], max_iterations: Option<usize>, tol: f64) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = initial_point.len();
+    let identity = |scale: f64| -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| (0..n).map(|j| if i == j { scale } else { 0.0 }).collect())
+            .collect()
+    };
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+
+    let mut point = initial_point.to_vec();
+    let mut value = f(&point);
+    let mut gradient = calculate_gradient(f, &point);
+    let mut inverse_hessian = identity(1.0);
+    for iteration in 0..max_iterations.unwrap_or(usize::MAX) {
+        let mut direction: Vec<f64> = inverse_hessian
+            .iter()
+            .map(|row| -dot(row, &gradient))
+            .collect();
+        let mut slope = dot(&gradient, &direction);
+        if slope >= 0.0 {
+            // Not a descent direction, restart from the gradient.
+            inverse_hessian = identity(1.0);
+            direction = gradient.iter().map(|g| -g).collect();
+            slope = -dot(&gradient, &gradient);
+        }
+        if slope == 0.0 || !slope.is_finite() {
+            break;
+        }
+
+        let mut step = 1.0;
+        let (new_point, new_value) = loop {
+            let candidate: Vec<f64> = point
+                .iter()
+                .zip(&direction)
+                .map(|(x, d)| x + step * d)
+                .collect();
+            let candidate_value = f(&candidate);
+            if candidate_value <= value + 1e-4 * step * slope {
+                break (candidate, candidate_value);
+            }
+            step /= 2.0;
+            if step < 1e-12 {
+                return point;
+            }
+        };
+        let new_gradient = calculate_gradient(f, &new_point);
+        let s: Vec<f64> = new_point.iter().zip(&point).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = new_gradient
+            .iter()
+            .zip(&gradient)
+            .map(|(a, b)| a - b)
+            .collect();
+        let converged = (value - new_value).abs() < tol;
+        (point, value, gradient) = (new_point, new_value, new_gradient);
+        if converged {
+            break;
+        }
+
+        let sy = dot(&s, &y);
+        if sy > 0.0 {
+            if iteration == 0 {
+                // Scales the first approximation to the curvature found.
+                inverse_hessian = identity(sy / dot(&y, &y));
+            }
+            // H ← (I - ρ·s·yᵀ)·H·(I - ρ·y·sᵀ) + ρ·s·sᵀ
+            let rho = 1.0 / sy;
+            let hy: Vec<f64> = inverse_hessian.iter().map(|row| dot(row, &y)).collect();
+            let yhy = dot(&y, &hy);
+            for i in 0..n {
+                for j in 0..n {
+                    inverse_hessian[i][j] +=
+                        rho * ((1.0 + rho * yhy) * s[i] * s[j] - hy[i] * s[j] - s[i] * hy[j]);
+                }
+            }
+        }
+    }
+    point
+}
+
 fn calculate_hessian_matrix<F>(objective_function: &F, params: &[f64]) -> Vec<Vec<f64>>
 where
     F: Fn(&[f64]) -> f64,
 {
     let n = params.len();
-    let h = 1e-6 * params.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let h = derivative_step(params);
 
     let mut hessian_matrix = vec![vec![0.0; n]; n];
 
@@ -456,7 +1424,7 @@ where
     F: Fn(&[f64]) -> f64,
 {
     let n = params.len();
-    let h = 1e-6 * params.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let h = derivative_step(params);
 
     let mut gradient = Vec::with_capacity(n);
 
@@ -476,6 +1444,15 @@ where
     gradient
 }
 
+/// Step of the numerical derivatives, relative to the smallest parameter or
+/// absolute if it is zero.
+fn derivative_step(params: &[f64]) -> f64 {
+    match 1e-6 * params.iter().fold(f64::INFINITY, |a, &b| a.min(b)) {
+        h if h != 0.0 => h,
+        _ => 1e-6,
+    }
+}
+
 fn invert_matrix(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
     let n = matrix.len();
 
@@ -515,17 +1492,19 @@ fn invert_matrix(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
             if pivot_val == 0.0 {
                 return None; // Matrix is singular
             }
-            for j in 0..2 * n {
-                augmented_matrix[i][j] /= pivot_val;
-            }
+            augmented_matrix[i]
+                .iter_mut()
+                .for_each(|val| *val /= pivot_val);
 
             // Make other elements in the column 0
-            for k in 0..n {
+            let pivot_row = augmented_matrix[i].clone();
+            for (k, row) in augmented_matrix.iter_mut().enumerate() {
                 if k != i {
-                    let factor = augmented_matrix[k][i];
-                    for j in i..2 * n {
-                        augmented_matrix[k][j] -= factor * augmented_matrix[i][j];
-                    }
+                    let factor = row[i];
+                    row.iter_mut()
+                        .zip(&pivot_row)
+                        .skip(i)
+                        .for_each(|(val, pivot)| *val -= factor * pivot);
                 }
             }
         } else {
@@ -0,0 +1,89 @@
+//! Contains the struct InstrumentProfile, used to compute the errors of the
+//! readings of an instrument from its datasheet.
+use crate::{aprox::round, objects::Measure, random::Rng};
+
+/// Accuracy of an instrument as given by its datasheet, usually
+/// `±(gain % of reading + offset)`, along with its resolution.
+///
+/// The error of a reading adds linearly the gain and offset errors, as the
+/// datasheets specify, and combines the result in quadrature with the
+/// resolution.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{Measure, InstrumentProfile};
+/// let multimeter = InstrumentProfile::new(0.01).gain_error(0.5).offset_error(0.02);
+/// let voltage = Measure::with_instrument(vec![2.0, 10.0], &multimeter);
+///
+/// assert!((voltage.error()[1] - 0.07_f64.hypot(0.01)).abs() < 1e-12);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InstrumentProfile {
+    resolution: f64,
+    gain_error: f64,
+    offset_error: f64,
+}
+
+impl InstrumentProfile {
+    /// Constructs a new InstrumentProfile with the smallest change the
+    /// instrument shows, without gain nor offset errors.
+    pub fn new(resolution: f64) -> Self {
+        InstrumentProfile {
+            resolution,
+            gain_error: 0.0,
+            offset_error: 0.0,
+        }
+    }
+    /// Error proportional to the reading, as a percentage, by default 0.
+    pub fn gain_error(mut self, percent: f64) -> Self {
+        self.gain_error = percent;
+        self
+    }
+    /// Constant error of every reading, by default 0.
+    pub fn offset_error(mut self, offset: f64) -> Self {
+        self.offset_error = offset;
+        self
+    }
+    /// Returns the resolution.
+    pub fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Error of a reading of the instrument.
+    pub fn error(&self, reading: f64) -> f64 {
+        (self.gain_error / 100.0 * reading.abs() + self.offset_error).hypot(self.resolution)
+    }
+    /// Simulates the readings of the instrument for some true values, adding
+    /// normal noise with the error of each reading and rounding to the
+    /// resolution. The same seed always gives the same readings.
+    pub fn simulate(&self, values: &[f64], seed: u64) -> Measure {
+        let mut rng = Rng::new(seed);
+        let readings = values
+            .iter()
+            .map(|value| {
+                let reading = value + self.error(*value) * rng.normal();
+                if self.resolution > 0.0 {
+                    // Removes the floating point noise of the multiplication.
+                    let decimals = 6 - self.resolution.log10().floor() as i32;
+                    round(
+                        (reading / self.resolution).round() * self.resolution,
+                        decimals,
+                    )
+                } else {
+                    reading
+                }
+            })
+            .collect();
+        Measure::with_instrument(readings, self)
+    }
+}
+
+impl Measure {
+    /// Constructs a measure whose errors are computed from the profile of the
+    /// instrument used, see [InstrumentProfile].
+    pub fn with_instrument(value: Vec<f64>, profile: &InstrumentProfile) -> Measure {
+        let error = value.iter().map(|val| profile.error(*val)).collect();
+        Measure::new(value, error, false).unwrap()
+    }
+}
@@ -0,0 +1,92 @@
+//! Contains the struct MeasureKey, used to store measures in hash maps and sets.
+use {
+    crate::Measure,
+    std::hash::{Hash, Hasher},
+};
+
+/// Wrapper of a measure with a byte-level equality and hash, so it can be used
+/// as the key of a `HashMap`, e.g. to cache the fit of every dataset.
+///
+/// Two keys are equal if their values, errors and unit dimensions have the same
+/// bits, being every NaN equal to any other NaN and `-0.0` equal to `0.0`. The
+/// name, style, metadata and provenance of the measure are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use ferrilab::{measure, Measure, MeasureKey};
+/// let mut fits = HashMap::new();
+/// let run = measure!([1.2, 1.4, 1.3], 0.1);
+///
+/// fits.insert(MeasureKey::new(run.clone()), run.estimation());
+/// assert!(fits.contains_key(&MeasureKey::new(run)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeasureKey(Measure);
+
+impl MeasureKey {
+    /// Constructs a new MeasureKey.
+    pub fn new(measure: Measure) -> MeasureKey {
+        MeasureKey(measure)
+    }
+    /// Returns the measure of the key.
+    pub fn measure(&self) -> &Measure {
+        &self.0
+    }
+    /// Returns the measure, consuming the key.
+    pub fn into_inner(self) -> Measure {
+        self.0
+    }
+    /// Identifier of the measure, a FNV-1a hash of its canonical bytes that,
+    /// unlike the hashers of the standard library, is the same on every run and
+    /// version, so it can be stored in files.
+    pub fn id(&self) -> u64 {
+        self.words().fold(0xcbf29ce484222325, |hash, word| {
+            word.to_le_bytes().iter().fold(hash, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+            })
+        })
+    }
+    // Canonical bits of the length, values, errors and unit of the measure.
+    fn words(&self) -> impl Iterator<Item = u64> + '_ {
+        let (value, error) = self.0.unpack();
+        std::iter::once(self.0.len() as u64).chain(
+            value
+                .iter()
+                .chain(error.iter())
+                .chain(self.0.unit().dimension().iter())
+                .map(|num| canonical(*num)),
+        )
+    }
+}
+
+fn canonical(num: f64) -> u64 {
+    if num.is_nan() {
+        f64::NAN.to_bits()
+    } else if num == 0.0 {
+        0
+    } else {
+        num.to_bits()
+    }
+}
+
+impl PartialEq for MeasureKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.words().eq(other.words())
+    }
+}
+
+impl Eq for MeasureKey {}
+
+impl Hash for MeasureKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.words().for_each(|word| state.write_u64(word));
+    }
+}
+
+impl From<Measure> for MeasureKey {
+    fn from(measure: Measure) -> MeasureKey {
+        MeasureKey(measure)
+    }
+}
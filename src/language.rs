@@ -0,0 +1,87 @@
+//! Contains the language of the messages and generated text of the library.
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+/// Language of the warnings, the messages of the styles that can't display a
+/// measure and the text generated for tables, by default English.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{language, measure, set_language, Language, Measure, Metadata, Style, Table};
+/// set_language(Language::Spanish);
+/// let time = measure!([0.2, 0.3], 0.01).change_style(Style::Table);
+/// assert_eq!(time.to_string(), "Este estilo es solo para un valor y su error.");
+///
+/// let table = Table::new(vec![time], vec!["t/s"])
+///     .metadata(Metadata::new().instrument("Cronómetro"))
+///     .latex();
+/// assert!(table.contains("\\caption{título (instrumento: Cronómetro)}"));
+///
+/// set_language(Language::English);
+/// assert_eq!(language(), Language::English);
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English.
+    #[default]
+    English,
+    /// Spanish.
+    Spanish,
+}
+
+/// Changes the language used by the whole library.
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Returns the language used by the library.
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Spanish,
+        _ => Language::English,
+    }
+}
+
+/// Every text shown to the user, translated to each language.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Message {
+    SingleValueStyle,
+    Caption,
+    Instrument,
+    Operator,
+    Date,
+}
+
+impl Message {
+    /// Text of the message in the language of the library.
+    pub(crate) fn text(self) -> &'static str {
+        match (self, language()) {
+            (Message::SingleValueStyle, Language::English) => {
+                "This style is only for one value and its error."
+            }
+            (Message::SingleValueStyle, Language::Spanish) => {
+                "Este estilo es solo para un valor y su error."
+            }
+            (Message::Caption, Language::English) => "caption",
+            (Message::Caption, Language::Spanish) => "título",
+            (Message::Instrument, Language::English) => "instrument",
+            (Message::Instrument, Language::Spanish) => "instrumento",
+            (Message::Operator, Language::English) => "operator",
+            (Message::Operator, Language::Spanish) => "operador",
+            (Message::Date, Language::English) => "date",
+            (Message::Date, Language::Spanish) => "fecha",
+        }
+    }
+    /// Translates the standard keys of the metadata, leaving the rest as they
+    /// are.
+    pub(crate) fn metadata_key(key: &str) -> &str {
+        match key {
+            "instrument" => Message::Instrument.text(),
+            "operator" => Message::Operator.text(),
+            "date" => Message::Date.text(),
+            _ => key,
+        }
+    }
+}
@@ -27,19 +27,78 @@
 //! let cosine = angle_rad.cos(); // Calculates the cosine of angles.
 //! ```
 
+#[cfg(feature = "nalgebra")]
+mod algebra;
 mod aprox;
+#[cfg(feature = "approx")]
+mod approx_eq;
+mod binary;
+mod budget;
+mod check;
+#[cfg(feature = "console")]
+pub mod console;
+mod dataset;
+mod dual;
+pub mod export;
 mod fit;
+mod instrument;
+mod key;
+mod language;
 mod macros;
+mod measure2d;
+mod metadata;
+pub mod models;
+mod moments;
 mod objects;
-mod plot;
+mod perf;
+pub mod plot;
+mod provenance;
+mod random;
 mod reader;
+mod scalar;
+mod session;
+#[cfg(feature = "shell")]
+pub mod shell;
+mod special;
+pub mod stats;
 mod tables;
+mod tagged;
+mod timeseries;
+mod units;
+mod view;
 
+#[cfg(feature = "mmap")]
+#[doc(inline)]
+pub use binary::MappedMeasures;
+#[cfg(feature = "perf-stats")]
+#[doc(inline)]
+pub use perf::{perf_stats, reset_perf_stats, PerfStats};
 #[doc(inline)]
 pub use {
-    fit::{CurveFit, LinearFit},
-    objects::Measure,
-    reader::Reader,
+    binary::{read_binary, write_binary},
+    budget::{BudgetEntry, ErrorBudget, SourcedMeasure},
+    check::DisplayMismatch,
+    dataset::{Dataset, ResampleMethod},
+    dual::Dual,
+    fit::{CurveFit, FitResult, LinearFit, ModelComparison, Optimizer, PowerLawFit},
+    instrument::InstrumentProfile,
+    key::MeasureKey,
+    language::{language, set_language, Language},
+    measure2d::Measure2D,
+    metadata::Metadata,
+    moments::Moments,
+    objects::{
+        autoscale_prefix, ratio, Float, GenericMeasure, Measure, MyError, OrdByValue,
+        OutlierCriterion, Style,
+    },
+    reader::{ReadProgress, Reader},
+    scalar::ScalarMeasure,
+    session::Session,
     tables::Table,
+    tagged::{ErrorTag, TaggedMeasure},
+    timeseries::{Gap, TimeSeries},
+    units::Unit,
+    view::MeasureView,
     plot::*,
+    provenance::Provenance,
 };
@@ -24,7 +24,6 @@
 ///
 /// The error is set to 0 when no error is given. It is important that vectors of values and
 /// errors are the same length, otherwise it will cause an error.
-
 #[macro_export]
 macro_rules! measure {
     // value: [...], error: _, aprox: true/false/nothing
@@ -99,58 +98,30 @@ macro_rules! impl_op {
             type Output = Measure;
 
             fn add(self, other: $from) -> Self::Output {
-                if self.len() == 1 {
-                    return Measure {
-                        value: other
-                            .value
-                            .iter()
-                            .map(|oval| self.value[0] + oval)
-                            .collect(),
-                        error: other
-                            .error
-                            .iter()
-                            .map(|oerr| (self.error[0].powi(2) + oerr.powi(2)).sqrt())
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-                if other.len() == 1 {
-                    return Measure {
-                        value: self
-                            .value
-                            .iter()
-                            .map(|sval| sval + other.value[0])
-                            .collect(),
-                        error: self
-                            .error
-                            .iter()
-                            .map(|serr| (serr.powi(2) + other.error[0].powi(2)).sqrt())
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-
-                assert_eq!(
-                    self.len(),
-                    other.len(),
-                    "Measures lengths must be equals, obtained {} and {}.",
-                    self.len(),
-                    other.len()
+                assert!(
+                    self.unit.is_compatible(&other.unit),
+                    "Measures units must be compatible, obtained {} and {}.",
+                    self.unit,
+                    other.unit
+                );
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("add", &[&self.provenance, &other.provenance], &[]);
+                let (value, error) = zip_operands(
+                    self.operand(),
+                    other.operand(),
+                    |(sval, serr), (oval, oerr)| {
+                        (sval + oval, (serr.powi(2) + oerr.powi(2)).sqrt())
+                    },
                 );
                 Measure {
-                    value: self
-                        .value
-                        .iter()
-                        .zip(other.value.iter())
-                        .map(|(sval, oval)| sval + oval)
-                        .collect(),
-                    error: self
-                        .error
-                        .iter()
-                        .zip(other.error.iter())
-                        .map(|(serr, oerr)| (serr.powi(2) + oerr.powi(2)).sqrt())
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -158,59 +129,32 @@ macro_rules! impl_op {
         impl Sub<$from> for $for {
             type Output = Measure;
 
+            #[allow(clippy::suspicious_arithmetic_impl)]
             fn sub(self, other: $from) -> Self::Output {
-                if self.len() == 1 {
-                    return Measure {
-                        value: other
-                            .value
-                            .iter()
-                            .map(|oval| self.value[0] - oval)
-                            .collect(),
-                        error: other
-                            .error
-                            .iter()
-                            .map(|oerr| (self.error[0].powi(2) + oerr.powi(2)).sqrt())
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-                if other.len() == 1 {
-                    return Measure {
-                        value: self
-                            .value
-                            .iter()
-                            .map(|sval| sval - other.value[0])
-                            .collect(),
-                        error: self
-                            .error
-                            .iter()
-                            .map(|serr| (serr.powi(2) + other.error[0].powi(2)).sqrt())
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-
-                assert_eq!(
-                    self.len(),
-                    other.len(),
-                    "Measures lengths must be equals, obtained {} and {}.",
-                    self.len(),
-                    other.len()
+                assert!(
+                    self.unit.is_compatible(&other.unit),
+                    "Measures units must be compatible, obtained {} and {}.",
+                    self.unit,
+                    other.unit
+                );
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("sub", &[&self.provenance, &other.provenance], &[]);
+                let (value, error) = zip_operands(
+                    self.operand(),
+                    other.operand(),
+                    |(sval, serr), (oval, oerr)| {
+                        (sval - oval, (serr.powi(2) + oerr.powi(2)).sqrt())
+                    },
                 );
                 Measure {
-                    value: self
-                        .value
-                        .iter()
-                        .zip(other.value.iter())
-                        .map(|(sval, oval)| sval - oval)
-                        .collect(),
-                    error: self
-                        .error
-                        .iter()
-                        .zip(other.error.iter())
-                        .map(|(serr, oerr)| (serr.powi(2) + oerr.powi(2)).sqrt())
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -218,64 +162,29 @@ macro_rules! impl_op {
         impl Mul<$from> for $for {
             type Output = Measure;
 
+            #[allow(clippy::suspicious_arithmetic_impl)]
             fn mul(self, other: $from) -> Self::Output {
-                if self.len() == 1 {
-                    return Measure {
-                        value: other
-                            .value
-                            .iter()
-                            .map(|oval| self.value[0] * oval)
-                            .collect(),
-                        error: other
-                            .iter()
-                            .map(|(oval, oerr)| {
-                                ((oval * self.error[0]).powi(2) + (oerr * self.value[0]).powi(2))
-                                    .sqrt()
-                            })
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-                if other.len() == 1 {
-                    return Measure {
-                        value: self
-                            .value
-                            .iter()
-                            .map(|sval| sval * other.value[0])
-                            .collect(),
-                        error: self
-                            .iter()
-                            .map(|(sval, serr)| {
-                                ((other.value[0] * serr).powi(2) + (sval * other.error[0]).powi(2))
-                                    .sqrt()
-                            })
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-
-                assert_eq!(
-                    self.len(),
-                    other.len(),
-                    "Measures lengths must be equals, obtained {} and {}.",
-                    self.len(),
-                    other.len()
+                let unit = &self.unit * &other.unit;
+                let provenance =
+                    Provenance::derive("mul", &[&self.provenance, &other.provenance], &[]);
+                let (value, error) = zip_operands(
+                    self.operand(),
+                    other.operand(),
+                    |(sval, serr), (oval, oerr)| {
+                        (
+                            sval * oval,
+                            ((oval * serr).powi(2) + (sval * oerr).powi(2)).sqrt(),
+                        )
+                    },
                 );
                 Measure {
-                    value: self
-                        .value
-                        .iter()
-                        .zip(other.value.iter())
-                        .map(|(sval, oval)| sval * oval)
-                        .collect(),
-                    error: self
-                        .iter()
-                        .zip(other.iter())
-                        .map(|((sval, serr), (oval, oerr))| {
-                            ((oval * serr).powi(2) + (sval * oerr).powi(2)).sqrt()
-                        })
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -283,67 +192,30 @@ macro_rules! impl_op {
         impl Div<$from> for $for {
             type Output = Measure;
 
+            #[allow(clippy::suspicious_arithmetic_impl)]
             fn div(self, other: $from) -> Self::Output {
-                if self.len() == 1 {
-                    return Measure {
-                        value: other
-                            .value
-                            .iter()
-                            .map(|oval| self.value[0] / oval)
-                            .collect(),
-                        error: other
-                            .iter()
-                            .map(|(oval, oerr)| {
-                                ((1.0 / oval * self.error[0]).powi(2)
-                                    + (self.value[0] / oval.powi(2) * oerr.powi(2)))
-                                .sqrt()
-                            })
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-                if other.len() == 1 {
-                    return Measure {
-                        value: self
-                            .value
-                            .iter()
-                            .map(|sval| sval / other.value[0])
-                            .collect(),
-                        error: self
-                            .iter()
-                            .map(|(sval, serr)| {
-                                ((1.0 / other.value[0] * serr).powi(2)
-                                    + (sval / other.value[0].powi(2) * other.error[0].powi(2)))
-                                .sqrt()
-                            })
-                            .collect(),
-                        style: Style::PM,
-                    };
-                }
-
-                assert_eq!(
-                    self.len(),
-                    other.len(),
-                    "Measures lengths must be equals, obtained {} and {}.",
-                    self.len(),
-                    other.len()
+                let unit = &self.unit / &other.unit;
+                let provenance =
+                    Provenance::derive("div", &[&self.provenance, &other.provenance], &[]);
+                let (value, error) = zip_operands(
+                    self.operand(),
+                    other.operand(),
+                    |(sval, serr), (oval, oerr)| {
+                        (
+                            sval / oval,
+                            ((1.0 / oval * serr).powi(2) + (sval / oval.powi(2) * oerr).powi(2))
+                                .sqrt(),
+                        )
+                    },
                 );
                 Measure {
-                    value: self
-                        .value
-                        .iter()
-                        .zip(other.value.iter())
-                        .map(|(sval, oval)| sval / oval)
-                        .collect(),
-                    error: self
-                        .iter()
-                        .zip(other.iter())
-                        .map(|((sval, serr), (oval, oerr))| {
-                            ((1.0 / oval * serr).powi(2) + (sval / oval.powi(2) * oerr.powi(2)))
-                                .sqrt()
-                        })
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -361,10 +233,18 @@ macro_rules! impl_op_number {
 
             fn add(self, num: T) -> Self::Output {
                 let num = num.into();
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("add", &[&self.provenance], &[("operand", num)]);
+                let (value, error) = map_operand(self.operand(), |val, err| (val + num, err));
                 Measure {
-                    value: self.value.iter().map(|val| val + num).collect(),
-                    error: self.error.clone(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -374,10 +254,18 @@ macro_rules! impl_op_number {
 
             fn sub(self, num: T) -> Self::Output {
                 let num = num.into();
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("sub", &[&self.provenance], &[("subtrahend", num)]);
+                let (value, error) = map_operand(self.operand(), |val, err| (val - num, err));
                 Measure {
-                    value: self.value.iter().map(|val| val - num).collect(),
-                    error: self.error.clone(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -387,10 +275,19 @@ macro_rules! impl_op_number {
 
             fn mul(self, num: T) -> Self::Output {
                 let num = num.into();
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("mul", &[&self.provenance], &[("operand", num)]);
+                let (value, error) =
+                    map_operand(self.operand(), |val, err| (val * num, err * num.abs()));
                 Measure {
-                    value: self.value.iter().map(|val| val * num).collect(),
-                    error: self.error.iter().map(|err| err * num.abs()).collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -400,10 +297,19 @@ macro_rules! impl_op_number {
 
             fn div(self, num: T) -> Self::Output {
                 let num = num.into();
+                let unit = self.unit.clone();
+                let provenance =
+                    Provenance::derive("div", &[&self.provenance], &[("divisor", num)]);
+                let (value, error) =
+                    map_operand(self.operand(), |val, err| (val / num, err / num.abs()));
                 Measure {
-                    value: self.value.iter().map(|val| val / num).collect(),
-                    error: self.error.iter().map(|err| err / num.abs()).collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -414,14 +320,19 @@ macro_rules! impl_op_number {
             type Output = Measure;
 
             fn add(self, measure: $from) -> Self::Output {
+                let unit = measure.unit.clone();
+                let provenance =
+                    Provenance::derive("add", &[&measure.provenance], &[("operand", self as f64)]);
+                let num = self as f64;
+                let (value, error) = map_operand(measure.operand(), |val, err| (val + num, err));
                 Measure {
-                    value: measure
-                        .value
-                        .iter()
-                        .map(|val| val + (self as f64))
-                        .collect(),
-                    error: measure.error.clone(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -430,14 +341,19 @@ macro_rules! impl_op_number {
             type Output = Measure;
 
             fn sub(self, measure: $from) -> Self::Output {
+                let unit = measure.unit.clone();
+                let provenance =
+                    Provenance::derive("sub", &[&measure.provenance], &[("minuend", self as f64)]);
+                let num = self as f64;
+                let (value, error) = map_operand(measure.operand(), |val, err| (val - num, err));
                 Measure {
-                    value: measure
-                        .value
-                        .iter()
-                        .map(|val| val - (self as f64))
-                        .collect(),
-                    error: measure.error.clone(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -446,18 +362,20 @@ macro_rules! impl_op_number {
             type Output = Measure;
 
             fn mul(self, measure: $from) -> Self::Output {
+                let unit = measure.unit.clone();
+                let provenance =
+                    Provenance::derive("mul", &[&measure.provenance], &[("operand", self as f64)]);
+                let num = self as f64;
+                let (value, error) =
+                    map_operand(measure.operand(), |val, err| (val * num, err * num.abs()));
                 Measure {
-                    value: measure
-                        .value
-                        .iter()
-                        .map(|val| val * (self as f64))
-                        .collect(),
-                    error: measure
-                        .error
-                        .iter()
-                        .map(|err| err * (self as f64).abs())
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
@@ -465,20 +383,210 @@ macro_rules! impl_op_number {
         impl Div<$from> for $for {
             type Output = Measure;
 
+            #[allow(clippy::suspicious_arithmetic_impl)]
             fn div(self, measure: $from) -> Self::Output {
+                let unit = measure.unit.powf(-1.0);
+                let provenance =
+                    Provenance::derive("div", &[&measure.provenance], &[("dividend", self as f64)]);
+                let num = self as f64;
+                let (value, error) = map_operand(measure.operand(), |val, err| {
+                    (num / val, num.abs() * err / val.powi(2))
+                });
                 Measure {
-                    value: measure
-                        .value
-                        .iter()
-                        .map(|val| (self as f64) / val)
-                        .collect(),
-                    error: measure
-                        .iter()
-                        .map(|(val, err)| (self as f64).abs() * err / val.powi(2))
-                        .collect(),
+                    value,
+                    error,
                     style: Style::PM,
+                    unit,
+                    name: None,
+                    metadata: Metadata::default(),
+                    provenance,
                 }
             }
         }
     };
 }
+
+/// Internal macro to implement operations traits between every type of number
+/// and a scalar measure.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_scalar_op_number {
+    ($for:ty) => {
+        impl Add<ScalarMeasure> for $for {
+            type Output = ScalarMeasure;
+
+            fn add(self, measure: ScalarMeasure) -> Self::Output {
+                ScalarMeasure {
+                    value: (self as f64) + measure.value,
+                    error: measure.error,
+                }
+            }
+        }
+
+        impl Sub<ScalarMeasure> for $for {
+            type Output = ScalarMeasure;
+
+            fn sub(self, measure: ScalarMeasure) -> Self::Output {
+                ScalarMeasure {
+                    value: (self as f64) - measure.value,
+                    error: measure.error,
+                }
+            }
+        }
+
+        impl Mul<ScalarMeasure> for $for {
+            type Output = ScalarMeasure;
+
+            fn mul(self, measure: ScalarMeasure) -> Self::Output {
+                ScalarMeasure {
+                    value: (self as f64) * measure.value,
+                    error: (self as f64).abs() * measure.error,
+                }
+            }
+        }
+
+        impl Div<ScalarMeasure> for $for {
+            type Output = ScalarMeasure;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, measure: ScalarMeasure) -> Self::Output {
+                ScalarMeasure {
+                    value: (self as f64) / measure.value,
+                    error: (self as f64).abs() * measure.error / measure.value.powi(2),
+                }
+            }
+        }
+    };
+}
+
+/// Internal macro to implement operations traits between measures and scalar
+/// measures. The scalar measure takes the unit of the measure when adding or
+/// subtracting.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_op_scalar {
+    ($measure:ty, $scalar:ty) => {
+        impl Add<$scalar> for $measure {
+            type Output = Measure;
+
+            fn add(self, other: $scalar) -> Self::Output {
+                let unit = self.unit().clone();
+                self + Measure::from(other).with_unit(unit)
+            }
+        }
+
+        impl Sub<$scalar> for $measure {
+            type Output = Measure;
+
+            fn sub(self, other: $scalar) -> Self::Output {
+                let unit = self.unit().clone();
+                self - Measure::from(other).with_unit(unit)
+            }
+        }
+
+        impl Mul<$scalar> for $measure {
+            type Output = Measure;
+
+            fn mul(self, other: $scalar) -> Self::Output {
+                self * Measure::from(other)
+            }
+        }
+
+        impl Div<$scalar> for $measure {
+            type Output = Measure;
+
+            fn div(self, other: $scalar) -> Self::Output {
+                self / Measure::from(other)
+            }
+        }
+
+        impl Add<$measure> for $scalar {
+            type Output = Measure;
+
+            fn add(self, other: $measure) -> Self::Output {
+                Measure::from(self).with_unit(other.unit().clone()) + other
+            }
+        }
+
+        impl Sub<$measure> for $scalar {
+            type Output = Measure;
+
+            fn sub(self, other: $measure) -> Self::Output {
+                Measure::from(self).with_unit(other.unit().clone()) - other
+            }
+        }
+
+        impl Mul<$measure> for $scalar {
+            type Output = Measure;
+
+            fn mul(self, other: $measure) -> Self::Output {
+                Measure::from(self) * other
+            }
+        }
+
+        impl Div<$measure> for $scalar {
+            type Output = Measure;
+
+            fn div(self, other: $measure) -> Self::Output {
+                Measure::from(self) / other
+            }
+        }
+    };
+}
+
+/// Internal macro to implement compound assignment operations traits on a
+/// measure, which behave as the binary operation followed by an assignment.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_op_assign {
+    (<$num:ident>) => {
+        impl<$num: std::convert::Into<f64>> AddAssign<$num> for Measure {
+            fn add_assign(&mut self, num: $num) {
+                *self = take(self) + num;
+            }
+        }
+
+        impl<$num: std::convert::Into<f64>> SubAssign<$num> for Measure {
+            fn sub_assign(&mut self, num: $num) {
+                *self = take(self) - num;
+            }
+        }
+
+        impl<$num: std::convert::Into<f64>> MulAssign<$num> for Measure {
+            fn mul_assign(&mut self, num: $num) {
+                *self = take(self) * num;
+            }
+        }
+
+        impl<$num: std::convert::Into<f64>> DivAssign<$num> for Measure {
+            fn div_assign(&mut self, num: $num) {
+                *self = take(self) / num;
+            }
+        }
+    };
+    ($from:ty) => {
+        impl AddAssign<$from> for Measure {
+            fn add_assign(&mut self, other: $from) {
+                *self = take(self) + other;
+            }
+        }
+
+        impl SubAssign<$from> for Measure {
+            fn sub_assign(&mut self, other: $from) {
+                *self = take(self) - other;
+            }
+        }
+
+        impl MulAssign<$from> for Measure {
+            fn mul_assign(&mut self, other: $from) {
+                *self = take(self) * other;
+            }
+        }
+
+        impl DivAssign<$from> for Measure {
+            fn div_assign(&mut self, other: $from) {
+                *self = take(self) / other;
+            }
+        }
+    };
+}
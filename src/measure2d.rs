@@ -0,0 +1,262 @@
+//! Contains the struct Measure2D, a matrix of values with their errors.
+use {
+    crate::{
+        objects::{Measure, MyError},
+        units::Unit,
+    },
+    std::{
+        fmt::Display,
+        ops::{Add, Div, Mul, Sub},
+    },
+};
+
+/// Matrix of values with their errors, like an image or the readings of
+/// several channels, stored by rows. The rows and columns are extracted as
+/// [Measure] and the operations are applied element by element, propagating
+/// the errors as the operations of measures.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, Measure2D};
+/// let counts = Measure2D::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![0.1], 2).unwrap();
+///
+/// assert_eq!(counts.shape(), (2, 3));
+/// assert_eq!(counts.row(1).value(), &vec![4.0, 5.0, 6.0]);
+/// assert_eq!(counts.column(2).value(), &vec![3.0, 6.0]);
+/// assert_eq!(counts.transpose().row(2).value(), &vec![3.0, 6.0]);
+/// assert_eq!((&counts * 2.0).get(0, 1), Some((4.0, 0.2)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure2D {
+    measure: Measure,
+    rows: usize,
+    columns: usize,
+}
+
+impl Measure2D {
+    /// Constructs a new Measure2D from its values by rows, with one error for
+    /// all values or one for each value. Panics if the values can't be split
+    /// in the given number of rows.
+    pub fn new(value: Vec<f64>, error: Vec<f64>, rows: usize) -> Result<Measure2D, MyError> {
+        Ok(Measure2D::from_measure(
+            Measure::new(value, error, false)?,
+            rows,
+        ))
+    }
+    /// Reshapes a measure into a matrix with the given number of rows. Panics
+    /// if its length can't be split in that number of rows.
+    pub fn from_measure(measure: Measure, rows: usize) -> Measure2D {
+        assert!(
+            rows > 0 && measure.len().is_multiple_of(rows),
+            "Can't split a measure of length {} in {} rows.",
+            measure.len(),
+            rows
+        );
+        Measure2D {
+            columns: measure.len() / rows,
+            measure,
+            rows,
+        }
+    }
+    /// Stacks measures of the same length as the rows of a matrix. The unit
+    /// and style are taken from the first one. Panics if there are no rows or
+    /// their lengths are diferent.
+    pub fn from_rows(rows: &[Measure]) -> Measure2D {
+        assert!(!rows.is_empty(), "Can't stack an empty list of rows.");
+        let columns = rows[0].len();
+        let (mut value, mut error) = (Vec::new(), Vec::new());
+        for row in rows {
+            assert_eq!(
+                row.len(),
+                columns,
+                "Measures lengths must be equals, obtained {} and {}.",
+                columns,
+                row.len()
+            );
+            value.extend(row.value());
+            error.extend(row.error());
+        }
+        let measure = Measure::new(value, error, false)
+            .unwrap()
+            .with_unit(rows[0].unit().clone())
+            .change_style(*rows[0].style());
+        Measure2D::from_measure(measure, rows.len())
+    }
+
+    /// Returns the number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.measure.len()
+    }
+    /// Checks if the matrix is empty.
+    pub fn is_empty(&self) -> bool {
+        self.measure.is_empty()
+    }
+    /// Returns the unit.
+    pub fn unit(&self) -> &Unit {
+        self.measure.unit()
+    }
+    /// Changes the unit.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.measure = self.measure.with_unit(unit);
+        self
+    }
+    /// Returns all the values and errors as a measure, by rows.
+    pub fn as_measure(&self) -> &Measure {
+        &self.measure
+    }
+    /// Returns the value and error at a row and column.
+    pub fn get(&self, row: usize, column: usize) -> Option<(f64, f64)> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+        self.measure
+            .get(row * self.columns + column)
+            .map(|(val, err)| (*val, *err))
+    }
+    /// Returns a row as a measure. Panics if it is out of bounds.
+    pub fn row(&self, row: usize) -> Measure {
+        assert!(row < self.rows, "Row {} out of {} rows.", row, self.rows);
+        self.measure
+            .slice(row * self.columns..(row + 1) * self.columns)
+            .to_measure()
+    }
+    /// Returns a column as a measure. Panics if it is out of bounds.
+    pub fn column(&self, column: usize) -> Measure {
+        assert!(
+            column < self.columns,
+            "Column {} out of {} columns.",
+            column,
+            self.columns
+        );
+        let indices: Vec<usize> = (0..self.rows)
+            .map(|row| row * self.columns + column)
+            .collect();
+        self.measure.reorder(&indices)
+    }
+    /// Iterates over the rows as measures.
+    pub fn rows(&self) -> impl Iterator<Item = Measure> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+    /// Iterates over the columns as measures.
+    pub fn columns(&self) -> impl Iterator<Item = Measure> + '_ {
+        (0..self.columns).map(|column| self.column(column))
+    }
+    /// Swaps the rows and the columns.
+    pub fn transpose(&self) -> Measure2D {
+        let indices: Vec<usize> = (0..self.columns)
+            .flat_map(|column| (0..self.rows).map(move |row| row * self.columns + column))
+            .collect();
+        Measure2D {
+            measure: self.measure.reorder(&indices),
+            rows: self.columns,
+            columns: self.rows,
+        }
+    }
+    /// Applies an operation of measures to all the values, which must return
+    /// a measure of the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{Measure, Measure2D};
+    /// let areas = Measure2D::new(vec![1.0, 4.0, 9.0, 16.0], vec![0.2], 2).unwrap();
+    ///
+    /// assert_eq!(areas.map(Measure::sqrt).get(1, 1), Some((4.0, 0.025)));
+    /// ```
+    pub fn map(&self, function: impl Fn(&Measure) -> Measure) -> Measure2D {
+        let measure = function(&self.measure);
+        assert_eq!(
+            measure.len(),
+            self.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            measure.len()
+        );
+        Measure2D {
+            measure,
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+    // Applies an operation of measures between two matrices of the same shape.
+    fn zip_with(
+        &self,
+        other: &Measure2D,
+        function: impl Fn(&Measure, &Measure) -> Measure,
+    ) -> Self {
+        assert_eq!(
+            self.shape(),
+            other.shape(),
+            "Matrices shapes must be equals, obtained {:?} and {:?}.",
+            self.shape(),
+            other.shape()
+        );
+        self.map(|measure| function(measure, &other.measure))
+    }
+}
+
+impl From<Measure2D> for Measure {
+    /// All the values and errors of the matrix, by rows.
+    fn from(matrix: Measure2D) -> Measure {
+        matrix.measure
+    }
+}
+
+impl Display for Measure2D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+// Element-wise operations between matrices and with numbers.
+macro_rules! impl_matrix_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait<&Measure2D> for &Measure2D {
+            type Output = Measure2D;
+
+            fn $method(self, other: &Measure2D) -> Measure2D {
+                self.zip_with(other, |a, b| a.$method(b))
+            }
+        }
+
+        impl $trait<Measure2D> for Measure2D {
+            type Output = Measure2D;
+
+            fn $method(self, other: Measure2D) -> Measure2D {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<f64> for &Measure2D {
+            type Output = Measure2D;
+
+            fn $method(self, other: f64) -> Measure2D {
+                self.map(|a| a.$method(other))
+            }
+        }
+
+        impl $trait<f64> for Measure2D {
+            type Output = Measure2D;
+
+            fn $method(self, other: f64) -> Measure2D {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+
+impl_matrix_op!(Add, add);
+impl_matrix_op!(Sub, sub);
+impl_matrix_op!(Mul, mul);
+impl_matrix_op!(Div, div);
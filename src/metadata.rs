@@ -0,0 +1,104 @@
+//! Contains the struct Metadata, used to keep track of how data was measured.
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Measurement protocol information (instrument, operator, date, settings...)
+/// that can be attached to measures and emitted on tables and data files.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::Metadata;
+/// let metadata = Metadata::new()
+///     .instrument("Oscilloscope TDS2012")
+///     .operator("Ó. Castro")
+///     .set("sampling", "1 kHz");
+///
+/// assert_eq!(Metadata::from_header(&metadata.to_header("#"), "#"), metadata);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Metadata {
+    entries: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    /// Constructs an empty Metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets any entry.
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.entries.insert(key.into(), value.into());
+        self
+    }
+    /// Sets the instrument used for measuring.
+    pub fn instrument(self, instrument: impl Into<String>) -> Self {
+        self.set("instrument", instrument)
+    }
+    /// Sets who took the measures.
+    pub fn operator(self, operator: impl Into<String>) -> Self {
+        self.set("operator", operator)
+    }
+    /// Sets when the measures were taken.
+    pub fn date(self, date: impl Into<String>) -> Self {
+        self.set("date", date)
+    }
+    /// Returns the value of an entry.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|val| val.as_str())
+    }
+    /// Removes an entry, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Checks if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Iterates over the entries as tuples (key, value) sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, val)| (key.as_str(), val.as_str()))
+    }
+    /// Adds the entries of other metadata, overwriting the repeated ones.
+    pub fn merge(mut self, other: &Metadata) -> Self {
+        self.entries.extend(other.entries.clone());
+        self
+    }
+    /// Writes every entry in a line "key: value" preceded by the comment prefix,
+    /// for example "#" for data files, "%" for latex or "//" for typst.
+    pub fn to_header(&self, prefix: &str) -> String {
+        self.iter()
+            .map(|(key, val)| format!("{} {}: {}\n", prefix, key, val))
+            .collect()
+    }
+    /// Reads the entries from the lines starting with the comment prefix,
+    /// ignoring every other line.
+    pub fn from_header(text: &str, prefix: &str) -> Self {
+        Metadata {
+            entries: text
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix(prefix))
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, val)| (key.trim().to_string(), val.trim().to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.iter()
+                .map(|(key, val)| format!("{}: {}", key, val))
+                .collect::<Vec<String>>()
+                .join("; ")
+        )
+    }
+}
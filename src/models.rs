@@ -0,0 +1,167 @@
+//! Common models of the laboratory to fit with [CurveFit], with heuristics to
+//! guess their initial parameters from the data.
+//!
+//! Every model takes the value of x and the parameters in the order of its
+//! documentation, so it can be passed to [CurveFit::new] with any other
+//! initial point.
+//!
+//! ```rust
+//! # use ferrilab::{models, CurveFit};
+//! let x = [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0];
+//! let y = [0.4, 1.8, 4.1, 5.9, 4.2, 1.9];
+//!
+//! let peak = CurveFit::gaussian(x, y).fit();
+//! assert!((peak[1].value - 0.9).abs() < 0.2);
+//!
+//! let same_peak = CurveFit::new(models::gaussian, x, y)
+//!     .initial_point(models::gaussian_guess(&x, &y))
+//!     .fit();
+//! assert_eq!(peak, same_peak);
+//! ```
+use crate::{CurveFit, LinearFit};
+
+/// Model as a function of x and the parameters.
+pub type Model = fn(&f64, &[f64]) -> f64;
+
+/// Gaussian peak `A·exp(-(x - μ)²/(2σ²))`, with parameters `[A, μ, σ]`.
+pub fn gaussian(x: &f64, p: &[f64]) -> f64 {
+    p[0] * (-(x - p[1]).powi(2) / (2.0 * p[2].powi(2))).exp()
+}
+
+/// Lorentzian peak `A·γ²/((x - x₀)² + γ²)`, with parameters `[A, x₀, γ]`,
+/// being `γ` the half width at half maximum.
+pub fn lorentzian(x: &f64, p: &[f64]) -> f64 {
+    p[0] * p[2].powi(2) / ((x - p[1]).powi(2) + p[2].powi(2))
+}
+
+/// Exponential decay `A·exp(-x/τ)`, with parameters `[A, τ]`.
+pub fn exponential(x: &f64, p: &[f64]) -> f64 {
+    p[0] * (-x / p[1]).exp()
+}
+
+/// Power law `A·x^k`, with parameters `[A, k]`.
+pub fn power_law(x: &f64, p: &[f64]) -> f64 {
+    p[0] * x.powf(p[1])
+}
+
+/// Guesses the parameters of a [gaussian] from the highest point and the
+/// width of the points above half of it.
+pub fn gaussian_guess(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let (height, center, half_width) = peak(x, y);
+    // The half width at half maximum of a gaussian is σ·√(2 ln 2).
+    vec![height, center, half_width / (2.0 * 2_f64.ln()).sqrt()]
+}
+
+/// Guesses the parameters of a [lorentzian] from the highest point and the
+/// width of the points above half of it.
+pub fn lorentzian_guess(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let (height, center, half_width) = peak(x, y);
+    vec![height, center, half_width]
+}
+
+/// Guesses the parameters of an [exponential] with a linear fit of the
+/// logarithm of the positive values of y.
+pub fn exponential_guess(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let (x, log_y) = logarithms(x, y, |_| true);
+    if x.len() < 2 {
+        return vec![1.0, 1.0];
+    }
+    let (slope, intercept) = LinearFit::new(x, log_y).fit();
+    vec![intercept.value.exp(), -1.0 / slope.value]
+}
+
+/// Guesses the parameters of a [power_law] with a linear fit of the
+/// logarithms of the points with positive x and y.
+pub fn power_law_guess(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let (x, log_y) = logarithms(x, y, |x| x > 0.0);
+    if x.len() < 2 {
+        return vec![1.0, 1.0];
+    }
+    let log_x: Vec<f64> = x.iter().map(|x| x.ln()).collect();
+    let (exponent, intercept) = LinearFit::new(log_x, log_y).fit();
+    vec![intercept.value.exp(), exponent.value]
+}
+
+// Height and position of the highest point, and the half width at half maximum
+// from the range of x of the points above half of it, or a quarter of the range
+// of x if there is only one.
+fn peak(x: &[f64], y: &[f64]) -> (f64, f64, f64) {
+    let Some(top) = (0..y.len()).max_by(|i, j| y[*i].total_cmp(&y[*j])) else {
+        return (1.0, 0.0, 1.0);
+    };
+    let (height, center) = (y[top], x[top]);
+    let (min, max) = x
+        .iter()
+        .zip(y)
+        .filter(|(_, y)| **y >= height / 2.0)
+        .fold((center, center), |(min, max), (x, _)| {
+            (min.min(*x), max.max(*x))
+        });
+    let half_width = if max > min {
+        (max - min) / 2.0
+    } else {
+        let (min, max) = x
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                (min.min(*x), max.max(*x))
+            });
+        (max - min) / 4.0
+    };
+    (
+        height,
+        center,
+        if half_width > 0.0 { half_width } else { 1.0 },
+    )
+}
+
+// Values of x and logarithms of y of the points with positive y whose x
+// satisfies a condition.
+fn logarithms(x: &[f64], y: &[f64], condition: impl Fn(f64) -> bool) -> (Vec<f64>, Vec<f64>) {
+    x.iter()
+        .zip(y)
+        .filter(|(x, y)| **y > 0.0 && condition(**x))
+        .map(|(x, y)| (*x, y.ln()))
+        .unzip()
+}
+
+impl CurveFit<Model> {
+    /// Constructs a CurveFit of a [gaussian] peak with the initial point
+    /// guessed from the data.
+    pub fn gaussian(x_values: impl Into<Vec<f64>>, y_values: impl Into<Vec<f64>>) -> Self {
+        CurveFit::guessed(gaussian, gaussian_guess, x_values.into(), y_values.into())
+    }
+    /// Constructs a CurveFit of a [lorentzian] peak with the initial point
+    /// guessed from the data.
+    pub fn lorentzian(x_values: impl Into<Vec<f64>>, y_values: impl Into<Vec<f64>>) -> Self {
+        CurveFit::guessed(
+            lorentzian,
+            lorentzian_guess,
+            x_values.into(),
+            y_values.into(),
+        )
+    }
+    /// Constructs a CurveFit of an [exponential] decay with the initial point
+    /// guessed from the data.
+    pub fn exponential(x_values: impl Into<Vec<f64>>, y_values: impl Into<Vec<f64>>) -> Self {
+        CurveFit::guessed(
+            exponential,
+            exponential_guess,
+            x_values.into(),
+            y_values.into(),
+        )
+    }
+    /// Constructs a CurveFit of a [power_law] with the initial point guessed
+    /// from the data.
+    pub fn power_law(x_values: impl Into<Vec<f64>>, y_values: impl Into<Vec<f64>>) -> Self {
+        CurveFit::guessed(power_law, power_law_guess, x_values.into(), y_values.into())
+    }
+    fn guessed(
+        model: Model,
+        guess: fn(&[f64], &[f64]) -> Vec<f64>,
+        x_values: Vec<f64>,
+        y_values: Vec<f64>,
+    ) -> Self {
+        let initial_point = guess(&x_values, &y_values);
+        CurveFit::new(model, x_values, y_values).initial_point(initial_point)
+    }
+}
@@ -0,0 +1,153 @@
+//! Contains the struct Moments, a single-pass accumulator of the statistics of
+//! the values of a measure.
+
+/// Mean and central moments up to the fourth of a sequence of values,
+/// accumulated in a single pass with the Welford algorithm, which doesn't lose
+/// precision when the values are large compared to their spread. The values
+/// can be pushed one by one, so the statistics of a file can be computed
+/// without storing it, and accumulators of several chunks can be merged.
+///
+/// The statistics of [Measure](crate::Measure) are computed with it, so
+/// [Measure::moments](crate::Measure::moments) gives all of them in one pass.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, Moments};
+/// let mut moments: Moments = [2.0, 4.0, 4.0, 4.0].into_iter().collect();
+/// moments.extend([5.0, 5.0, 7.0, 9.0]);
+///
+/// assert_eq!(moments.mean(), 5.0);
+/// assert_eq!(moments.population_variance(), 4.0);
+/// assert_eq!(moments, measure!([2, 4, 4, 4, 5, 5, 7, 9]).moments());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Moments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    /// Constructs an empty accumulator.
+    pub fn new() -> Moments {
+        Moments::default()
+    }
+    /// Adds a value.
+    pub fn push(&mut self, value: f64) {
+        self.n += 1;
+        let n = self.n as f64;
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        self.mean += delta_n;
+        // Equal to `delta² (n - 1) / n`, but rounded as the classic Welford
+        // update of the sum of squares.
+        let term = delta * (value - self.mean);
+        self.m4 += term * delta_n.powi(2) * (n * n - 3.0 * n + 3.0)
+            + 6.0 * delta_n.powi(2) * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+    /// Adds the values accumulated by another accumulator, as if they had
+    /// been pushed to this one.
+    pub fn merge(&mut self, other: &Moments) {
+        if other.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = *other;
+            return;
+        }
+        let (na, nb) = (self.n as f64, other.n as f64);
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let m2 = self.m2 + other.m2 + delta.powi(2) * na * nb / n;
+        let m3 = self.m3
+            + other.m3
+            + delta.powi(3) * na * nb * (na - nb) / n.powi(2)
+            + 3.0 * delta * (na * other.m2 - nb * self.m2) / n;
+        let m4 = self.m4
+            + other.m4
+            + delta.powi(4) * na * nb * (na * na - na * nb + nb * nb) / n.powi(3)
+            + 6.0 * delta.powi(2) * (na * na * other.m2 + nb * nb * self.m2) / n.powi(2)
+            + 4.0 * delta * (na * other.m3 - nb * self.m3) / n;
+        *self = Moments {
+            n: self.n + other.n,
+            mean: self.mean + delta * nb / n,
+            m2,
+            m3,
+            m4,
+        };
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+    /// Checks if no value has been added.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+    /// Returns the mean, NaN without values.
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+    /// Returns the central moment of an order from 2 to 4, dividing by `n`.
+    /// Panics for other orders.
+    pub fn central_moment(&self, order: i32) -> f64 {
+        let sum = match order {
+            2 => self.m2,
+            3 => self.m3,
+            4 => self.m4,
+            _ => panic!("Only the central moments 2, 3 and 4 are accumulated."),
+        };
+        sum / self.n as f64
+    }
+    /// Returns the sample variance, dividing by `n - 1`.
+    pub fn variance(&self) -> f64 {
+        self.m2 / (self.n as f64 - 1.0)
+    }
+    /// Returns the population variance, dividing by `n`.
+    pub fn population_variance(&self) -> f64 {
+        self.central_moment(2)
+    }
+    /// Returns the standard desviation.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+    /// Returns the standard error of the mean.
+    pub fn standard_error(&self) -> f64 {
+        self.standard_deviation() / (self.n as f64).sqrt()
+    }
+    /// Returns the skewness, `m3 / m2^(3/2)`.
+    pub fn skewness(&self) -> f64 {
+        self.central_moment(3) / self.central_moment(2).powf(1.5)
+    }
+    /// Returns the excess kurtosis, `m4 / m2² - 3`.
+    pub fn kurtosis(&self) -> f64 {
+        self.central_moment(4) / self.central_moment(2).powi(2) - 3.0
+    }
+}
+
+impl Extend<f64> for Moments {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<f64> for Moments {
+    fn from_iter<I: IntoIterator<Item = f64>>(values: I) -> Moments {
+        let mut moments = Moments::new();
+        moments.extend(values);
+        moments
+    }
+}
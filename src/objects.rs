@@ -2,21 +2,66 @@
 use {
     crate::{
         aprox::{aprox, round},
-        impl_op, impl_op_number,
+        check::{check_display, DisplayMismatch},
+        dataset::{Dataset, ResampleMethod},
+        dual::Dual,
+        impl_op, impl_op_assign, impl_op_number,
+        language::Message,
+        metadata::Metadata,
+        moments::Moments,
+        perf::{count, Counter},
+        provenance::Provenance,
+        random::Rng,
+        scalar::ScalarMeasure,
+        special::{normal_cdf, student_t_two_sided},
+        units::{accepts_prefix, si_prefix, Unit},
+        view::MeasureView,
     },
     std::{
+        borrow::Cow,
+        cmp::Ordering,
         f64::consts::PI,
-        fmt::Display,
-        ops::{Add, Div, Mul, Sub},
+        fmt::{Display, LowerExp, UpperExp},
+        iter::{Product, Sum},
+        ops::{Add, AddAssign, Bound, Div, DivAssign, Mul, MulAssign, RangeBounds, Sub, SubAssign},
+        sync::Arc,
     },
 };
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
 /// Essential object to store and manage measures.
+///
+/// The values and errors are stored as `f64`, see [GenericMeasure] to store
+/// them as `f32`.
+pub type Measure = GenericMeasure<f64>;
+
+/// Measure whose values and errors are stored as any [Float], by default
+/// `f64`. The measures stored as `f32`, that need half the memory, support the
+/// arithmetic operators, computed in `f64` and stored back as `f32`. The rest
+/// of the operations are implemented for [Measure], so they are converted with
+/// [GenericMeasure::cast] to process them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{GenericMeasure, Measure};
+/// let acquisition = GenericMeasure::<f32>::new(vec![1.5, 2.5], vec![0.1], false).unwrap();
+/// assert_eq!((&acquisition * 2.0).value(), &vec![3.0_f32, 5.0]);
+///
+/// let voltage: Measure = acquisition.cast();
+/// assert_eq!(voltage.ln().len(), 2);
+/// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct Measure {
-    value: Vec<f64>,
-    error: Vec<f64>,
+pub struct GenericMeasure<T = f64> {
+    value: Vec<T>,
+    error: Vec<T>,
     style: Style,
+    unit: Unit,
+    name: Option<String>,
+    metadata: Metadata,
+    provenance: Option<Arc<Provenance>>,
 }
 
 /// Diferent style types for print measures.
@@ -32,13 +77,69 @@ pub enum Style {
     LatexTable,
     /// $value plus.minus error$
     TypstTable,
+    /// value ± error, ... scaled with the SI prefix that fits the magnitude of
+    /// the value, e.g. 1.23 mV ± 0.04 mV
+    SI,
+    /// value ± error, one per line, scaled to a common exponent and padded so
+    /// the columns line up, e.g.
+    /// ```text
+    /// ( 0.0012 ± 0.0001)e3
+    /// (12.3400 ± 0.2000)e3
+    /// ```
+    Aligned,
+    /// value ± error, ... with the power of ten of each value factored out,
+    /// e.g. (1.23 ± 0.04)×10⁻³
+    Scientific,
+    /// $(value \pm error) \times 10^{exponent}$
+    LatexScientific,
+    /// $(value plus.minus error) times 10^(exponent)$
+    TypstScientific,
+    /// value(error), ... with the error in units of the last digit of the
+    /// value, e.g. 1.234(5)
+    Compact,
+    /// $value(error)$
+    LatexCompact,
+    /// $value(error)$
+    TypstCompact,
+}
+
+/// Floating point types a [GenericMeasure] can store.
+pub trait Float: Copy + PartialEq + PartialOrd + std::fmt::Debug + Send + Sync {
+    /// Converts the number to `f64`.
+    fn to_f64(self) -> f64;
+    /// Converts a `f64` to the nearest number of this type.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Float for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+impl Float for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
 }
 
-#[doc(hidden)]
+/// Errors of the fallible constructors and operations of measures.
 #[derive(Debug, thiserror::Error)]
 pub enum MyError {
     #[error("You're only allowed to assign either one error for all values or one error for each value.")]
     InvalidErrorLen,
+    #[error("Expected a measure with only one value, obtained {0}.")]
+    NotScalar(usize),
+    #[error("Measures lengths must be equals, obtained {0} and {1}.")]
+    LengthMismatch(usize, usize),
+    #[error("Measures units must be compatible, obtained {0} and {1}.")]
+    IncompatibleUnits(String, String),
 }
 
 impl Measure {
@@ -69,27 +170,150 @@ impl Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: None,
         })
     }
-    /// Length of the measure.
-    pub fn len(&self) -> usize {
-        self.value.len()
+    /// Constructs a measure whose errors are a fraction of the absolute value,
+    /// with one fraction for all values or one for each value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::Measure;
+    /// let current = Measure::with_relative_error(vec![2.0, -4.0], vec![0.05]).unwrap();
+    ///
+    /// assert_eq!(current.error(), &vec![0.1, 0.2]);
+    /// ```
+    pub fn with_relative_error(value: Vec<f64>, relative: Vec<f64>) -> Result<Measure, MyError> {
+        if value.len() != relative.len() && relative.len() != 1 {
+            return Err(MyError::InvalidErrorLen);
+        }
+        let error = value
+            .iter()
+            .enumerate()
+            .map(|(i, val)| val.abs() * relative[if relative.len() == 1 { 0 } else { i }])
+            .collect();
+        Measure::new(value, error, false)
     }
-    /// Checks if the measure is empty.
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    /// Constructs a measure whose errors are a percentage of the absolute value,
+    /// as the accuracy of many instruments is given.
+    pub fn with_percent_error(value: Vec<f64>, percent: Vec<f64>) -> Result<Measure, MyError> {
+        Measure::with_relative_error(value, percent.into_iter().map(|pct| pct / 100.0).collect())
     }
-    /// Returns the values of a measure.
-    pub fn value(&self) -> &Vec<f64> {
-        &self.value
+    /// Constructs a measure of one value from repeated readings of the same
+    /// magnitude, being the value their mean and the error the combination in
+    /// quadrature of their standard error (type A) and the error of the
+    /// instrument (type B), as in [Measure::estimation]. With a single reading
+    /// the error is the one of the instrument. Panics if there are no readings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::Measure;
+    /// let period = Measure::from_samples(&[1.48, 1.52], 0.01);
+    ///
+    /// assert!((period.value()[0] - 1.5).abs() < 1e-12);
+    /// assert!((period.error()[0] - 0.02_f64.hypot(0.01)).abs() < 1e-12);
+    /// ```
+    pub fn from_samples(readings: &[f64], instrument_error: f64) -> Measure {
+        assert!(
+            !readings.is_empty(),
+            "Can't estimate a measure without readings."
+        );
+        let samples = Measure::new(readings.to_vec(), vec![instrument_error], false).unwrap();
+        let estimation = match readings.len() {
+            1 => ScalarMeasure::new(readings[0], instrument_error),
+            _ => samples.estimation(),
+        };
+        Measure::new(vec![estimation.value], vec![estimation.error], false).unwrap()
     }
-    /// Returns the error of a measure.
-    pub fn error(&self) -> &Vec<f64> {
-        &self.error
+    /// Constructs a measure of `n` values evenly spaced from `start` to `end`,
+    /// both included, without errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::Measure;
+    /// let time = Measure::linspace(0.0, 1.0, 5).with_error(0.01);
+    ///
+    /// assert_eq!(time.value(), &vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    /// assert_eq!(time.error(), &vec![0.01; 5]);
+    /// ```
+    pub fn linspace(start: f64, end: f64, n: usize) -> Measure {
+        let step = (end - start) / (n.max(2) - 1) as f64;
+        let value = (0..n)
+            .map(|i| {
+                if i + 1 == n && n > 1 {
+                    end
+                } else {
+                    start + step * i as f64
+                }
+            })
+            .collect();
+        Measure::new(value, vec![0.0; n], false).unwrap()
     }
-    /// Returns the style of a measure.
-    pub fn style(&self) -> &Style {
-        &self.style
+    /// Constructs a measure of the values from `start`, included, to `end`,
+    /// excluded, separated by `step`, without errors. Panics if the step is
+    /// zero or goes away from the end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::Measure;
+    /// assert_eq!(Measure::arange(0.0, 1.0, 0.25).value(), &vec![0.0, 0.25, 0.5, 0.75]);
+    /// assert_eq!(Measure::arange(3.0, 0.0, -1.0).value(), &vec![3.0, 2.0, 1.0]);
+    /// ```
+    pub fn arange(start: f64, end: f64, step: f64) -> Measure {
+        assert!(
+            step != 0.0 && (end - start) * step >= 0.0,
+            "Can't go from {} to {} with a step of {}.",
+            start,
+            end,
+            step
+        );
+        // The tolerance avoids an extra value at the end when the division is
+        // slightly above an integer, as in (1.0 - 0.0) / 0.1.
+        let n = ((end - start) / step - 1e-9).ceil().max(0.0) as usize;
+        let value = (0..n).map(|i| start + step * i as f64).collect();
+        Measure::new(value, vec![0.0; n], false).unwrap()
+    }
+    /// Constructs a measure of `n` zeros without errors.
+    pub fn zeros(n: usize) -> Measure {
+        Measure::new(vec![0.0; n], vec![0.0; n], false).unwrap()
+    }
+    /// Sets the same error to every value.
+    pub fn with_error(mut self, error: f64) -> Self {
+        self.error = vec![error; self.len()];
+        self
+    }
+    /// Constructs a measure from the columns returned by
+    /// [Reader::read_file](crate::Reader::read_file), with one error for all
+    /// values or one for each value. The missing cells become NaN, so they can
+    /// be removed with [Measure::drop_nan] or replaced with
+    /// [Measure::fill_nan].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let column = vec![Some(1.2), None, Some(1.4)];
+    /// let voltage = Measure::from_options(&column, &[Some(0.1)]).unwrap();
+    ///
+    /// assert!(voltage.has_nan());
+    /// assert_eq!(voltage.drop_nan(), measure!([1.2, 1.4], 0.1));
+    /// ```
+    pub fn from_options(value: &[Option<f64>], error: &[Option<f64>]) -> Result<Measure, MyError> {
+        let numbers =
+            |column: &[Option<f64>]| column.iter().map(|cell| cell.unwrap_or(f64::NAN)).collect();
+        Measure::new(numbers(value), numbers(error), false)
+    }
+    /// Returns the relative errors, `σ/|x|`, which are infinite for the
+    /// values that are zero, or NaN if their error is also zero.
+    pub fn relative_error(&self) -> Vec<f64> {
+        self.iter().map(|(val, err)| err / val.abs()).collect()
     }
     /// Changes the style of a measure.
     pub fn change_style(self, style: Style) -> Measure {
@@ -97,8 +321,79 @@ impl Measure {
             value: self.value,
             error: self.error,
             style,
+            unit: self.unit,
+            name: self.name,
+            metadata: self.metadata,
+            provenance: self.provenance,
+        }
+    }
+    /// Returns the label of the quantity as "name / unit", e.g. "t / s", or
+    /// only the one that is available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Unit};
+    /// let time = measure!([1.2, 1.4], 0.1).with_name("t").with_unit(Unit::second());
+    ///
+    /// assert_eq!(time.label(), "t / s");
+    /// ```
+    pub fn label(&self) -> String {
+        self.styled_label(Style::PM)
+    }
+    /// Returns the label formatted for the math mode of latex and typst tables.
+    pub(crate) fn styled_label(&self, style: Style) -> String {
+        let (name, unit) = match style {
+            Style::LatexTable => (
+                self.name.as_ref().map(|name| format!("${}$", name)),
+                format!("${}$", self.unit.latex()),
+            ),
+            Style::TypstTable => (
+                self.name.as_ref().map(|name| format!("${}$", name)),
+                format!("${}$", self.unit.typst()),
+            ),
+            _ => (self.name.clone(), self.unit.symbol()),
+        };
+        // Dimensionless units are only shown if they have a symbol, like the
+        // scale of an autoscaled measure.
+        match (
+            name,
+            self.unit.is_dimensionless() && self.unit.custom_symbol().is_none(),
+        ) {
+            (Some(name), false) => format!("{} / {}", name, unit),
+            (Some(name), true) => name,
+            (None, false) => unit,
+            (None, true) => String::new(),
         }
     }
+    /// Changes the measurement protocol metadata of a measure.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Measure {
+        self.metadata = metadata;
+        self
+    }
+    /// Starts recording the operations applied to this measure and every
+    /// measure derived from it, naming it in the operation tree.
+    pub fn track(mut self, name: impl Into<String>) -> Measure {
+        self.provenance = Some(Arc::new(Provenance::leaf(name)));
+        self
+    }
+    /// Returns the operation tree that produced the measure, if it was tracked.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_deref()
+    }
+    /// Records the operation that produced the measure from other measures,
+    /// used by the functions outside this module.
+    pub(crate) fn derived(
+        mut self,
+        operation: &str,
+        inputs: &[&Measure],
+        parameters: &[(&str, f64)],
+    ) -> Measure {
+        let inputs: Vec<&Option<Arc<Provenance>>> =
+            inputs.iter().map(|input| &input.provenance).collect();
+        self.provenance = Provenance::derive(operation, &inputs, parameters);
+        self
+    }
     /// Returns a tuple (values, error)
     pub fn unpack(&self) -> (&Vec<f64>, &Vec<f64>) {
         (&self.value, &self.error)
@@ -110,21 +405,164 @@ impl Measure {
                 value: vec![*value],
                 error: vec![*error],
                 style: Style::PM,
+                unit: self.unit.clone(),
+                name: self.name.clone(),
+                metadata: self.metadata.clone(),
+                provenance: self.provenance.clone(),
             })
             .collect()
     }
+    /// Verifies that every element of the measure printed with a style reads
+    /// back to its value and error within half a unit in the last place of the
+    /// printed error, returning the elements that don't. Useful to guard the
+    /// tables of a report against silent rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Style};
+    /// let time = measure!([0.227, 0.312], [0.012, 0.023]);
+    ///
+    /// assert!(time.check_display(Style::LatexTable).is_empty());
+    /// ```
+    pub fn check_display(&self, style: Style) -> Vec<DisplayMismatch> {
+        check_display(self, style)
+    }
     /// Iterates over a measure without taking ownership as a tuple (value, error).
-    pub fn iter(&self) -> MeasureIter {
+    pub fn iter(&self) -> MeasureIter<'_> {
         self.value.iter().zip(self.error.iter())
     }
     /// Iterates over a measure as a tuple (value, error) that allows modifying the values.
-    pub fn iter_mut(&mut self) -> MeasureIterMut {
+    pub fn iter_mut(&mut self) -> MeasureIterMut<'_> {
         self.value.iter_mut().zip(self.error.iter_mut())
     }
+    /// Iterates in parallel over a measure as a tuple (value, error). Requires
+    /// the feature `rayon`.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> MeasureParIter<'_> {
+        self.value.par_iter().zip(self.error.par_iter())
+    }
+    /// Iterates in parallel over a measure as a tuple (value, error) that
+    /// allows modifying the values. Requires the feature `rayon`.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> MeasureParIterMut<'_> {
+        self.value.par_iter_mut().zip(self.error.par_iter_mut())
+    }
     /// Returns the value and error of a certain index.
     pub fn get(&self, index: usize) -> Option<(&f64, &f64)> {
         Some((self.value.get(index)?, self.error.get(index)?))
     }
+    /// Returns the value and error of a certain index allowing to modify them.
+    /// It plays the role of `IndexMut`, that can't hand out both values at once
+    /// as they are stored in diferent vectors.
+    pub fn get_mut(&mut self, index: usize) -> Option<(&mut f64, &mut f64)> {
+        Some((self.value.get_mut(index)?, self.error.get_mut(index)?))
+    }
+    /// Returns a new measure with the values and errors of a range of indices,
+    /// or None if the range is out of bounds.
+    pub fn get_range<R: RangeBounds<usize>>(&self, range: R) -> Option<Measure> {
+        let (start, end) = self.bounds(range);
+        if start > end || end > self.len() {
+            return None;
+        }
+        Some(MeasureView::new(self, start, end).to_measure())
+    }
+    /// Returns a view of a range of indices without copying the values and
+    /// errors. Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, LinearFit};
+    /// let time = measure!([0, 1, 2, 3, 4, 5], 0.1);
+    /// let position = measure!([0.3, 0.1, 2.0, 3.1, 3.9, 5.0], 0.1);
+    ///
+    /// // Exclude the warm-up region from the fit.
+    /// let (slope, intercept) = LinearFit::new(time.skip(2), position.skip(2)).fit();
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> MeasureView<'_> {
+        let (start, end) = self.bounds(range);
+        assert!(
+            start <= end && end <= self.len(),
+            "Range {}..{} out of bounds for a measure of length {}.",
+            start,
+            end,
+            self.len()
+        );
+        MeasureView::new(self, start, end)
+    }
+    /// Returns a view of the first n values, or the whole measure if it is shorter.
+    pub fn take(&self, n: usize) -> MeasureView<'_> {
+        MeasureView::new(self, 0, n.min(self.len()))
+    }
+    /// Returns a view skipping the first n values, which is empty if the
+    /// measure is shorter.
+    pub fn skip(&self, n: usize) -> MeasureView<'_> {
+        MeasureView::new(self, n.min(self.len()), self.len())
+    }
+    /// Splits the measure in measures of `size` values, the last one being
+    /// shorter if the length is not a multiple of the size. Panics if the size
+    /// is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// // Two oscillation cycles of three readings.
+    /// let angle = measure!([0.1, 0.0, -0.1, 0.09, 0.0, -0.09], 0.01);
+    ///
+    /// let amplitudes: Vec<f64> = angle.chunks(3).iter().map(|cycle| cycle.value()[0]).collect();
+    /// assert_eq!(amplitudes, vec![0.1, 0.09]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Vec<Measure> {
+        assert!(size > 0, "The size of the chunks must be greater than 0.");
+        (0..self.len())
+            .step_by(size)
+            .map(|start| {
+                self.slice(start..(start + size).min(self.len()))
+                    .to_measure()
+            })
+            .collect()
+    }
+    /// Splits the measure in the values before an index and the ones from it.
+    /// Panics if the index is greater than the length.
+    pub fn split_at(&self, index: usize) -> (Measure, Measure) {
+        (
+            self.slice(..index).to_measure(),
+            self.slice(index..).to_measure(),
+        )
+    }
+    /// Splits the measure in the values and errors that satisfy a predicate
+    /// and the ones that don't, keeping their order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let velocity = measure!([1.2, -0.8, 1.1, -0.9], 0.1);
+    ///
+    /// let (forward, backward) = velocity.partition(|val, _| val > 0.0);
+    /// assert_eq!(forward.value(), &vec![1.2, 1.1]);
+    /// assert_eq!(backward.value(), &vec![-0.8, -0.9]);
+    /// ```
+    pub fn partition(&self, mut predicate: impl FnMut(f64, f64) -> bool) -> (Measure, Measure) {
+        let (matching, rest): (Vec<usize>, Vec<usize>) =
+            (0..self.len()).partition(|i| predicate(self.value[*i], self.error[*i]));
+        (self.reorder(&matching), self.reorder(&rest))
+    }
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end + 1,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.len(),
+        };
+        (start, end)
+    }
     /// Modify the value and error of a certain index.
     pub fn set<T, U>(&mut self, index: usize, measure: (T, U))
     where
@@ -163,34 +601,898 @@ impl Measure {
 
         self
     }
+    /// Returns the indices that would sort the measure by its values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let x = measure!([3, 1, 2], 0.1);
+    /// let y = measure!([9, 1, 4], [0.9, 0.1, 0.4]);
+    ///
+    /// let indices = x.argsort();
+    /// assert_eq!(y.reorder(&indices), measure!([1, 4, 9], [0.1, 0.4, 0.9]));
+    /// ```
+    pub fn argsort(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.sort_by(|i, j| self.value[*i].total_cmp(&self.value[*j]));
+        indices
+    }
+    /// Returns a measure with the values and errors in the order of the indices
+    /// given. Panics if some index is out of bounds.
+    pub fn reorder(&self, indices: &[usize]) -> Measure {
+        let mut measure = self.clone();
+        measure.value = indices.iter().map(|i| self.value[*i]).collect();
+        measure.error = indices.iter().map(|i| self.error[*i]).collect();
+        measure
+    }
+    /// Keeps only the values and errors that satisfy a predicate, in place and
+    /// keeping their order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let mut voltage = measure!([1.2, f64::NAN, 1.4, 9.9], [0.1, 0.1, 0.1, 5.0]);
+    ///
+    /// voltage.retain(|val, err| !val.is_nan() && err < 1.0);
+    /// assert_eq!(voltage, measure!([1.2, 1.4], 0.1));
+    /// ```
+    pub fn retain(&mut self, mut predicate: impl FnMut(f64, f64) -> bool) {
+        let keep: Vec<bool> = self
+            .iter()
+            .map(|(val, err)| predicate(*val, *err))
+            .collect();
+        let mut flags = keep.iter();
+        self.value.retain(|_| *flags.next().unwrap());
+        let mut flags = keep.iter();
+        self.error.retain(|_| *flags.next().unwrap());
+    }
+    /// Merges in place the consecutive values that agree within a tolerance
+    /// with the first one of their run, as the repeated samples of some
+    /// instrument exports, into their mean with the mean of their errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let mut pressure = measure!([101.3, 101.3, 101.5, 101.7, 101.3], [0.1, 0.3, 0.1, 0.1, 0.1]);
+    ///
+    /// pressure.dedup_by_value(0.0);
+    /// assert_eq!(pressure.value(), &vec![101.3, 101.5, 101.7, 101.3]);
+    /// assert_eq!(pressure.error()[0], 0.2);
+    /// ```
+    pub fn dedup_by_value(&mut self, tolerance: f64) {
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for i in 0..self.len() {
+            match runs.last_mut() {
+                Some((start, end)) if (self.value[i] - self.value[*start]).abs() <= tolerance => {
+                    *end = i + 1
+                }
+                _ => runs.push((i, i + 1)),
+            }
+        }
+        let mean = |numbers: &[f64]| numbers.iter().sum::<f64>() / numbers.len() as f64;
+        (self.value, self.error) = runs
+            .into_iter()
+            .map(|(start, end)| (mean(&self.value[start..end]), mean(&self.error[start..end])))
+            .unzip();
+    }
+    /// Checks if some value or error is NaN.
+    pub fn has_nan(&self) -> bool {
+        self.iter().any(|(val, err)| val.is_nan() || err.is_nan())
+    }
+    /// Removes the values whose value or error is NaN.
+    pub fn drop_nan(mut self) -> Self {
+        self.retain(|val, err| !val.is_nan() && !err.is_nan());
+        self
+    }
+    /// Replaces the NaN values with a value and error, and the remaining NaN
+    /// errors with the error.
+    pub fn fill_nan(mut self, value: f64, error: f64) -> Self {
+        for (val, err) in &mut self {
+            if val.is_nan() {
+                (*val, *err) = (value, error);
+            } else if err.is_nan() {
+                *err = error;
+            }
+        }
+        self
+    }
+    /// Reverses the order of the values and errors in place.
+    pub fn reverse(&mut self) {
+        self.value.reverse();
+        self.error.reverse();
+    }
+    /// Sorts the measure by its values, keeping each error with its value.
+    pub fn sort_by_value(self) -> Self {
+        let indices = self.argsort();
+        self.reorder(&indices)
+    }
+    /// Returns the index of the smallest value, or None if the measure is empty.
+    pub fn argmin(&self) -> Option<usize> {
+        (0..self.len()).min_by(|i, j| self.value[*i].total_cmp(&self.value[*j]))
+    }
+    /// Returns the index of the largest value, or None if the measure is empty.
+    pub fn argmax(&self) -> Option<usize> {
+        (0..self.len())
+            .rev()
+            .max_by(|i, j| self.value[*i].total_cmp(&self.value[*j]))
+    }
+    /// Groups the values of a measure in `bins` bins of the same width between
+    /// the smallest and largest values, returning the centers of the bins, with
+    /// half the width as error, and the number of values in each bin, with
+    /// `√N` as error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let energy = measure!([1.0, 1.2, 1.9, 2.1, 2.2, 3.0]);
+    ///
+    /// let (centers, counts) = energy.histogram(2);
+    /// assert_eq!(centers, measure!([1.5, 2.5], 0.5; false));
+    /// assert_eq!(counts.value(), &vec![3.0, 3.0]);
+    /// ```
+    pub fn histogram(&self, bins: usize) -> (Measure, Measure) {
+        assert!(bins > 0, "The histogram must have at least one bin.");
+        assert!(
+            !self.is_empty(),
+            "Can't calculate the histogram of an empty measure."
+        );
+        let (mut low, mut high) = self
+            .value
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), val| {
+                (low.min(*val), high.max(*val))
+            });
+        if low == high {
+            (low, high) = (low - 0.5, high + 0.5);
+        }
+        let width = (high - low) / bins as f64;
+        let mut counts = vec![0.0; bins];
+        for val in &self.value {
+            // The largest value belongs to the last bin.
+            let bin = (((val - low) / width) as usize).min(bins - 1);
+            counts[bin] += 1.0;
+        }
+        let centers = Measure {
+            value: (0..bins).map(|i| low + (i as f64 + 0.5) * width).collect(),
+            error: vec![width / 2.0; bins],
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: self.name.clone(),
+            metadata: self.metadata.clone(),
+            provenance: Provenance::derive(
+                "histogram",
+                &[&self.provenance],
+                &[("bins", bins as f64)],
+            ),
+        };
+        let counts = Measure {
+            error: counts.iter().map(|count: &f64| count.sqrt()).collect(),
+            value: counts,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: centers.provenance.clone(),
+        };
+        (centers, counts)
+    }
     /// Calculates the mean of a measure.
     pub fn mean(&self) -> f64 {
         self.value.iter().sum::<f64>() / (self.len() as f64)
     }
+    /// Accumulates the mean and central moments of the values in a single
+    /// pass, to compute several statistics without going through the values
+    /// again.
+    pub fn moments(&self) -> Moments {
+        self.value.iter().copied().collect()
+    }
+    /// Calculates the sample variance of a measure, dividing by `n - 1`.
+    pub fn variance(&self) -> f64 {
+        self.moments().variance()
+    }
+    /// Calculates the population variance of a measure, dividing by `n`.
+    pub fn population_variance(&self) -> f64 {
+        self.moments().population_variance()
+    }
     /// Calculates the standard desviation of a measure.
     pub fn standard_deviation(&self) -> f64 {
-        (self
+        self.moments().standard_deviation()
+    }
+    /// Calculates the mean ignoring the NaN values.
+    pub fn nan_mean(&self) -> f64 {
+        self.without_nan_values().mean()
+    }
+    /// Calculates the standard desviation ignoring the NaN values.
+    pub fn nan_standard_deviation(&self) -> f64 {
+        self.without_nan_values().standard_deviation()
+    }
+    /// Calculates the standard error ignoring the NaN values.
+    pub fn nan_standard_error(&self) -> f64 {
+        self.without_nan_values().standard_error()
+    }
+    // Copy of the measure without the NaN values, keeping the NaN errors as
+    // they don't affect the statistics of the values.
+    fn without_nan_values(&self) -> Measure {
+        let mut measure = self.clone();
+        measure.retain(|val, _| !val.is_nan());
+        measure
+    }
+    /// Calculates the standard desviation of the values weighted by the
+    /// inverse of their variances, as in [combine](crate::stats::combine),
+    /// corrected with the [effective_n](Measure::effective_n) so it is unbiased.
+    /// It is NaN if some value has no error.
+    pub fn weighted_std(&self) -> f64 {
+        let weights = self.inverse_variances();
+        let total: f64 = weights.iter().sum();
+        let mean = self
             .value
             .iter()
-            .map(|val| (val - self.mean()).powi(2))
+            .zip(&weights)
+            .map(|(val, weight)| weight * val)
             .sum::<f64>()
-            / (self.len() as f64 - 1.0))
-            .sqrt()
+            / total;
+        let variance = self
+            .value
+            .iter()
+            .zip(&weights)
+            .map(|(val, weight)| weight * (val - mean).powi(2))
+            .sum::<f64>()
+            / total;
+        let n = self.effective_n();
+        (variance * n / (n - 1.0)).sqrt()
     }
-    /// Calculates the standard error of a measure.
-    pub fn standard_error(&self) -> f64 {
-        self.standard_deviation() / (self.len() as f64).sqrt()
+    /// Calculates the Kish effective sample size, `(Σw)²/Σw²` with the weights
+    /// `w = 1/σ²`, the number of equally weighted values that would give the
+    /// same precision. It is the length of the measure when all the errors are
+    /// equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let heteroscedastic = measure!([1.0, 1.2, 0.9, 1.1], [0.1, 0.1, 1.0, 1.0]);
+    ///
+    /// assert!((heteroscedastic.effective_n() - 2.0).abs() < 0.1);
+    /// assert_eq!(measure!([1.0, 1.2, 0.9], 0.1).effective_n(), 3.0);
+    /// ```
+    pub fn effective_n(&self) -> f64 {
+        let weights = self.inverse_variances();
+        weights.iter().sum::<f64>().powi(2) / weights.iter().map(|w| w.powi(2)).sum::<f64>()
+    }
+    // Weights of the values, the inverse of their variances.
+    fn inverse_variances(&self) -> Vec<f64> {
+        self.error.iter().map(|err| err.powi(-2)).collect()
+    }
+    /// Calculates the mean direction of a measure of angles in radians, in
+    /// `(-π, π]`, averaging the unit vectors of the angles so the values close
+    /// to both sides of `2π` are averaged correctly. It is NaN if the angles
+    /// cancel out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let phase = measure!([6.2, 0.1, 6.25], 0.01);
+    ///
+    /// assert!(phase.mean() > 4.0);
+    /// assert!(phase.circular_mean().abs() < 0.05);
+    /// ```
+    pub fn circular_mean(&self) -> f64 {
+        let (sin, cos) = self.mean_resultant();
+        if sin.hypot(cos) < 1e-12 {
+            return f64::NAN;
+        }
+        sin.atan2(cos)
+    }
+    /// Calculates the circular standard desviation of a measure of angles in
+    /// radians, `sqrt(-2 ln R)` with `R` the length of the mean of the unit
+    /// vectors of the angles. It is close to the standard desviation for
+    /// concentrated angles and infinite if they cancel out.
+    pub fn circular_std(&self) -> f64 {
+        let (sin, cos) = self.mean_resultant();
+        if sin.hypot(cos) < 1e-12 {
+            return f64::INFINITY;
+        }
+        (-2.0 * sin.hypot(cos).min(1.0).ln()).sqrt()
+    }
+    // Mean of the sines and cosines of the values.
+    fn mean_resultant(&self) -> (f64, f64) {
+        let n = self.len() as f64;
+        let (sin, cos) = self.value.iter().fold((0.0, 0.0), |(sin, cos), val| {
+            (sin + val.sin(), cos + val.cos())
+        });
+        (sin / n, cos / n)
     }
-    /// Calculates an estimation of a measure.
-    pub fn estimation(&self) -> Measure {
+    /// Averages every `window` consecutive values of a measure, returning
+    /// `len - window + 1` values. The error of each average is the quadrature
+    /// sum of the errors in the window divided by its size. Panics if the
+    /// window is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let signal = measure!([1.0, 3.0, 2.0, 4.0], 0.2; false);
+    ///
+    /// let smooth = signal.rolling_mean(2);
+    /// assert_eq!(smooth.value(), &vec![2.0, 2.5, 3.0]);
+    /// ```
+    pub fn rolling_mean(&self, window: usize) -> Measure {
+        assert!(window > 0, "The window must contain at least one value.");
+        let size = window as f64;
         Measure {
-            value: vec![self.mean(); self.len()],
+            value: self
+                .value
+                .windows(window)
+                .map(|values| values.iter().sum::<f64>() / size)
+                .collect(),
             error: self
                 .error
-                .iter()
-                .map(|err| (self.standard_error().powi(2) + err.powi(2)).sqrt())
+                .windows(window)
+                .map(|errors| errors.iter().map(|err| err.powi(2)).sum::<f64>().sqrt() / size)
                 .collect(),
             style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(
+                "rolling_mean",
+                &[&self.provenance],
+                &[("window", size)],
+            ),
+        }
+    }
+    /// Calculates the standard desviation of every `window` consecutive values
+    /// of a measure, returning `len - window + 1` values. Panics if the window
+    /// is 0.
+    pub fn rolling_std(&self, window: usize) -> Vec<f64> {
+        assert!(window > 0, "The window must contain at least one value.");
+        self.value
+            .windows(window)
+            .map(|values| {
+                let mean = values.iter().sum::<f64>() / window as f64;
+                (values.iter().map(|val| (val - mean).powi(2)).sum::<f64>() / (window as f64 - 1.0))
+                    .sqrt()
+            })
+            .collect()
+    }
+    /// Calculates the skewness of a measure, `m3 / m2^(3/2)`, being `mk` the
+    /// central moments. It is 0 for symmetric distributions.
+    pub fn skewness(&self) -> f64 {
+        self.moments().skewness()
+    }
+    /// Calculates the excess kurtosis of a measure, `m4 / m2² - 3`, being `mk`
+    /// the central moments. It is 0 for a gaussian distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let uniform = measure!([1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(uniform.skewness(), 0.);
+    /// assert!(uniform.kurtosis() < 0.); // Lighter tails than a gaussian.
+    /// ```
+    pub fn kurtosis(&self) -> f64 {
+        self.moments().kurtosis()
+    }
+    /// Calculates the standard error of a measure.
+    pub fn standard_error(&self) -> f64 {
+        self.moments().standard_error()
+    }
+    /// Returns the mean of the values as a measure of one value, being the
+    /// error the standard error, so it can be operated propagating it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Unit};
+    /// let period = measure!([1.9, 2.0, 2.1, 2.0]).with_unit(Unit::second());
+    ///
+    /// let mean = period.mean_measure();
+    /// assert_eq!(mean.value(), &vec![2.0]);
+    /// assert_eq!(mean.unit(), &Unit::second());
+    /// let frequency = mean.recip();
+    /// ```
+    pub fn mean_measure(&self) -> Measure {
+        let moments = self.moments();
+        self.statistic("mean", moments.mean(), moments.standard_error())
+    }
+    /// Returns the standard error as a measure of one value, being the error
+    /// its own uncertainty, `σ/√(2(n - 1))` for gaussian values.
+    pub fn stderr_measure(&self) -> Measure {
+        let standard_error = self.standard_error();
+        let error = standard_error / (2.0 * (self.len() as f64 - 1.0)).sqrt();
+        self.statistic("standard_error", standard_error, error)
+    }
+    // Measure of one value with a statistic of the values, in their unit.
+    fn statistic(&self, operation: &str, value: f64, error: f64) -> Measure {
+        Measure {
+            value: vec![value],
+            error: vec![error],
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(operation, &[&self.provenance], &[]),
+        }
+    }
+    /// Calculates an estimation of a measure from repeated measurements, being
+    /// the error the combination of the standard error and the largest error of
+    /// the measurements.
+    pub fn estimation(&self) -> ScalarMeasure {
+        let moments = self.moments();
+        let error = self.error.iter().fold(0.0_f64, |acc, err| acc.max(*err));
+        ScalarMeasure {
+            value: moments.mean(),
+            error: (moments.standard_error().powi(2) + error.powi(2)).sqrt(),
+        }
+    }
+    /// Removes the outliers of repeated measurements, returning the measure
+    /// without them and the indices of the values removed. With less than 3
+    /// values nothing is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, OutlierCriterion};
+    /// let period = measure!([1.21, 1.19, 1.22, 1.20, 1.18, 1.21, 1.62], 0.01);
+    ///
+    /// let (period, removed) = period.reject_outliers(OutlierCriterion::Chauvenet);
+    /// assert_eq!(removed, vec![6]);
+    /// let period = period.estimation();
+    /// ```
+    pub fn reject_outliers(&self, criterion: OutlierCriterion) -> (Measure, Vec<usize>) {
+        let mut kept: Vec<usize> = (0..self.len()).collect();
+        let mut removed = Vec::new();
+        while kept.len() >= 3 {
+            let sample = self.reorder(&kept);
+            let (mean, deviation) = (sample.mean(), sample.standard_deviation());
+            if deviation == 0.0 || deviation.is_nan() {
+                break;
+            }
+            let n = kept.len() as f64;
+            let z = |i: &usize| (self.value[*i] - mean).abs() / deviation;
+            match criterion {
+                OutlierCriterion::Chauvenet => {
+                    let (outliers, rest) = kept
+                        .iter()
+                        .partition(|i| n * 2.0 * (1.0 - normal_cdf(z(i))) < 0.5);
+                    removed = outliers;
+                    kept = rest;
+                    break;
+                }
+                OutlierCriterion::Grubbs(alpha) => {
+                    let (position, g) = kept
+                        .iter()
+                        .map(z)
+                        .enumerate()
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap();
+                    // The statistic follows a Student's t distribution with
+                    // `n - 2` degrees of freedom, compared at level `α / 2n`.
+                    let denominator = (n - 1.0).powi(2) - n * g.powi(2);
+                    let p_value = if denominator <= 0.0 {
+                        0.0
+                    } else {
+                        let t = (n * (n - 2.0) * g.powi(2) / denominator).sqrt();
+                        (n * student_t_two_sided(t, n - 2.0)).min(1.0)
+                    };
+                    if p_value >= alpha {
+                        break;
+                    }
+                    removed.push(kept.remove(position));
+                }
+            }
+        }
+        removed.sort_unstable();
+        (self.reorder(&kept), removed)
+    }
+    /// Collapses the readings of this measure taken at the same `x`, within a
+    /// tolerance of the first `x` of each group, into their mean with its
+    /// standard error, returning the reduced `x` and readings sorted by `x`,
+    /// ready to be fitted. The `x` of each group is its mean with the largest
+    /// error, and the readings that are alone keep their error. Panics if the
+    /// lengths are diferent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let mass = measure!([0.1, 0.2, 0.1, 0.2, 0.3], 0.001);
+    /// let period = measure!([0.62, 0.89, 0.64, 0.87, 1.1], 0.01);
+    ///
+    /// let (mass, period) = period.group_by(&mass, 1e-9);
+    /// assert_eq!(mass.value(), &vec![0.1, 0.2, 0.3]);
+    /// assert_eq!(period.value(), &vec![0.63, 0.88, 1.1]);
+    /// assert_eq!(period.error()[2], 0.01);
+    /// ```
+    pub fn group_by(&self, x: &Measure, tolerance: f64) -> (Measure, Measure) {
+        assert_eq!(
+            x.len(),
+            self.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            x.len(),
+            self.len()
+        );
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in x.argsort() {
+            match groups.last_mut() {
+                Some(group) if x.value[i] - x.value[group[0]] <= tolerance => group.push(i),
+                _ => groups.push(vec![i]),
+            }
+        }
+
+        let (mut reduced_x, mut reduced_y) = (x.clone(), self.clone());
+        (reduced_x.value, reduced_x.error) = groups
+            .iter()
+            .map(|group| {
+                let group = x.reorder(group);
+                let error = group.error.iter().fold(0.0_f64, |acc, err| acc.max(*err));
+                (group.mean(), error)
+            })
+            .unzip();
+        (reduced_y.value, reduced_y.error) = groups
+            .iter()
+            .map(|group| match group[..] {
+                [i] => (self.value[i], self.error[i]),
+                _ => {
+                    let group = self.reorder(group);
+                    (group.mean(), group.standard_error())
+                }
+            })
+            .unzip();
+        (reduced_x, reduced_y)
+    }
+    /// Calculates the quantile `q` of the values of a measure, interpolating
+    /// linearly between the sorted values. The error is half the width of the
+    /// order statistics interval `nq ± √(nq(1-q))`, which covers about the 68%
+    /// of the cases, so it does not assume the values are gaussian.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let times = measure!([1.2, 1.3, 1.25, 4.8, 1.28, 1.31, 1.27]);
+    ///
+    /// let median = times.quantile(0.5);
+    /// assert_eq!(median.value, 1.28);
+    /// ```
+    pub fn quantile(&self, q: f64) -> ScalarMeasure {
+        assert!(
+            (0.0..=1.0).contains(&q),
+            "The quantile must be between 0 and 1, obtained {}.",
+            q
+        );
+        assert!(
+            !self.is_empty(),
+            "Can't calculate a quantile of an empty measure."
+        );
+        let mut sorted = self.value.clone();
+        sorted.sort_by(f64::total_cmp);
+        let last = (sorted.len() - 1) as f64;
+        let at = |position: f64| {
+            let position = position.clamp(0.0, last);
+            let (low, high) = (position.floor() as usize, position.ceil() as usize);
+            sorted[low] + (position - low as f64) * (sorted[high] - sorted[low])
+        };
+        let n = sorted.len() as f64;
+        let spread = (n * q * (1.0 - q)).sqrt();
+        ScalarMeasure {
+            value: at(last * q),
+            error: (at(last * q + spread) - at(last * q - spread)) / 2.0,
+        }
+    }
+    /// Calculates the median of the values of a measure, see [Measure::quantile].
+    pub fn median(&self) -> ScalarMeasure {
+        self.quantile(0.5)
+    }
+    /// Calculates the interquartile range of the values of a measure, the
+    /// distance between the quantiles 0.25 and 0.75. The errors of both
+    /// quantiles are added in quadrature.
+    pub fn iqr(&self) -> ScalarMeasure {
+        let (low, high) = (self.quantile(0.25), self.quantile(0.75));
+        ScalarMeasure {
+            value: high.value - low.value,
+            error: (high.error.powi(2) + low.error.powi(2)).sqrt(),
+        }
+    }
+    /// Calculates the Pearson correlation coefficient between the values of
+    /// two measures, as [LinearFit::r_value](crate::LinearFit::r_value)
+    /// without fitting, with its standard error `√((1 - r²)/(n - 2))`, NaN with
+    /// less than three values. Panics if the lengths are diferent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let voltage = measure!([1.0, 2.0, 3.0, 4.0, 5.0], 0.1);
+    /// let current = measure!([0.11, 0.19, 0.32, 0.38, 0.52], 0.01);
+    ///
+    /// let r = voltage.pearson(&current);
+    /// assert!(r.value > 0.99 && r.error < 0.1);
+    /// ```
+    pub fn pearson(&self, other: &Measure) -> ScalarMeasure {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        correlation(&self.value, &other.value)
+    }
+    /// Calculates the Spearman rank correlation coefficient between the values
+    /// of two measures, the Pearson coefficient of their ranks, so it measures
+    /// any monotonic relation and is robust to outliers. Tied values get the
+    /// mean of their ranks and the error is computed as in
+    /// [Measure::pearson]. Panics if the lengths are diferent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let time = measure!([1.0, 2.0, 3.0, 4.0, 5.0], 0.1);
+    /// let population = time.exp();
+    ///
+    /// assert_eq!(time.spearman(&population).value, 1.0);
+    /// assert!(time.pearson(&population).value < 1.0);
+    /// ```
+    pub fn spearman(&self, other: &Measure) -> ScalarMeasure {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        correlation(&ranks(&self.value), &ranks(&other.value))
+    }
+    /// Adds two measures as the operator `+`, returning an error instead of
+    /// panicking if their lengths or units don't match.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, MyError};
+    /// let a = measure!([1.0, 2.0], 0.1);
+    /// let b = measure!([1.0, 2.0, 3.0], 0.1);
+    ///
+    /// assert!(matches!(a.try_add(&b), Err(MyError::LengthMismatch(2, 3))));
+    /// assert_eq!(a.try_add(&measure!([1.0], 0.1)).unwrap(), &a + &measure!([1.0], 0.1));
+    /// ```
+    pub fn try_add(&self, other: &Measure) -> Result<Measure, MyError> {
+        self.check_operand(other, true)?;
+        Ok(self + other)
+    }
+    /// Subtracts two measures as the operator `-`, returning an error instead
+    /// of panicking if their lengths or units don't match.
+    pub fn try_sub(&self, other: &Measure) -> Result<Measure, MyError> {
+        self.check_operand(other, true)?;
+        Ok(self - other)
+    }
+    /// Multiplies two measures as the operator `*`, returning an error instead
+    /// of panicking if their lengths don't match.
+    pub fn try_mul(&self, other: &Measure) -> Result<Measure, MyError> {
+        self.check_operand(other, false)?;
+        Ok(self * other)
+    }
+    /// Divides two measures as the operator `/`, returning an error instead of
+    /// panicking if their lengths don't match.
+    pub fn try_div(&self, other: &Measure) -> Result<Measure, MyError> {
+        self.check_operand(other, false)?;
+        Ok(self / other)
+    }
+    // Checks that an element-wise operation with another measure can be done,
+    // as the operators assert, and optionally that the units are compatible.
+    fn check_operand(&self, other: &Measure, units: bool) -> Result<(), MyError> {
+        if self.len() != other.len() && self.len() != 1 && other.len() != 1 {
+            return Err(MyError::LengthMismatch(self.len(), other.len()));
+        }
+        if units && !self.unit.is_compatible(&other.unit) {
+            return Err(MyError::IncompatibleUnits(
+                self.unit.to_string(),
+                other.unit.to_string(),
+            ));
+        }
+        Ok(())
+    }
+    /// Computes the reciprocal of a measure, `1/x`.
+    pub fn recip(&self) -> Measure {
+        let (value, error) = map_elements(self, |val, err| (val.recip(), err / val.powi(2)));
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: self.unit.powf(-1.0),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("recip", &[&self.provenance], &[]),
+        }
+    }
+    /// Takes element-wise the smallest value of two measures, along with its
+    /// error. Either of them may have length 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let current = measure!([0.5, 1.2, 2.5], 0.1);
+    /// let limit = measure!(2, 0.05);
+    ///
+    /// assert_eq!(current.min_with(&limit), measure!([0.5, 1.2, 2], [0.1, 0.1, 0.05]));
+    /// ```
+    pub fn min_with(&self, other: &Measure) -> Measure {
+        self.select(other, "min", |sval, oval| sval <= oval)
+    }
+    /// Takes element-wise the largest value of two measures, along with its
+    /// error. Either of them may have length 1.
+    pub fn max_with(&self, other: &Measure) -> Measure {
+        self.select(other, "max", |sval, oval| sval >= oval)
+    }
+    fn select(&self, other: &Measure, operation: &str, keep: fn(f64, f64) -> bool) -> Measure {
+        let (value, error) = self
+            .pairs(other)
+            .map(|((sval, serr), (oval, oerr))| {
+                if keep(sval, oval) {
+                    (sval, serr)
+                } else {
+                    (oval, oerr)
+                }
+            })
+            .unzip();
+
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(operation, &[&self.provenance, &other.provenance], &[]),
+        }
+    }
+    /// Iterates element-wise over the values and errors of two measures with
+    /// compatible units, repeating the one that has length 1.
+    fn pairs<'a>(
+        &'a self,
+        other: &'a Measure,
+    ) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + 'a {
+        assert!(
+            self.len() == other.len() || self.len() == 1 || other.len() == 1,
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        assert!(
+            self.unit.is_compatible(&other.unit),
+            "Measures units must be compatible, obtained {} and {}.",
+            self.unit,
+            other.unit
+        );
+        let len = if self.is_empty() || other.is_empty() {
+            0
+        } else {
+            self.len().max(other.len())
+        };
+        (0..len).map(|i| {
+            (
+                (self.value[i % self.len()], self.error[i % self.len()]),
+                (other.value[i % other.len()], other.error[i % other.len()]),
+            )
+        })
+    }
+    /// Checks element-wise if a measure is smaller than another one even
+    /// considering their errors, that is, `x + σx < y - σy`.
+    ///
+    /// Use this instead of `<`, as the [PartialOrd] of measures compares the
+    /// values and then the errors lexicographically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let g = measure!([9.6, 9.75, 9.9], [0.1, 0.1, 0.05]);
+    /// let expected = measure!(9.81, 0.01);
+    ///
+    /// assert_eq!(g.definitely_less_than(&expected), vec![true, false, false]);
+    /// assert_eq!(g.overlaps(&expected), vec![false, true, false]);
+    /// ```
+    pub fn definitely_less_than(&self, other: &Measure) -> Vec<bool> {
+        self.pairs(other)
+            .map(|((sval, serr), (oval, oerr))| sval + serr < oval - oerr)
+            .collect()
+    }
+    /// Checks element-wise if a measure is greater than another one even
+    /// considering their errors, that is, `x - σx > y + σy`.
+    pub fn definitely_greater_than(&self, other: &Measure) -> Vec<bool> {
+        other
+            .pairs(self)
+            .map(|((oval, oerr), (sval, serr))| oval + oerr < sval - serr)
+            .collect()
+    }
+    /// Checks element-wise if the intervals `x ± σx` and `y ± σy` of two measures
+    /// overlap, so the measures are compatible.
+    pub fn overlaps(&self, other: &Measure) -> Vec<bool> {
+        self.pairs(other)
+            .map(|((sval, serr), (oval, oerr))| (sval - oval).abs() <= serr + oerr)
+            .collect()
+    }
+    /// Calculates element-wise the discrepancy of two measures in number of
+    /// standard deviations, `|x - y| / √(σx² + σy²)`. Usually the measures are
+    /// said to agree below 2 and to disagree above 3. Equal values are always
+    /// at 0 sigmas, even without errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let g = measure!([9.79, 9.95], [0.03, 0.04]);
+    /// let accepted = measure!(9.81, 0.0);
+    ///
+    /// let sigmas = g.compatible_with(&accepted);
+    /// assert!(sigmas[0] < 2.0 && sigmas[1] > 3.0);
+    /// ```
+    pub fn compatible_with(&self, other: &Measure) -> Vec<f64> {
+        self.pairs(other)
+            .map(|((sval, serr), (oval, oerr))| sigmas(sval - oval, serr.hypot(oerr)))
+            .collect()
+    }
+    /// Compares the values of two measures, ignoring the errors, with a total
+    /// order in which NaN is greater than any number. Measures with more than one
+    /// value are compared lexicographically.
+    pub fn ord_by_value(&self, other: &Measure) -> Ordering {
+        self.value
+            .iter()
+            .zip(other.value.iter())
+            .map(|(sval, oval)| sval.total_cmp(oval))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| self.len().cmp(&other.len()))
+    }
+    /// Restricts the values of a measure to the interval `[low, high]`. The
+    /// values out of the interval are replaced by the limit, which is exact, so
+    /// their error becomes 0. Panics if `low > high` or any of them is NaN.
+    pub fn clamp<T, U>(&self, low: T, high: U) -> Measure
+    where
+        T: std::convert::Into<f64>,
+        U: std::convert::Into<f64>,
+    {
+        let (low, high) = (low.into(), high.into());
+        assert!(
+            low <= high,
+            "The lower limit must not be greater than the upper one, obtained {} and {}.",
+            low,
+            high
+        );
+        let (value, error) = self
+            .iter()
+            .map(|(val, err)| {
+                if *val < low {
+                    (low, 0.0)
+                } else if *val > high {
+                    (high, 0.0)
+                } else {
+                    (*val, *err)
+                }
+            })
+            .unzip();
+
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(
+                "clamp",
+                &[&self.provenance],
+                &[("low", low), ("high", high)],
+            ),
         }
     }
     /// Raises a measure to any number.
@@ -203,155 +1505,268 @@ impl Measure {
                 .map(|(val, err)| (other * val.powf(other - 1.0) * err).abs())
                 .collect(),
             style: Style::PM,
+            unit: self.unit.powf(other),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("pow", &[&self.provenance], &[("exponent", other)]),
+        }
+    }
+    /// Raises a measure to an integer power, using the error `|n·x^(n-1)|·σ`.
+    ///
+    /// Unlike `&m * &m`, which treats both factors as independent measures and
+    /// underestimates the error by a factor `√2`, the power considers that the
+    /// same measure is multiplied by itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let side = measure!([2, 3], 0.1; false);
+    ///
+    /// let area = side.powi(2);
+    /// assert_eq!(area.error(), &vec![0.4, 0.6000000000000001]);
+    /// ```
+    pub fn powi(&self, n: i32) -> Measure {
+        let (value, error) = map_elements(self, |val, err| {
+            (val.powi(n), (n as f64 * val.powi(n - 1) * err).abs())
+        });
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: self.unit.powf(n as f64),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("pow", &[&self.provenance], &[("exponent", n as f64)]),
+        }
+    }
+    /// Raises a measure to another measure, propagating the error of both the
+    /// base and the exponent. Either of them may have length 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let base = measure!([2, 3], 0.1);
+    /// let exponent = measure!(1.5, 0.05);
+    ///
+    /// let power = base.pow_measure(&exponent);
+    /// ```
+    pub fn pow_measure(&self, other: &Measure) -> Measure {
+        assert!(
+            self.len() == other.len() || self.len() == 1 || other.len() == 1,
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        assert!(
+            other.unit.is_dimensionless(),
+            "The exponent must be dimensionless, obtained {}.",
+            other.unit
+        );
+        let unit = if other.len() == 1 {
+            self.unit.powf(other.value[0])
+        } else {
+            assert!(
+                self.unit.is_dimensionless(),
+                "A measure with units can only be raised to a single exponent, obtained {}.",
+                other.len()
+            );
+            Unit::dimensionless()
+        };
+        let len = self.len().max(other.len());
+        let (value, error) = (0..len)
+            .map(|i| {
+                let (base, berr) = (self.value[i % self.len()], self.error[i % self.len()]);
+                let (exp, eerr) = (other.value[i % other.len()], other.error[i % other.len()]);
+                let value = base.powf(exp);
+                let exponent_term = if eerr == 0.0 {
+                    0.0
+                } else {
+                    value * base.ln() * eerr
+                };
+                (
+                    value,
+                    (exp * base.powf(exp - 1.0) * berr).hypot(exponent_term),
+                )
+            })
+            .unzip();
+
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit,
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("pow", &[&self.provenance, &other.provenance], &[]),
         }
     }
     /// Converts grades in radians.
     pub fn rad(&self) -> Measure {
+        let (value, error) = map_elements(self, |val, err| (val * PI / 180.0, err * PI / 180.0));
         Measure {
-            value: self.value.iter().map(|val| val * PI / 180.0).collect(),
-            error: self.error.iter().map(|err| err * PI / 180.0).collect(),
+            value,
+            error,
             style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("rad", &[&self.provenance], &[]),
         }
     }
     /// Converts radians in grades.
     pub fn grad(&self) -> Measure {
+        let (value, error) = map_elements(self, |val, err| (val * 180.0 / PI, err * 180.0 / PI));
         Measure {
-            value: self.value.iter().map(|val| val * 180.0 / PI).collect(),
-            error: self.error.iter().map(|err| err * 180.0 / PI).collect(),
+            value,
+            error,
             style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("grad", &[&self.provenance], &[]),
         }
     }
     /// Returns the square root of a measure.
     pub fn sqrt(&self) -> Measure {
+        let (value, error) = map_elements(self, |val, err| (val.sqrt(), err / (2.0 * val.sqrt())));
         Measure {
-            value: self.value.iter().map(|val| val.sqrt()).collect(),
-            error: self
-                .iter()
-                .map(|(val, err)| err / (2.0 * val.sqrt()))
-                .collect(),
+            value,
+            error,
             style: Style::PM,
+            unit: self.unit.powf(0.5),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("sqrt", &[&self.provenance], &[]),
         }
     }
     /// Computes the absolute value of a measure.
     pub fn abs(&self) -> Measure {
+        let (value, error) = map_elements(self, |val, err| (val.abs(), err));
         Measure {
-            value: self.value.clone().iter().map(|val| val.abs()).collect(),
-            error: self.error.clone(),
+            value,
+            error,
             style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("abs", &[&self.provenance], &[]),
         }
     }
     /// Computes the sine of a measure in radians.
     pub fn sin(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.sin()).collect();
-        let error = self
-            .iter()
-            .zip(value.iter())
-            .map(|((sval, serr), value)| {
-                if *value == 1.0 || *value == -1.0 {
-                    ((sval + serr).sin() - sval.sin()).abs()
-                } else {
-                    (sval.cos() * serr).abs()
-                }
-            })
-            .collect();
+        let (value, error) = map_elements(self, |val, err| {
+            let value = val.sin();
+            if value == 1.0 || value == -1.0 {
+                (value, ((val + err).sin() - value).abs())
+            } else {
+                (value, (val.cos() * err).abs())
+            }
+        });
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("sin", &[&self.provenance], &[]),
         }
     }
     /// Computes the cosine of a measure in radians.
     pub fn cos(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.cos()).collect();
-        let error: Vec<f64> = self
-            .iter()
-            .zip(value.iter())
-            .map(|((sval, serr), value)| {
-                if *value == 1.0 || *value == -1.0 {
-                    ((sval + serr).cos() - sval.cos()).abs()
-                } else {
-                    (sval.sin() * serr).abs()
-                }
-            })
-            .collect();
+        let (value, error) = map_elements(self, |val, err| {
+            let value = val.cos();
+            if value == 1.0 || value == -1.0 {
+                (value, ((val + err).cos() - value).abs())
+            } else {
+                (value, (val.sin() * err).abs())
+            }
+        });
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("cos", &[&self.provenance], &[]),
         }
     }
     /// Computes the tangent of a measure in radians.
     pub fn tan(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.tan()).collect();
-        let error: Vec<f64> = self
-            .error
-            .iter()
-            .zip(value.iter())
-            .map(|(serr, value)| (1.0 + value.powi(2)) * serr)
-            .collect();
+        let (value, error) = map_elements(self, |val, err| {
+            let value = val.tan();
+            (value, (1.0 + value.powi(2)) * err)
+        });
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("tan", &[&self.provenance], &[]),
         }
     }
     /// Computes the arcsine of a measure in radians.
     pub fn asin(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.asin()).collect();
-        let error: Vec<f64> = self
-            .iter()
-            .map(|(val, err)| {
-                if *val != 1.0 && *val != -1.0 {
-                    err / (1.0 - val.powi(2)).sqrt()
-                } else {
-                    ((val - err).asin() - val).abs()
-                }
-            })
-            .collect();
+        let (value, error) = map_elements(self, |val, err| {
+            if val != 1.0 && val != -1.0 {
+                (val.asin(), err / (1.0 - val.powi(2)).sqrt())
+            } else {
+                (val.asin(), ((val - err).asin() - val).abs())
+            }
+        });
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("asin", &[&self.provenance], &[]),
         }
     }
     /// Computes the arccosine of a measure in radians.
     pub fn acos(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.acos()).collect();
-        let error: Vec<f64> = self
-            .iter()
-            .map(|(val, err)| {
-                if *val != 1.0 && *val != -1.0 {
-                    err / (1.0 - val.powi(2)).sqrt()
-                } else {
-                    let d = if *val > 0.0 { val - err } else { val + err };
+        let (value, error) = map_elements(self, |val, err| {
+            if val != 1.0 && val != -1.0 {
+                (val.acos(), err / (1.0 - val.powi(2)).sqrt())
+            } else {
+                let d = if val > 0.0 { val - err } else { val + err };
 
-                    (d.acos() - val.acos()).abs()
-                }
-            })
-            .collect();
+                (val.acos(), (d.acos() - val.acos()).abs())
+            }
+        });
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("acos", &[&self.provenance], &[]),
         }
     }
     /// Computes the arctangent of a measure in radians.
     pub fn atan(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.atan()).collect();
-        let error: Vec<f64> = self
-            .iter()
-            .map(|(val, err)| err / (1.0 + val.powi(2)))
-            .collect();
+        let (value, error) = map_elements(self, |val, err| (val.atan(), err / (1.0 + val.powi(2))));
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("atan", &[&self.provenance], &[]),
         }
     }
     /// Computes the four quadrant arctangent of two measures.
@@ -366,8 +1781,7 @@ impl Measure {
             .iter()
             .zip(other.iter())
             .map(|((sval, serr), (oval, oerr))| {
-                ((sval.powi(2) * oerr.powi(2)).powi(2) + (oval.powi(2) * serr.powi(2)).powi(2))
-                    .sqrt()
+                ((oval * serr).powi(2) + (sval * oerr).powi(2)).sqrt()
                     / (sval.powi(2) + oval.powi(2))
             })
             .collect();
@@ -376,86 +1790,1655 @@ impl Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("atan2", &[&self.provenance, &other.provenance], &[]),
         }
     }
     /// Returns the natural logarithm of a measure.
     pub fn ln(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.ln()).collect();
-        let error: Vec<f64> = self
-            .iter()
-            .map(|(val, err)| (1.0 / val).abs() * err)
-            .collect();
+        let (value, error) = map_elements(self, |val, err| (val.ln(), (1.0 / val).abs() * err));
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("ln", &[&self.provenance], &[]),
         }
     }
+    /// Returns the logarithm of a measure in any base.
+    pub fn log(&self, base: f64) -> Measure {
+        let (value, error) = map_elements(self, |val, err| {
+            (log(val, base), (1.0 / (val * base.ln())).abs() * err)
+        });
+
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("log", &[&self.provenance], &[("base", base)]),
+        }
+    }
+    /// Returns the base 10 logarithm of a measure.
+    pub fn log10(&self) -> Measure {
+        self.log(10.0)
+    }
+    /// Returns the base 2 logarithm of a measure.
+    pub fn log2(&self) -> Measure {
+        self.log(2.0)
+    }
     /// Returns the exponential function of a measure.
     pub fn exp(&self) -> Measure {
-        let value: Vec<f64> = self.value.iter().map(|val| val.exp()).collect();
-        let error: Vec<f64> = self.iter().map(|(val, err)| val.abs() * err).collect();
+        let (value, error) = map_elements(self, |val, err| (val.exp(), val.exp() * err));
 
         Measure {
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("exp", &[&self.provenance], &[]),
         }
     }
     /// Returns the diference between a value and the next one in a measure.
     pub fn delta(&self) -> Measure {
-        self.iter()
+        let mut delta = self
+            .iter()
             .zip(self.iter().skip(1))
             .map(|((val, err), (next_val, next_err))| {
                 (next_val - val, (err.powi(2) + next_err.powi(2)).sqrt())
             })
-            .collect()
+            .collect::<Measure>()
+            .with_unit(self.unit.clone());
+        delta.provenance = Provenance::derive("delta", &[&self.provenance], &[]);
+        delta
+    }
+    /// Integrates a measure over another one with the trapezoidal rule,
+    /// returning a measure of length 1. The errors of both measures are
+    /// propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let position = measure!([0.0, 0.1, 0.2, 0.3], 0.001);
+    /// let force = measure!([2.0, 2.4, 2.2, 1.8], 0.1);
+    ///
+    /// let work = force.integrate(&position);
+    /// ```
+    pub fn integrate(&self, x: &Measure) -> Measure {
+        self.integral(x, trapezoid_weights(&x.value), "integrate")
+    }
+    /// Integrates a measure over another one with the Simpson's rule, which is
+    /// exact for parabolas, returning a measure of length 1. The x values may be
+    /// unevenly spaced, and with an odd number of intervals the last one uses
+    /// the parabola through the last three points. The errors of the x values
+    /// are propagated as in [Measure::integrate].
+    pub fn integrate_simpson(&self, x: &Measure) -> Measure {
+        self.integral(x, simpson_weights(&x.value), "integrate_simpson")
+    }
+    /// Calculates the dot product of two vectors given by their components,
+    /// `Σ aᵢbᵢ`, as a measure of one value. Panics if the lengths are diferent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Unit};
+    /// let a = measure!([3.0, 4.0, 0.0], 0.1; false).with_unit(Unit::meter());
+    /// let b = measure!([2.0, 0.0, 1.0], 0.0; false).with_unit(Unit::meter());
+    ///
+    /// let projection = a.dot(&b);
+    /// assert_eq!(projection.value(), &vec![6.0]);
+    /// assert_eq!(projection.unit(), &(&Unit::meter() * &Unit::meter()));
+    /// assert!((a.norm().value()[0] - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn dot(&self, other: &Measure) -> Measure {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        let value = self
+            .value
+            .iter()
+            .zip(&other.value)
+            .map(|(a, b)| a * b)
+            .sum();
+        let error = self
+            .iter()
+            .zip(other.iter())
+            .map(|((a, aerr), (b, berr))| (b * aerr).powi(2) + (a * berr).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        Measure {
+            value: vec![value],
+            error: vec![error],
+            style: Style::PM,
+            unit: &self.unit * &other.unit,
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("dot", &[&self.provenance, &other.provenance], &[]),
+        }
+    }
+    /// Calculates the euclidean norm of a vector given by its components,
+    /// `sqrt(Σ aᵢ²)`, as a measure of one value. The error of the zero vector
+    /// is NaN, as the norm has no derivative there.
+    pub fn norm(&self) -> Measure {
+        let value = self.value.iter().map(|a| a.powi(2)).sum::<f64>().sqrt();
+        let error = self
+            .iter()
+            .map(|(a, err)| (a * err).powi(2))
+            .sum::<f64>()
+            .sqrt()
+            / value;
+        Measure {
+            value: vec![value],
+            error: vec![error],
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("norm", &[&self.provenance], &[]),
+        }
+    }
+    /// Calculates the cross product of two vectors of three components.
+    /// Panics if some of them doesn't have three components.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let x = measure!([1.0, 0.0, 0.0], 0.1; false);
+    /// let y = measure!([0.0, 2.0, 0.0], 0.1; false);
+    ///
+    /// assert_eq!(x.cross(&y).value(), &vec![0.0, 0.0, 2.0]);
+    /// ```
+    pub fn cross(&self, other: &Measure) -> Measure {
+        assert!(
+            self.len() == 3 && other.len() == 3,
+            "The cross product requires vectors of three components, obtained {} and {}.",
+            self.len(),
+            other.len()
+        );
+        let (a, b) = (&self.value, &other.value);
+        let (aerr, berr) = (&self.error, &other.error);
+        let (value, error) = (0..3)
+            .map(|i| {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let error = ((b[k] * aerr[j]).powi(2)
+                    + (a[j] * berr[k]).powi(2)
+                    + (b[j] * aerr[k]).powi(2)
+                    + (a[k] * berr[j]).powi(2))
+                .sqrt();
+                (a[j] * b[k] - a[k] * b[j], error)
+            })
+            .unzip();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: &self.unit * &other.unit,
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("cross", &[&self.provenance, &other.provenance], &[]),
+        }
+    }
+
+    // Integral as the sum of the values multiplied by the weights.
+    fn integral(&self, x: &Measure, weights: Vec<f64>, operation: &str) -> Measure {
+        assert_eq!(
+            self.len(),
+            x.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            self.len(),
+            x.len()
+        );
+        let n = self.len();
+        let value = weights
+            .iter()
+            .zip(self.value.iter())
+            .map(|(w, y)| w * y)
+            .sum();
+        // Sensitivity to each x value of the trapezoidal rule.
+        let x_error = (0..n).map(|i| {
+            let previous = if i > 0 {
+                self.value[i - 1] + self.value[i]
+            } else {
+                0.0
+            };
+            let next = if i + 1 < n {
+                self.value[i] + self.value[i + 1]
+            } else {
+                0.0
+            };
+            (previous - next) / 2.0 * x.error[i]
+        });
+        let error = weights
+            .iter()
+            .zip(self.error.iter())
+            .map(|(w, err)| w * err)
+            .chain(x_error)
+            .map(|err| err.powi(2))
+            .sum::<f64>()
+            .sqrt();
+        Measure {
+            value: vec![value],
+            error: vec![error],
+            style: Style::PM,
+            unit: &self.unit * &x.unit,
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(operation, &[&self.provenance, &x.provenance], &[]),
+        }
+    }
+    /// Interpolates linearly a measure sampled at the x values at new x values,
+    /// so measures taken at diferent points can be operated element-wise. The
+    /// error at a new point is the same combination of the surrounding errors.
+    /// Panics if the x values are not increasing or a new one is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let time = measure!([0, 1, 2], 0.01);
+    /// let position = measure!([0, 2, 4], [0.1, 0.1, 0.3]);
+    ///
+    /// let middle = position.interp(&time, &measure!([0.5, 1.5]));
+    /// assert_eq!(middle.value(), &[1.0, 3.0]);
+    /// ```
+    pub fn interp(&self, x: &Measure, new_x: &Measure) -> Measure {
+        Dataset::new(x.clone(), self.clone())
+            .resample_to(new_x, ResampleMethod::Linear)
+            .y()
+            .clone()
+    }
+    /// Interpolates a measure sampled at the x values at new x values with a
+    /// natural cubic spline. The errors are propagated as in [Measure::interp].
+    pub fn interp_spline(&self, x: &Measure, new_x: &Measure) -> Measure {
+        Dataset::new(x.clone(), self.clone())
+            .resample_to(new_x, ResampleMethod::Spline)
+            .y()
+            .clone()
+    }
+    /// Applies a function element-wise propagating the errors to first order
+    /// with the derivative given, `σ_f = |f'(x)|σ`, for the functions that
+    /// are not provided nor can be written with [Dual] numbers, like the ones
+    /// of other crates. The result has no unit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let x = measure!([0.5, 2.0], 0.1; false);
+    ///
+    /// // The logistic function, whose derivative is f(1 - f).
+    /// let logistic = |x: f64| 1.0 / (1.0 + (-x).exp());
+    /// let y = x.map_with(logistic, |x| logistic(x) * (1.0 - logistic(x)));
+    /// assert_eq!(y.value()[1], logistic(2.0));
+    /// assert_eq!(y.error()[1], 0.1 * logistic(2.0) * (1.0 - logistic(2.0)));
+    /// ```
+    pub fn map_with<F, D>(&self, function: F, derivative: D) -> Measure
+    where
+        F: Fn(f64) -> f64,
+        D: Fn(f64) -> f64,
+    {
+        let (value, error) = self
+            .iter()
+            .map(|(val, err)| (function(*val), (derivative(*val) * err).abs()))
+            .unzip();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("map_with", &[&self.provenance], &[]),
+        }
+    }
+    /// Applies a function element-wise propagating the errors to first order
+    /// with its exact derivative, obtained by evaluating it with [Dual]
+    /// numbers, so any expression can be used without implementing its
+    /// derivative. The result has no unit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let angle = measure!([0.5, 1.0], 0.01; false);
+    ///
+    /// let y = angle.propagate(|x| x.sin() * x.exp() + 1.0);
+    /// assert_eq!(y.value()[0], 0.5_f64.sin() * 0.5_f64.exp() + 1.0);
+    /// ```
+    pub fn propagate<F: Fn(Dual) -> Dual>(&self, function: F) -> Measure {
+        let (value, error) = self
+            .iter()
+            .map(|(val, err)| {
+                let result = function(Dual::variable(*val));
+                (result.value, (result.derivative * err).abs())
+            })
+            .unzip();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("propagate", &[&self.provenance], &[]),
+        }
+    }
+    /// Applies element-wise a function of several independent measures,
+    /// propagating the errors to first order with its exact partial
+    /// derivatives as in [Measure::propagate]. The function receives a value of
+    /// every measure, in the same order, and the measures of length 1 are used
+    /// for every element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let mass = measure!([1.0, 2.0], 0.01);
+    /// let speed = measure!([3.0, 1.5], 0.05);
+    ///
+    /// let energy = Measure::propagate_multi(&[&mass, &speed], |x| x[0] * x[1].powi(2) / 2.0);
+    /// assert_eq!(energy.value(), &vec![4.5, 2.25]);
+    /// ```
+    pub fn propagate_multi<F: Fn(&[Dual]) -> Dual>(inputs: &[&Measure], function: F) -> Measure {
+        let lengths: Vec<usize> = inputs.iter().map(|input| input.len()).collect();
+        let len = lengths.iter().copied().max().unwrap_or(0);
+        assert!(
+            lengths.iter().all(|n| *n == len || *n == 1),
+            "Measures lengths must be equals or 1, obtained {:?}.",
+            lengths
+        );
+        let (value, error) = (0..len)
+            .map(|i| {
+                let points: Vec<(f64, f64)> = inputs
+                    .iter()
+                    .map(|input| (input.value[i % input.len()], input.error[i % input.len()]))
+                    .collect();
+                let (mut value, mut variance) = (f64::NAN, 0.0);
+                // One evaluation per input, differentiating with respect to it.
+                for k in 0..points.len() {
+                    let duals: Vec<Dual> = points
+                        .iter()
+                        .enumerate()
+                        .map(|(j, (val, _))| Dual::new(*val, if j == k { 1.0 } else { 0.0 }))
+                        .collect();
+                    let result = function(&duals);
+                    value = result.value;
+                    variance += (result.derivative * points[k].1).powi(2);
+                }
+                (value, variance.sqrt())
+            })
+            .unzip();
+        let provenances: Vec<&Option<Arc<Provenance>>> =
+            inputs.iter().map(|input| &input.provenance).collect();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("propagate", &provenances, &[]),
+        }
+    }
+    /// Propagates the errors through any function of the values of a measure
+    /// with the Monte Carlo method, useful when its derivatives are unknown.
+    /// The function is evaluated `n_samples` times with every value sampled
+    /// from a normal distribution whose standard deviation is its error, and
+    /// the result is the mean and standard deviation of the evaluations, in a
+    /// measure of length 1 without unit. The samples are the same on every
+    /// call, so the results are reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let sides = measure!([3.0, 4.0], [0.1, 0.1]);
+    ///
+    /// let hypotenuse = sides.propagate_mc(10000, |sides| sides[0].hypot(sides[1]));
+    /// assert!((hypotenuse.value()[0] - 5.0).abs() < 0.01);
+    /// assert!((hypotenuse.error()[0] - 0.1).abs() < 0.01);
+    /// ```
+    pub fn propagate_mc<F: Fn(&[f64]) -> f64>(&self, n_samples: usize, function: F) -> Measure {
+        let points: Vec<(f64, f64)> = self.iter().map(|(val, err)| (*val, *err)).collect();
+        let (value, error) = monte_carlo(&points, n_samples, &function);
+        Measure {
+            value: vec![value],
+            error: vec![error],
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(
+                "propagate_mc",
+                &[&self.provenance],
+                &[("samples", n_samples as f64)],
+            ),
+        }
+    }
+    /// Propagates the errors element-wise through a function of several
+    /// measures with the Monte Carlo method, as in [Measure::propagate_mc]. The
+    /// function receives a value of every measure, in the same order, and the
+    /// measures of length 1 are used for every element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let mass = measure!([1.0, 2.0], 0.01);
+    /// let speed = measure!([3.0, 1.5], 0.05);
+    ///
+    /// let energy = Measure::propagate_mc_multi(&[&mass, &speed], 10000, |x| x[0] * x[1].powi(2) / 2.0);
+    /// assert_eq!(energy.len(), 2);
+    /// ```
+    pub fn propagate_mc_multi<F: Fn(&[f64]) -> f64>(
+        inputs: &[&Measure],
+        n_samples: usize,
+        function: F,
+    ) -> Measure {
+        let lengths: Vec<usize> = inputs.iter().map(|input| input.len()).collect();
+        let len = lengths.iter().copied().max().unwrap_or(0);
+        assert!(
+            lengths.iter().all(|n| *n == len || *n == 1),
+            "Measures lengths must be equals or 1, obtained {:?}.",
+            lengths
+        );
+        let (value, error) = (0..len)
+            .map(|i| {
+                let points: Vec<(f64, f64)> = inputs
+                    .iter()
+                    .map(|input| (input.value[i % input.len()], input.error[i % input.len()]))
+                    .collect();
+                monte_carlo(&points, n_samples, &function)
+            })
+            .unzip();
+        let provenances: Vec<&Option<Arc<Provenance>>> =
+            inputs.iter().map(|input| &input.provenance).collect();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive(
+                "propagate_mc",
+                &provenances,
+                &[("samples", n_samples as f64)],
+            ),
+        }
+    }
+    /// Adds every value of a measure, returning a measure of length 1 whose
+    /// error is the quadrature sum of the errors.
+    pub fn sum(&self) -> Measure {
+        Measure {
+            value: vec![self.value.iter().sum()],
+            error: vec![self.error.iter().map(|err| err.powi(2)).sum::<f64>().sqrt()],
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("sum", &[&self.provenance], &[]),
+        }
+    }
+    /// Returns the running totals of a measure, whose errors are the quadrature
+    /// sum of the errors added so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let charge = measure!([1.0, 2.0, 2.0], [0.3, 0.4, 0.0]; false);
+    ///
+    /// assert_eq!(charge.cumsum(), measure!([1.0, 3.0, 5.0], [0.3, 0.5, 0.5]; false));
+    /// ```
+    pub fn cumsum(&self) -> Measure {
+        let mut total = (0.0, 0.0);
+        let (value, error) = self
+            .iter()
+            .map(|(val, err)| {
+                total = (total.0 + val, total.1 + err.powi(2));
+                (total.0, total.1.sqrt())
+            })
+            .unzip();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: self.unit.clone(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("cumsum", &[&self.provenance], &[]),
+        }
+    }
+    /// Returns the running products of a dimensionless measure. The relative
+    /// errors are added in quadrature, computed as `σₖ² = (xₖσₖ₋₁)² + (Pₖ₋₁εₖ)²`,
+    /// being `Pₖ ± σₖ` the products and `xₖ ± εₖ` the values, so it also works
+    /// with values equal to zero.
+    pub fn cumprod(&self) -> Measure {
+        assert!(
+            self.unit.is_dimensionless(),
+            "The running products of a measure with units have diferent units, obtained {}.",
+            self.unit
+        );
+        let mut total = (1.0, 0.0_f64);
+        let (value, error) = self
+            .iter()
+            .map(|(val, err)| {
+                total = (total.0 * val, (val * total.1).hypot(total.0 * err));
+                total
+            })
+            .unzip();
+        Measure {
+            value,
+            error,
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("cumprod", &[&self.provenance], &[]),
+        }
+    }
+    /// Multiplies every value of a measure, returning a measure of length 1.
+    /// The error of each value is weighted by the product of the other ones,
+    /// so it also works with values equal to zero.
+    pub fn product(&self) -> Measure {
+        // Products of the values before and after each index.
+        let mut before = vec![1.0; self.len() + 1];
+        let mut after = vec![1.0; self.len() + 1];
+        for i in 0..self.len() {
+            before[i + 1] = before[i] * self.value[i];
+            after[self.len() - i - 1] = after[self.len() - i] * self.value[self.len() - i - 1];
+        }
+        Measure {
+            value: vec![before[self.len()]],
+            error: vec![self
+                .error
+                .iter()
+                .enumerate()
+                .map(|(i, err)| (err * before[i] * after[i + 1]).powi(2))
+                .sum::<f64>()
+                .sqrt()],
+            style: Style::PM,
+            unit: self.unit.powf(self.len() as f64),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: Provenance::derive("product", &[&self.provenance], &[]),
+        }
     }
 }
 
+impl<T: Float> GenericMeasure<T> {
+    /// Length of the measure.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+    /// Checks if the measure is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the values of a measure.
+    pub fn value(&self) -> &Vec<T> {
+        &self.value
+    }
+    /// Returns the error of a measure.
+    pub fn error(&self) -> &Vec<T> {
+        &self.error
+    }
+    /// Returns the style of a measure.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+    /// Returns the unit of a measure.
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+    /// Changes the unit of a measure.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+    /// Returns the name of the quantity measured, if it was given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Names the quantity measured, e.g. "t", used to generate axis labels and
+    /// table headers.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    /// Returns the measurement protocol metadata of a measure.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+    /// Converts the values and errors to another floating point type, keeping
+    /// the unit, the name and the rest of the information of the measure.
+    pub fn cast<U: Float>(&self) -> GenericMeasure<U> {
+        let convert = |numbers: &Vec<T>| {
+            numbers
+                .iter()
+                .map(|num| U::from_f64(num.to_f64()))
+                .collect()
+        };
+        GenericMeasure {
+            value: convert(&self.value),
+            error: convert(&self.error),
+            style: self.style,
+            unit: self.unit.clone(),
+            name: self.name.clone(),
+            metadata: self.metadata.clone(),
+            provenance: self.provenance.clone(),
+        }
+    }
+}
+
+impl GenericMeasure<f32> {
+    /// Constructor of a measure stored as `f32`, see [Measure::new].
+    pub fn new(
+        value: Vec<f32>,
+        error: Vec<f32>,
+        aproximate: bool,
+    ) -> Result<GenericMeasure<f32>, MyError> {
+        let widen = |numbers: Vec<f32>| numbers.into_iter().map(f64::from).collect();
+        Ok(Measure::new(widen(value), widen(error), aproximate)?.cast())
+    }
+}
+
+impl From<GenericMeasure<f32>> for Measure {
+    fn from(measure: GenericMeasure<f32>) -> Measure {
+        measure.cast()
+    }
+}
+
+impl From<Measure> for GenericMeasure<f32> {
+    fn from(measure: Measure) -> GenericMeasure<f32> {
+        measure.cast()
+    }
+}
+
+impl Display for GenericMeasure<f32> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.cast::<f64>(), f)
+    }
+}
+
+// Operations between measures stored as `f32` and with numbers, computed as a
+// [Measure] and stored back as `f32`.
+macro_rules! impl_f32_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait<&GenericMeasure<f32>> for &GenericMeasure<f32> {
+            type Output = GenericMeasure<f32>;
+
+            fn $method(self, other: &GenericMeasure<f32>) -> GenericMeasure<f32> {
+                self.cast::<f64>().$method(other.cast::<f64>()).cast()
+            }
+        }
+
+        impl $trait<GenericMeasure<f32>> for GenericMeasure<f32> {
+            type Output = GenericMeasure<f32>;
+
+            fn $method(self, other: GenericMeasure<f32>) -> GenericMeasure<f32> {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<f64> for &GenericMeasure<f32> {
+            type Output = GenericMeasure<f32>;
+
+            fn $method(self, other: f64) -> GenericMeasure<f32> {
+                self.cast::<f64>().$method(other).cast()
+            }
+        }
+
+        impl $trait<f64> for GenericMeasure<f32> {
+            type Output = GenericMeasure<f32>;
+
+            fn $method(self, other: f64) -> GenericMeasure<f32> {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+
+impl_f32_op!(Add, add);
+impl_f32_op!(Sub, sub);
+impl_f32_op!(Mul, mul);
+impl_f32_op!(Div, div);
+
 impl Style {
-    /// Changes how a measure is displayed depending on its style.
+    /// Changes how a measure is displayed depending on its style. The
+    /// precision of the formatter, e.g. `{:.3}`, is applied to every number,
+    /// and the width and alignment to the whole text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure};
+    /// let current = measure!([0.001234], 0.000056; false);
+    ///
+    /// assert_eq!(format!("{:.4}", current), "0.0012 ± 0.0001");
+    /// assert_eq!(format!("{:.2e}", current), "1.23e-3 ± 5.60e-5");
+    /// assert_eq!(format!("{:>20.4}", current), "     0.0012 ± 0.0001");
+    /// ```
     pub fn disp(&self, measure: &Measure, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = NumberFormat::new(f, Notation::Decimal);
+        pad(f, &self.format(measure, &format))
+    }
+
+    // Text of a measure with every number formatted as indicated.
+    fn format(&self, measure: &Measure, format: &NumberFormat) -> String {
+        let num = |num: f64| format.number(num);
         match *self {
-            Style::List => write!(f, "{:?} ± {:?}", measure.value, measure.error),
+            Style::List => {
+                let list = |numbers: &Vec<f64>| {
+                    if format.is_default() {
+                        format!("{:?}", numbers)
+                    } else {
+                        let numbers: Vec<String> = numbers.iter().map(|x| num(*x)).collect();
+                        format!("[{}]", numbers.join(", "))
+                    }
+                };
+                format!(
+                    "{} ± {}{}",
+                    list(&measure.value),
+                    list(&measure.error),
+                    unit_suffix(measure, " ", Unit::symbol)
+                )
+            }
 
             Style::PM => {
-                for i in 0..measure.len() - 1 {
-                    write!(f, "{} ± {}, ", measure.value[i], measure.error[i])?;
-                }
-                write!(
-                    f,
-                    "{} ± {}",
-                    measure.value[measure.len() - 1],
-                    measure.error[measure.len() - 1]
-                )
+                let unit = unit_suffix(measure, " ", Unit::symbol);
+                measure
+                    .iter()
+                    .map(|(val, err)| format!("{} ± {}{}", num(*val), num(*err), unit))
+                    .collect::<Vec<String>>()
+                    .join(", ")
             }
 
             Style::Table => {
                 if measure.len() == 1 {
-                    write!(f, "{} ± {}", measure.value[0], measure.error[0])
+                    format!(
+                        "{} ± {}{}",
+                        num(measure.value[0]),
+                        num(measure.error[0]),
+                        unit_suffix(measure, " ", Unit::symbol)
+                    )
                 } else {
-                    write!(f, "This style is only for one value and its error.")
+                    Message::SingleValueStyle.text().to_string()
                 }
             }
 
             Style::LatexTable => {
                 if measure.len() == 1 {
-                    write!(f, "${} \\pm {}$", measure.value[0], measure.error[0])
+                    format!(
+                        "${} \\pm {}{}$",
+                        num(measure.value[0]),
+                        num(measure.error[0]),
+                        unit_suffix(measure, "\\ ", Unit::latex)
+                    )
                 } else {
-                    write!(f, "This style is only for one value and its error.")
+                    Message::SingleValueStyle.text().to_string()
                 }
             }
 
             Style::TypstTable => {
                 if measure.len() == 1 {
-                    write!(f, "${} plus.minus {}$", measure.value[0], measure.error[0])
+                    format!(
+                        "${} plus.minus {}{}$",
+                        num(measure.value[0]),
+                        num(measure.error[0]),
+                        unit_suffix(measure, " ", Unit::typst)
+                    )
+                } else {
+                    Message::SingleValueStyle.text().to_string()
+                }
+            }
+
+            Style::SI => measure
+                .iter()
+                .map(|(val, err)| si_format(*val, *err, &measure.unit, format))
+                .collect::<Vec<String>>()
+                .join(", "),
+
+            Style::Aligned => aligned_format(measure, format.precision).join("\n"),
+
+            Style::Scientific => {
+                let unit = unit_suffix(measure, " ", Unit::symbol);
+                measure
+                    .iter()
+                    .map(|(val, err)| {
+                        format!("{}{}", scientific_format(*val, *err, *self, format), unit)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }
+
+            Style::LatexScientific => {
+                if measure.len() == 1 {
+                    format!(
+                        "${}{}$",
+                        scientific_format(measure.value[0], measure.error[0], *self, format),
+                        unit_suffix(measure, "\\ ", Unit::latex)
+                    )
+                } else {
+                    Message::SingleValueStyle.text().to_string()
+                }
+            }
+
+            Style::TypstScientific => {
+                if measure.len() == 1 {
+                    format!(
+                        "${}{}$",
+                        scientific_format(measure.value[0], measure.error[0], *self, format),
+                        unit_suffix(measure, " ", Unit::typst)
+                    )
                 } else {
-                    write!(f, "This style is only for one value and its error.")
+                    Message::SingleValueStyle.text().to_string()
                 }
             }
+
+            Style::Compact => {
+                let unit = unit_suffix(measure, " ", Unit::symbol);
+                measure
+                    .iter()
+                    .map(|(val, err)| format!("{}{}", compact_format(*val, *err, format), unit))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }
+
+            Style::LatexCompact => {
+                if measure.len() == 1 {
+                    format!(
+                        "${}{}$",
+                        compact_format(measure.value[0], measure.error[0], format),
+                        unit_suffix(measure, "\\ ", Unit::latex)
+                    )
+                } else {
+                    Message::SingleValueStyle.text().to_string()
+                }
+            }
+
+            Style::TypstCompact => {
+                if measure.len() == 1 {
+                    format!(
+                        "${}{}$",
+                        compact_format(measure.value[0], measure.error[0], format),
+                        unit_suffix(measure, " ", Unit::typst)
+                    )
+                } else {
+                    Message::SingleValueStyle.text().to_string()
+                }
+            }
+        }
+    }
+}
+
+/// How the numbers of a measure are written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Notation {
+    /// Decimal notation, e.g. 0.0012.
+    Decimal,
+    /// Scientific notation, e.g. 1.2e-3.
+    LowerExp,
+    /// Scientific notation, e.g. 1.2E-3.
+    UpperExp,
+}
+
+/// Format of every number of a measure, taken from the flags of a formatter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct NumberFormat {
+    precision: Option<usize>,
+    notation: Notation,
+}
+
+impl NumberFormat {
+    fn new(f: &std::fmt::Formatter<'_>, notation: Notation) -> Self {
+        NumberFormat {
+            precision: f.precision(),
+            notation,
+        }
+    }
+    /// Checks if the numbers are written as by default.
+    fn is_default(&self) -> bool {
+        self.precision.is_none() && self.notation == Notation::Decimal
+    }
+    fn number(&self, num: f64) -> String {
+        match (self.notation, self.precision) {
+            (Notation::Decimal, None) => num.to_string(),
+            (Notation::Decimal, Some(precision)) => format!("{:.*}", precision, num),
+            (Notation::LowerExp, None) => format!("{:e}", num),
+            (Notation::LowerExp, Some(precision)) => format!("{:.*e}", precision, num),
+            (Notation::UpperExp, None) => format!("{:E}", num),
+            (Notation::UpperExp, Some(precision)) => format!("{:.*E}", precision, num),
+        }
+    }
+}
+
+/// Writes a text with the width, fill and alignment of the formatter, left
+/// aligned by default. Unlike [std::fmt::Formatter::pad], the precision doesn't
+/// truncate the text, as it was applied to the numbers.
+fn pad(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    let length = text.chars().count();
+    let padding = f.width().map_or(0, |width| width.saturating_sub(length));
+    let (before, after) = match f.align() {
+        Some(std::fmt::Alignment::Right) => (padding, 0),
+        Some(std::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill().to_string();
+    write!(f, "{}{}{}", fill.repeat(before), text, fill.repeat(after))
+}
+
+/// Formats every value and error of a measure with the exponent of the largest
+/// value, the decimals needed by the smallest error, unless a precision is
+/// given, and the same width, so they can be printed as aligned columns.
+fn aligned_format(measure: &Measure, precision: Option<usize>) -> Vec<String> {
+    let largest = |numbers: &Vec<f64>| {
+        numbers
+            .iter()
+            .filter(|num| num.is_finite())
+            .fold(0.0_f64, |acc, num| acc.max(num.abs()))
+    };
+    let magnitude = |num: f64| num.abs().log10().floor() as i32;
+    let exponent = match largest(&measure.value) {
+        max if max > 0.0 => magnitude(max),
+        _ => 0,
+    };
+    let scale = 10.0_f64.powi(exponent);
+    // Decimals needed to show the first significative figure of the smallest
+    // error, or of the smallest value if there are no errors.
+    let smallest = |numbers: &Vec<f64>| {
+        numbers
+            .iter()
+            .filter(|num| **num != 0.0 && num.is_finite())
+            .fold(f64::INFINITY, |acc, num| acc.min(num.abs()))
+    };
+    let smallest = match smallest(&measure.error) {
+        min if min.is_finite() => min,
+        _ => smallest(&measure.value),
+    };
+    let decimals = match precision {
+        Some(precision) => precision,
+        None if smallest.is_finite() => (-magnitude(smallest / scale)).clamp(0, 15) as usize,
+        None => 0,
+    };
+
+    let values: Vec<String> = measure
+        .value
+        .iter()
+        .map(|val| format!("{:.*}", decimals, val / scale))
+        .collect();
+    let errors: Vec<String> = measure
+        .error
+        .iter()
+        .map(|err| format!("{:.*}", decimals, err / scale))
+        .collect();
+    let width = |column: &Vec<String>| column.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    let (value_width, error_width) = (width(&values), width(&errors));
+    let unit = unit_suffix(measure, " ", Unit::symbol);
+
+    values
+        .iter()
+        .zip(errors.iter())
+        .map(|(val, err)| {
+            if exponent == 0 {
+                format!(
+                    "{:>vw$} ± {:>ew$}{}",
+                    val,
+                    err,
+                    unit,
+                    vw = value_width,
+                    ew = error_width
+                )
+            } else {
+                format!(
+                    "({:>vw$} ± {:>ew$})e{}{}",
+                    val,
+                    err,
+                    exponent,
+                    unit,
+                    vw = value_width,
+                    ew = error_width
+                )
+            }
+        })
+        .collect()
+}
+
+/// Digits used to write the powers of ten as superscripts, e.g. 10⁻³.
+pub(crate) const SUPERSCRIPT_DIGITS: [char; 10] =
+    ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Writes an integer as a superscript.
+fn superscript(num: i32) -> String {
+    num.to_string()
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(digit) => SUPERSCRIPT_DIGITS[digit as usize],
+            None => '⁻',
+        })
+        .collect()
+}
+
+/// Formats a value and its error with the power of ten of the value factored
+/// out, or of the error if the value is zero, in the notation of a scientific
+/// style. Nothing is factored out for the power 0.
+fn scientific_format(value: f64, error: f64, style: Style, format: &NumberFormat) -> String {
+    let reference = if value != 0.0 && value.is_finite() {
+        value
+    } else {
+        error
+    };
+    let exponent = if reference != 0.0 && reference.is_finite() {
+        reference.abs().log10().floor() as i32
+    } else {
+        0
+    };
+    let (value, error) = (
+        format.number(rescale(value, exponent)),
+        format.number(rescale(error, exponent)),
+    );
+    match (style, exponent) {
+        (Style::LatexScientific, 0) => format!("{} \\pm {}", value, error),
+        (Style::LatexScientific, _) => {
+            format!("({} \\pm {}) \\times 10^{{{}}}", value, error, exponent)
+        }
+        (Style::TypstScientific, 0) => format!("{} plus.minus {}", value, error),
+        (Style::TypstScientific, _) => {
+            format!("({} plus.minus {}) times 10^({})", value, error, exponent)
+        }
+        (_, 0) => format!("{} ± {}", value, error),
+        (_, _) => format!("({} ± {})×10{}", value, error, superscript(exponent)),
+    }
+}
+
+/// Formats a value followed by its error in parentheses, in units of the last
+/// digit of the value, e.g. 1.234(5) for 1.234 ± 0.005. The digits are those of
+/// the error, or the ones given by the precision, and with an exponential
+/// notation the power of ten of the value goes after the error, e.g.
+/// 1.234(5)e-3.
+fn compact_format(value: f64, error: f64, format: &NumberFormat) -> String {
+    let exponent = match format.notation {
+        Notation::Decimal => 0,
+        _ if value != 0.0 && value.is_finite() => value.abs().log10().floor() as i32,
+        _ if error != 0.0 && error.is_finite() => error.abs().log10().floor() as i32,
+        _ => 0,
+    };
+    let (value, error) = (rescale(value, exponent), rescale(error, exponent));
+    let compact = if !value.is_finite() || !error.is_finite() {
+        format!("{}({})", value, error)
+    } else {
+        // Decimals of the error as printed by default, or of the value if
+        // there is no error.
+        let decimals = |num: f64| {
+            num.to_string()
+                .split_once('.')
+                .map_or(0, |(_, decimals)| decimals.len())
+        };
+        let decimals = match format.precision {
+            Some(precision) => precision,
+            None if error != 0.0 => decimals(error),
+            None => decimals(value),
+        };
+        format!(
+            "{:.*}({:.0})",
+            decimals,
+            value,
+            (error.abs() * 10.0_f64.powi(decimals as i32)).round()
+        )
+    };
+    match format.notation {
+        Notation::Decimal => compact,
+        Notation::LowerExp => format!("{}e{}", compact, exponent),
+        Notation::UpperExp => format!("{}E{}", compact, exponent),
+    }
+}
+
+/// Pearson correlation coefficient of two lists of numbers of the same length,
+/// with its standard error.
+fn correlation(x: &[f64], y: &[f64]) -> ScalarMeasure {
+    let n = x.len() as f64;
+    let (x_mean, y_mean) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (xi, yi) in x.iter().zip(y) {
+        let (dx, dy) = (xi - x_mean, yi - y_mean);
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+    let r = (sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0);
+    ScalarMeasure {
+        value: r,
+        error: ((1.0 - r.powi(2)) / (n - 2.0)).sqrt(),
+    }
+}
+
+/// Ranks of a list of numbers starting at 1, giving the mean rank to the tied
+/// numbers.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for index in &order[start..end] {
+            ranks[*index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Formats a value and its error using the SI prefix that fits the magnitude of
+/// the value, or of the error if the value is zero.
+fn si_format(value: f64, error: f64, unit: &Unit, format: &NumberFormat) -> String {
+    let mut symbol = unit.symbol();
+    let (mut value, mut error) = (value, error);
+    // The prefixes of the mass are applied to the gram.
+    if symbol == "kg" {
+        symbol = String::from("g");
+        value *= 1e3;
+        error *= 1e3;
+    }
+    let (exponent, prefix) = si_prefix(if value != 0.0 { value } else { error });
+    let scale = |num: f64| format.number(rescale(num, exponent));
+
+    if unit.is_dimensionless() || !accepts_prefix(&symbol) {
+        // A prefix of a compound unit would only scale its first factor, so
+        // they are written with a power of ten instead.
+        let suffix = if unit.is_dimensionless() {
+            String::new()
+        } else {
+            format!(" {}", symbol)
+        };
+        if exponent == 0 {
+            format!("{} ± {}{}", scale(value), scale(error), suffix)
+        } else {
+            format!(
+                "({} ± {})e{}{}",
+                scale(value),
+                scale(error),
+                exponent,
+                suffix
+            )
+        }
+    } else {
+        format!(
+            "{} {}{} ± {} {}{}",
+            scale(value),
+            prefix,
+            symbol,
+            scale(error),
+            prefix,
+            symbol
+        )
+    }
+}
+
+/// Divides a number by a power of ten, removing the floating point noise
+/// introduced by the scaling.
+fn rescale(num: f64, exponent: i32) -> f64 {
+    let num = num / 10.0_f64.powi(exponent);
+    if num == 0.0 || !num.is_finite() {
+        return num;
+    }
+    round(num, 11 - num.abs().log10().floor() as i32)
+}
+
+/// Rescales a measure with the SI prefix that best fits its largest value,
+/// returning it along with the new unit symbol, so axes and headers read
+/// "I / mA" instead of showing values like 0.0012. Units with several factors
+/// and dimensionless measures are scaled with a power of ten instead, e.g.
+/// "10^-3 m·s^-1".
+///
+/// The unit of the measure returned has the prefix in its symbol but the same
+/// dimensions, so it is meant to be shown and not operated: plot it and label
+/// the axis with [Labels::xlabel_from](crate::Labels::xlabel_from), or use
+/// [Table::autoscale](crate::Table::autoscale).
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{autoscale_prefix, measure, Measure, Table, Unit};
+/// let current = measure!([0.0012, 0.0034], 0.0001).with_name("I").with_unit(Unit::ampere());
+/// let (scaled, suffix) = autoscale_prefix(&current);
+///
+/// assert_eq!(suffix, "mA");
+/// assert_eq!(scaled.value(), &vec![1.2, 3.4]);
+/// assert_eq!(scaled.label(), "I / mA");
+///
+/// let table = Table::new(vec![current], vec![]).header_from_measures().autoscale(true);
+/// assert!(table.typst().contains("[$I$ / $upright(\"mA\")$]"));
+/// ```
+pub fn autoscale_prefix(measure: &Measure) -> (Measure, String) {
+    let mut symbol = measure.unit.symbol();
+    let mut factor = 1.0;
+    // The prefixes of the mass are applied to the gram.
+    if symbol == "kg" {
+        symbol = String::from("g");
+        factor = 1e3;
+    }
+    let largest = measure
+        .value
+        .iter()
+        .filter(|val| val.is_finite())
+        .fold(0.0_f64, |acc, val| acc.max(val.abs()));
+    let (exponent, prefix) = si_prefix(largest * factor);
+    if exponent == 0 && factor == 1.0 {
+        return (measure.clone(), symbol);
+    }
+
+    let suffix = if measure.unit.is_dimensionless() {
+        format!("10^{}", exponent)
+    } else if !accepts_prefix(&symbol) {
+        format!("10^{} {}", exponent, symbol)
+    } else {
+        format!("{}{}", prefix, symbol)
+    };
+    let mut scaled = measure.clone();
+    for (val, err) in &mut scaled {
+        *val = rescale(*val * factor, exponent);
+        *err = rescale(*err * factor, exponent);
+    }
+    scaled.unit = Unit::new(suffix.clone(), *measure.unit.dimension());
+    (scaled, suffix)
+}
+
+/// Minimum length of the measures whose element-wise operations are computed in
+/// parallel with the feature `rayon`, as shorter ones are faster sequentially.
+#[cfg(feature = "rayon")]
+const PARALLEL_LENGTH: usize = 1 << 14;
+
+/// Computes the value and error of every element of a measure, in parallel for
+/// long measures with the feature `rayon`.
+pub(crate) fn map_elements<F>(measure: &Measure, function: F) -> (Vec<f64>, Vec<f64>)
+where
+    F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+{
+    count(Counter::Allocations, 2);
+    count(Counter::Passes, 1);
+    #[cfg(feature = "rayon")]
+    if measure.len() >= PARALLEL_LENGTH {
+        return measure
+            .par_iter()
+            .map(|(val, err)| function(*val, *err))
+            .unzip();
+    }
+    measure
+        .iter()
+        .map(|(val, err)| function(*val, *err))
+        .unzip()
+}
+
+/// Computes element-wise the value and error of an operation of two measures,
+/// repeating the one that has length 1, in parallel for long measures with the
+/// feature `rayon`.
+pub(crate) fn zip_elements<F>(
+    measure: &Measure,
+    other: &Measure,
+    function: F,
+) -> (Vec<f64>, Vec<f64>)
+where
+    F: Fn((f64, f64), (f64, f64)) -> (f64, f64) + Sync + Send,
+{
+    let len = zip_len(measure, other);
+    count(Counter::Allocations, 2);
+    count(Counter::Passes, 1);
+    let element = |i: usize| {
+        function(
+            (
+                measure.value[i % measure.len()],
+                measure.error[i % measure.len()],
+            ),
+            (other.value[i % other.len()], other.error[i % other.len()]),
+        )
+    };
+    #[cfg(feature = "rayon")]
+    if len >= PARALLEL_LENGTH {
+        return (0..len).into_par_iter().map(element).unzip();
+    }
+    (0..len).map(element).unzip()
+}
+
+/// Divides two measures whose errors are correlated, including the covariance
+/// term that the operator `/` ignores:
+/// `σ² = (σa/b)² + (a σb/b²)² - 2ρ (σa/b) (a σb/b²)`.
+/// Either of them may have length 1. Panics if the correlation is not between
+/// -1 and 1.
+///
+/// For efficiencies, where the numerator is a subset of the denominator, the
+/// correlation of the counts is `σa/σb`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, ratio, Measure};
+/// let passed = Measure::new(vec![90.0], vec![90.0_f64.sqrt()], false).unwrap();
+/// let total = measure!([100.0], 10.0; false);
+/// let correlation = passed.error()[0] / total.error()[0];
+///
+/// let efficiency = ratio(&passed, &total, correlation);
+/// assert!((efficiency.error()[0] - (0.9_f64 * 0.1 / 100.0).sqrt()).abs() < 1e-12);
+/// assert!(efficiency.error()[0] < (&passed / &total).error()[0]);
+/// ```
+pub fn ratio(numerator: &Measure, denominator: &Measure, correlation: f64) -> Measure {
+    assert!(
+        (-1.0..=1.0).contains(&correlation),
+        "The correlation must be between -1 and 1, obtained {}.",
+        correlation
+    );
+    let (value, error) = zip_elements(numerator, denominator, |(nval, nerr), (dval, derr)| {
+        let (nterm, dterm) = (nerr / dval, nval * derr / dval.powi(2));
+        let variance = nterm.powi(2) + dterm.powi(2) - 2.0 * correlation * nterm * dterm;
+        (nval / dval, variance.max(0.0).sqrt())
+    });
+    Measure {
+        value,
+        error,
+        style: Style::PM,
+        unit: &numerator.unit / &denominator.unit,
+        name: None,
+        metadata: Metadata::default(),
+        provenance: Provenance::derive(
+            "ratio",
+            &[&numerator.provenance, &denominator.provenance],
+            &[("correlation", correlation)],
+        ),
+    }
+}
+
+/// Length of the result of an element-wise operation of two measures, where
+/// the one that has length 1 is repeated.
+fn zip_len(measure: &Measure, other: &Measure) -> usize {
+    assert!(
+        measure.len() == other.len() || measure.len() == 1 || other.len() == 1,
+        "Measures lengths must be equals, obtained {} and {}.",
+        measure.len(),
+        other.len()
+    );
+    if measure.is_empty() || other.is_empty() {
+        0
+    } else {
+        measure.len().max(other.len())
+    }
+}
+
+/// Operand of an arithmetic operation, whose vectors are reused for the result
+/// when it is owned instead of allocating new ones.
+pub(crate) trait Operand<'a> {
+    fn operand(self) -> Cow<'a, Measure>;
+}
+
+impl<'a> Operand<'a> for Measure {
+    fn operand(self) -> Cow<'a, Measure> {
+        Cow::Owned(self)
+    }
+}
+
+impl<'a> Operand<'a> for &'a Measure {
+    fn operand(self) -> Cow<'a, Measure> {
+        Cow::Borrowed(self)
+    }
+}
+
+/// Takes the measure out of a reference, leaving an empty one that doesn't
+/// allocate, so compound assignments reuse its vectors.
+pub(crate) fn take(measure: &mut Measure) -> Measure {
+    std::mem::replace(
+        measure,
+        Measure {
+            value: Vec::new(),
+            error: Vec::new(),
+            style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: None,
+        },
+    )
+}
+
+/// Replaces the value and error of every element of a measure.
+fn map_in_place<F>(measure: &mut Measure, function: &F)
+where
+    F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+{
+    count(Counter::Passes, 1);
+    let apply = |(val, err): (&mut f64, &mut f64)| (*val, *err) = function(*val, *err);
+    #[cfg(feature = "rayon")]
+    if measure.len() >= PARALLEL_LENGTH {
+        return measure.par_iter_mut().for_each(apply);
+    }
+    measure.iter_mut().for_each(apply);
+}
+
+/// Replaces element-wise the value and error of a measure with an operation of
+/// itself and another measure, which is repeated if it has length 1. The
+/// measure replaced is the first operand if `first` is true.
+fn zip_in_place<F>(measure: &mut Measure, other: &Measure, first: bool, function: &F)
+where
+    F: Fn((f64, f64), (f64, f64)) -> (f64, f64) + Sync + Send,
+{
+    count(Counter::Passes, 1);
+    let apply = |(i, (val, err)): (usize, (&mut f64, &mut f64))| {
+        let own = (*val, *err);
+        let other = (other.value[i % other.len()], other.error[i % other.len()]);
+        (*val, *err) = if first {
+            function(own, other)
+        } else {
+            function(other, own)
+        };
+    };
+    #[cfg(feature = "rayon")]
+    if measure.len() >= PARALLEL_LENGTH {
+        return measure.par_iter_mut().enumerate().for_each(apply);
+    }
+    measure.iter_mut().enumerate().for_each(apply);
+}
+
+/// Computes the value and error of every element of an operand, reusing its
+/// vectors if it is owned.
+pub(crate) fn map_operand<F>(measure: Cow<'_, Measure>, function: F) -> (Vec<f64>, Vec<f64>)
+where
+    F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+{
+    match measure {
+        Cow::Owned(mut measure) => {
+            map_in_place(&mut measure, &function);
+            (measure.value, measure.error)
+        }
+        Cow::Borrowed(measure) => map_elements(measure, function),
+    }
+}
+
+/// Computes element-wise an operation of two operands, reusing the vectors of
+/// one of them if it is owned and as long as the result.
+pub(crate) fn zip_operands<F>(
+    measure: Cow<'_, Measure>,
+    other: Cow<'_, Measure>,
+    function: F,
+) -> (Vec<f64>, Vec<f64>)
+where
+    F: Fn((f64, f64), (f64, f64)) -> (f64, f64) + Sync + Send,
+{
+    let len = zip_len(&measure, &other);
+    match (measure, other) {
+        (Cow::Owned(mut measure), other) if measure.len() == len => {
+            zip_in_place(&mut measure, &other, true, &function);
+            (measure.value, measure.error)
+        }
+        (measure, Cow::Owned(mut other)) if other.len() == len => {
+            zip_in_place(&mut other, &measure, false, &function);
+            (other.value, other.error)
         }
+        (measure, other) => zip_elements(&measure, &other, function),
+    }
+}
+
+/// Seed of the samples of the Monte Carlo methods.
+const MONTE_CARLO_SEED: u64 = 0x5eed_f3a1_1ab5;
+
+/// Mean and standard deviation of a function evaluated with samples of the
+/// values and errors given.
+fn monte_carlo<F: Fn(&[f64]) -> f64>(
+    points: &[(f64, f64)],
+    n_samples: usize,
+    function: &F,
+) -> (f64, f64) {
+    assert!(
+        n_samples >= 2,
+        "The Monte Carlo method requires at least 2 samples, obtained {}.",
+        n_samples
+    );
+    let mut rng = Rng::new(MONTE_CARLO_SEED);
+    let mut sample = vec![0.0; points.len()];
+    let results: Vec<f64> = (0..n_samples)
+        .map(|_| {
+            for (x, (val, err)) in sample.iter_mut().zip(points) {
+                *x = val + err * rng.normal();
+            }
+            function(&sample)
+        })
+        .collect();
+    let mean = results.iter().sum::<f64>() / n_samples as f64;
+    let variance = results.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n_samples - 1) as f64;
+    (mean, variance.sqrt())
+}
+
+/// Discrepancy in number of standard deviations, being 0 if there is none.
+pub(crate) fn sigmas(difference: f64, error: f64) -> f64 {
+    if difference == 0.0 {
+        0.0
+    } else {
+        difference.abs() / error
+    }
+}
+
+/// Weights of each value in the integral with the trapezoidal rule.
+fn trapezoid_weights(x: &[f64]) -> Vec<f64> {
+    let mut weights = vec![0.0; x.len()];
+    for i in 1..x.len() {
+        let h = (x[i] - x[i - 1]) / 2.0;
+        weights[i - 1] += h;
+        weights[i] += h;
+    }
+    weights
+}
+
+/// Weights of each value in the integral with the Simpson's rule for unevenly
+/// spaced points.
+fn simpson_weights(x: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    if n < 3 {
+        return trapezoid_weights(x);
+    }
+    let mut weights = vec![0.0; n];
+    // Number of points covered by pairs of intervals.
+    let end = if n % 2 == 1 { n } else { n - 1 };
+    for i in (0..end - 2).step_by(2) {
+        let (h0, h1) = (x[i + 1] - x[i], x[i + 2] - x[i + 1]);
+        let scale = (h0 + h1) / 6.0;
+        weights[i] += scale * (2.0 - h1 / h0);
+        weights[i + 1] += scale * (h0 + h1).powi(2) / (h0 * h1);
+        weights[i + 2] += scale * (2.0 - h0 / h1);
+    }
+    if end < n {
+        // Last interval, integrating the parabola through the last three points.
+        let (h0, h1) = (x[n - 2] - x[n - 3], x[n - 1] - x[n - 2]);
+        weights[n - 1] += (2.0 * h1.powi(2) + 3.0 * h0 * h1) / (6.0 * (h0 + h1));
+        weights[n - 2] += (h1.powi(2) + 3.0 * h0 * h1) / (6.0 * h0);
+        weights[n - 3] -= h1.powi(3) / (6.0 * h0 * (h0 + h1));
+    }
+    weights
+}
+
+/// Logarithm in any base, using the exact functions for bases 10 and 2.
+pub(crate) fn log(value: f64, base: f64) -> f64 {
+    if base == 10.0 {
+        value.log10()
+    } else if base == 2.0 {
+        value.log2()
+    } else {
+        value.log(base)
+    }
+}
+
+/// Formats the unit of a measure after the separator, or nothing if it is dimensionless.
+pub(crate) fn unit_suffix(
+    measure: &Measure,
+    separator: &str,
+    format: fn(&Unit) -> String,
+) -> String {
+    if measure.unit.is_dimensionless() {
+        String::new()
+    } else {
+        format!("{}{}", separator, format(&measure.unit))
+    }
+}
+
+/// Wrapper of a measure ordered by its values with [Measure::ord_by_value], to
+/// sort collections of measures or use them in a `BTreeMap`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, OrdByValue};
+/// let mut periods = vec![measure!(1.3, 0.1), measure!(1.1, 0.3), measure!(1.2, 0.2)];
+///
+/// periods.sort_by_key(|period| OrdByValue(period.clone()));
+/// assert_eq!(periods[0], measure!(1.1, 0.3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrdByValue(pub Measure);
+
+/// Diferent criteria to reject outliers of repeated measurements.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OutlierCriterion {
+    /// Rejects every value whose deviation from the mean is expected in less
+    /// than half a value of the sample, assuming a normal distribution.
+    Chauvenet,
+    /// Grubbs' test with the given significance level, rejecting the furthest
+    /// value from the mean while the test is significant.
+    Grubbs(f64),
+}
+
+impl PartialEq for OrdByValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for OrdByValue {}
+
+impl PartialOrd for OrdByValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdByValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.ord_by_value(&other.0)
     }
 }
 
@@ -471,6 +3454,24 @@ impl From<Measure> for (Vec<f64>, Vec<f64>) {
     }
 }
 
+impl From<MeasureView<'_>> for Measure {
+    fn from(m: MeasureView<'_>) -> Measure {
+        Measure {
+            value: m.value().to_vec(),
+            error: m.error().to_vec(),
+            style: m.measure.style,
+            unit: m.measure.unit.clone(),
+            name: m.measure.name.clone(),
+            metadata: m.measure.metadata.clone(),
+            provenance: Provenance::derive(
+                "range",
+                &[&m.measure.provenance],
+                &[("start", m.start as f64), ("end", m.end as f64)],
+            ),
+        }
+    }
+}
+
 impl From<&Measure> for Vec<f64> {
     fn from(m: &Measure) -> Vec<f64> {
         m.value.clone()
@@ -502,6 +3503,20 @@ impl Display for Measure {
     }
 }
 
+impl LowerExp for Measure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = NumberFormat::new(f, Notation::LowerExp);
+        pad(f, &self.style.format(self, &format))
+    }
+}
+
+impl UpperExp for Measure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = NumberFormat::new(f, Notation::UpperExp);
+        pad(f, &self.style.format(self, &format))
+    }
+}
+
 type MeasureIntoIter = std::iter::Zip<std::vec::IntoIter<f64>, std::vec::IntoIter<f64>>;
 impl IntoIterator for Measure {
     type Item = (f64, f64);
@@ -522,6 +3537,13 @@ impl<'a> IntoIterator for &'a Measure {
     }
 }
 
+#[cfg(feature = "rayon")]
+type MeasureParIter<'a> =
+    rayon::iter::Zip<rayon::slice::Iter<'a, f64>, rayon::slice::Iter<'a, f64>>;
+#[cfg(feature = "rayon")]
+type MeasureParIterMut<'a> =
+    rayon::iter::Zip<rayon::slice::IterMut<'a, f64>, rayon::slice::IterMut<'a, f64>>;
+
 type MeasureIterMut<'a> =
     std::iter::Zip<std::slice::IterMut<'a, f64>, std::slice::IterMut<'a, f64>>;
 impl<'a> IntoIterator for &'a mut Measure {
@@ -552,10 +3574,46 @@ where
             value,
             error,
             style: Style::PM,
+            unit: Unit::dimensionless(),
+            name: None,
+            metadata: Metadata::default(),
+            provenance: None,
         }
     }
 }
 
+impl Sum<Measure> for Measure {
+    /// Adds the measures element-wise, returning an empty measure if there are none.
+    fn sum<I: Iterator<Item = Measure>>(iter: I) -> Self {
+        iter.reduce(|acc, measure| acc + measure)
+            .unwrap_or_else(|| Measure::from_iter(Vec::<(f64, f64)>::new()))
+    }
+}
+
+impl<'a> Sum<&'a Measure> for Measure {
+    /// Adds the measures element-wise, returning an empty measure if there are none.
+    fn sum<I: Iterator<Item = &'a Measure>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl Product<Measure> for Measure {
+    /// Multiplies the measures element-wise, returning an empty measure if there
+    /// are none.
+    fn product<I: Iterator<Item = Measure>>(iter: I) -> Self {
+        iter.reduce(|acc, measure| acc * measure)
+            .unwrap_or_else(|| Measure::from_iter(Vec::<(f64, f64)>::new()))
+    }
+}
+
+impl<'a> Product<&'a Measure> for Measure {
+    /// Multiplies the measures element-wise, returning an empty measure if there
+    /// are none.
+    fn product<I: Iterator<Item = &'a Measure>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
 // Implementing Add, Sub, Mul, Div:
 
 // Between Measure - Measure:
@@ -593,3 +3651,10 @@ impl_op_number!(Measure, u128);
 impl_op_number!(&Measure, u128);
 impl_op_number!(Measure, i128);
 impl_op_number!(&Measure, i128);
+
+// Implementing AddAssign, SubAssign, MulAssign, DivAssign:
+impl_op_assign!(Measure);
+impl_op_assign!(&Measure);
+impl_op_assign!(ScalarMeasure);
+impl_op_assign!(&ScalarMeasure);
+impl_op_assign!(<T>);
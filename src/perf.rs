@@ -0,0 +1,79 @@
+//! Contains the performance counters of the operations of measures and fits,
+//! that only count with the feature `perf-stats`.
+#[cfg(feature = "perf-stats")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "perf-stats")]
+static COUNTERS: [AtomicUsize; 3] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+/// Events counted by the performance counters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Counter {
+    Allocations,
+    Passes,
+    FitEvaluations,
+}
+
+/// Adds some events to a counter, doing nothing without the feature
+/// `perf-stats`.
+#[inline]
+pub(crate) fn count(counter: Counter, events: usize) {
+    #[cfg(feature = "perf-stats")]
+    COUNTERS[counter as usize].fetch_add(events, Ordering::Relaxed);
+    #[cfg(not(feature = "perf-stats"))]
+    let _ = (counter, events);
+}
+
+/// Counts of the work done by the library since the start of the program or
+/// the last [reset_perf_stats], shared by all threads. Requires the feature
+/// `perf-stats`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, perf_stats, reset_perf_stats, Measure};
+/// let time = measure!([0.2, 0.3, 0.4], 0.01);
+///
+/// reset_perf_stats();
+/// let owned = time.clone() * 2.0 + 1.0;
+/// assert_eq!(perf_stats().allocations, 0);
+///
+/// let borrowed = &(&time * 2.0) + 1.0;
+/// assert_eq!(perf_stats().allocations, 4);
+/// assert_eq!(perf_stats().passes, 4);
+/// assert_eq!(owned, borrowed);
+/// ```
+#[cfg(feature = "perf-stats")]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PerfStats {
+    /// Vectors of values or errors allocated by element-wise operations.
+    pub allocations: usize,
+    /// Element-wise passes over the values of a measure.
+    pub passes: usize,
+    /// Evaluations of the objective function of a fit, each of them a pass
+    /// over the data.
+    pub fit_evaluations: usize,
+}
+
+/// Returns the current counts, see [PerfStats].
+#[cfg(feature = "perf-stats")]
+pub fn perf_stats() -> PerfStats {
+    let load = |counter: Counter| COUNTERS[counter as usize].load(Ordering::Relaxed);
+    PerfStats {
+        allocations: load(Counter::Allocations),
+        passes: load(Counter::Passes),
+        fit_evaluations: load(Counter::FitEvaluations),
+    }
+}
+
+/// Sets all the counts to zero.
+#[cfg(feature = "perf-stats")]
+pub fn reset_perf_stats() {
+    for counter in &COUNTERS {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
@@ -1,6 +1,88 @@
+pub mod testing;
+
+use crate::{Dataset, Measure, MeasureView, Style};
+use pyo3::exceptions::PyAttributeError;
 use pyo3::prelude::*;
-use pyo3::types::{IntoPyDict, PyDict};
-use std::default::Default;
+use pyo3::types::{IntoPyDict, PyDict, PyTuple};
+use std::{
+    default::Default,
+    iter::{Cloned, Copied, Map},
+    path::PathBuf,
+    sync::Once,
+    time::SystemTime,
+};
+
+// Data that can be plotted, moving it when owned and copying it only once
+// otherwise.
+pub trait IntoPlotData {
+    fn into_plot_data(self) -> Vec<f64>;
+}
+
+impl IntoPlotData for Vec<f64> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self
+    }
+}
+
+impl IntoPlotData for &Vec<f64> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.clone()
+    }
+}
+
+impl IntoPlotData for &[f64] {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.to_vec()
+    }
+}
+
+impl<const N: usize> IntoPlotData for [f64; N] {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.to_vec()
+    }
+}
+
+impl<const N: usize> IntoPlotData for &[f64; N] {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.to_vec()
+    }
+}
+
+impl IntoPlotData for Measure {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.into()
+    }
+}
+
+impl IntoPlotData for &Measure {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.value().clone()
+    }
+}
+
+impl IntoPlotData for MeasureView<'_> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.value().to_vec()
+    }
+}
+
+impl<I: Iterator, F: FnMut(I::Item) -> f64> IntoPlotData for Map<I, F> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.collect()
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a f64>> IntoPlotData for Copied<I> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.collect()
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a f64>> IntoPlotData for Cloned<I> {
+    fn into_plot_data(self) -> Vec<f64> {
+        self.collect()
+    }
+}
 
 // Scatter
 #[derive(Debug, Clone)]
@@ -17,13 +99,17 @@ pub struct Scatter {
     yerrorbar: Option<Vec<f64>>,
     errorbarcolor: String,
     barzorder: i32,
+    uplims: Option<Vec<bool>>,
+    lolims: Option<Vec<bool>>,
+    errorbar_only: bool,
+    extra_kwargs: Vec<(String, KwargValue)>,
 }
 
 impl Scatter {
-    pub fn new(x: impl Into<Vec<f64>>, y: impl Into<Vec<f64>>) -> Self {
+    pub fn new(x: impl IntoPlotData, y: impl IntoPlotData) -> Self {
         Scatter {
-            x_values: x.into(),
-            y_values: y.into(),
+            x_values: x.into_plot_data(),
+            y_values: y.into_plot_data(),
             color: String::from("tab:blue"),
             fill: true,
             marker: String::from('o'),
@@ -34,8 +120,20 @@ impl Scatter {
             yerrorbar: None,
             errorbarcolor: String::from("tab:red"),
             barzorder: 0,
+            uplims: None,
+            lolims: None,
+            errorbar_only: false,
+            extra_kwargs: Vec::new(),
         }
     }
+    pub fn from_measures(x: &Measure, y: &Measure) -> Self {
+        Scatter::new(x, y)
+            .xerrorbar(x.error().clone())
+            .yerrorbar(y.error().clone())
+    }
+    pub fn from_dataset(data: &Dataset) -> Self {
+        Scatter::from_measures(data.x(), data.y())
+    }
 
     pub fn color(mut self, color: impl Into<String>) -> Self {
         self.color = color.into();
@@ -57,6 +155,12 @@ impl Scatter {
         self.label = label.into();
         self
     }
+    pub fn label_from(self, measure: &Measure) -> Self {
+        self.label(measure.label())
+    }
+    pub fn label_measure(self, text: impl Into<String>, measure: impl Into<Measure>) -> Self {
+        self.label(measure_label(text.into(), measure.into()))
+    }
     pub fn zorder(mut self, zorder: i32) -> Self {
         self.zorder = zorder;
         self
@@ -77,14 +181,45 @@ impl Scatter {
         self.barzorder = barzorder;
         self
     }
-    pub fn scatter(self) -> PyResult<()> {
+    /// Marks the points that are upper limits, drawn as an arrow pointing
+    /// down from the end of their y errorbar.
+    pub fn uplims(mut self, uplims: impl Into<Option<Vec<bool>>>) -> Self {
+        self.uplims = uplims.into();
+        self
+    }
+    /// Marks the points that are lower limits, drawn as an arrow pointing up
+    /// from the end of their y errorbar.
+    pub fn lolims(mut self, lolims: impl Into<Option<Vec<bool>>>) -> Self {
+        self.lolims = lolims.into();
+        self
+    }
+    /// Draws only the errorbars, without the dots, so the label is given to
+    /// the errorbars.
+    pub fn errorbar_only(mut self, errorbar_only: bool) -> Self {
+        self.errorbar_only = errorbar_only;
+        self
+    }
+    /// Adds a keyword argument passed directly to the scatter call, or to the
+    /// errorbar one when only errorbars are drawn.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
+    pub fn scatter(self) -> Result<(), PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("errorbar")?.call(
+            let plt = pyplot(py)?;
+            call(
+                plt,
+                "errorbar",
                 (self.x_values.clone(), self.y_values.clone()),
                 Some(self.clone().errorbarconfig(py)?),
             )?;
-            plt.getattr("scatter")?.call(
+            if self.errorbar_only {
+                return Ok(());
+            }
+            call(
+                plt,
+                "scatter",
                 (self.x_values.clone(), self.y_values.clone()),
                 Some(self.dotconfig(py)?),
             )?;
@@ -105,6 +240,7 @@ impl Scatter {
         pydict.set_item("edgecolors", self.color)?;
         pydict.set_item("label", self.label)?;
         pydict.set_item("zorder", self.zorder)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
         Ok(pydict)
     }
 
@@ -115,6 +251,16 @@ impl Scatter {
         pydict.set_item("ecolor", self.errorbarcolor.clone())?;
         pydict.set_item("fmt", "none")?;
         pydict.set_item("zorder", self.barzorder)?;
+        if let Some(uplims) = self.uplims {
+            pydict.set_item("uplims", uplims)?;
+        }
+        if let Some(lolims) = self.lolims {
+            pydict.set_item("lolims", lolims)?;
+        }
+        if self.errorbar_only {
+            pydict.set_item("label", self.label)?;
+            set_extra_kwargs(pydict, &self.extra_kwargs)?;
+        }
         Ok(pydict)
     }
 }
@@ -129,18 +275,20 @@ pub struct Plot {
     linewidth: usize,
     label: Option<String>,
     zorder: i32,
+    extra_kwargs: Vec<(String, KwargValue)>,
 }
 
 impl Plot {
-    pub fn new(x: impl Into<Vec<f64>>, y: impl Into<Vec<f64>>) -> Self {
+    pub fn new(x: impl IntoPlotData, y: impl IntoPlotData) -> Self {
         Plot {
-            x_values: x.into(),
-            y_values: y.into(),
+            x_values: x.into_plot_data(),
+            y_values: y.into_plot_data(),
             color: String::from("tab:blue"),
             linestyle: String::from("-"),
             linewidth: 2,
             label: None,
             zorder: 0,
+            extra_kwargs: Vec::new(),
         }
     }
     pub fn color(mut self, color: impl Into<String>) -> Self {
@@ -159,15 +307,28 @@ impl Plot {
         self.label = label.into();
         self
     }
+    pub fn label_from(self, measure: &Measure) -> Self {
+        self.label(measure.label())
+    }
+    pub fn label_measure(self, text: impl Into<String>, measure: impl Into<Measure>) -> Self {
+        self.label(measure_label(text.into(), measure.into()))
+    }
     pub fn zorder(mut self, zorder: i32) -> Self {
         self.zorder = zorder;
         self
     }
+    /// Adds a keyword argument passed directly to the plot call.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
 
-    pub fn plot(self) -> PyResult<()> {
+    pub fn plot(self) -> Result<(), PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("plot")?.call(
+            let plt = pyplot(py)?;
+            call(
+                plt,
+                "plot",
                 (self.x_values.clone(), self.y_values.clone()),
                 Some(self.plot_config(py)?),
             )?;
@@ -181,18 +342,217 @@ impl Plot {
         pydict.set_item("linewidth", self.linewidth)?;
         pydict.set_item("label", self.label)?;
         pydict.set_item("zorder", self.zorder)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
         Ok(pydict)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+// Hexbin: density plot for datasets too dense to draw every point.
+#[derive(Debug, Clone)]
+pub struct Hexbin {
+    x_values: Vec<f64>,
+    y_values: Vec<f64>,
+    gridsize: usize,
+    colormap: String,
+    log: bool,
+    mincount: Option<usize>,
+    colorbar: bool,
+    colorbar_label: Option<String>,
+    zorder: i32,
+    extra_kwargs: Vec<(String, KwargValue)>,
+}
+
+impl Hexbin {
+    pub fn new(x: impl IntoPlotData, y: impl IntoPlotData) -> Self {
+        Hexbin {
+            x_values: x.into_plot_data(),
+            y_values: y.into_plot_data(),
+            gridsize: 100,
+            colormap: String::from("viridis"),
+            log: false,
+            mincount: None,
+            colorbar: true,
+            colorbar_label: None,
+            zorder: 0,
+            extra_kwargs: Vec::new(),
+        }
+    }
+    // Number of hexagons in the x direction.
+    pub fn gridsize(mut self, gridsize: usize) -> Self {
+        self.gridsize = gridsize;
+        self
+    }
+    pub fn colormap(mut self, colormap: impl Into<String>) -> Self {
+        self.colormap = colormap.into();
+        self
+    }
+    // Colors the hexagons by log10 of the count instead of the count.
+    pub fn log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+    // Hides the hexagons with less points than mincount.
+    pub fn mincount(mut self, mincount: impl Into<Option<usize>>) -> Self {
+        self.mincount = mincount.into();
+        self
+    }
+    pub fn colorbar(mut self, colorbar: bool) -> Self {
+        self.colorbar = colorbar;
+        self
+    }
+    pub fn colorbar_label(mut self, label: impl Into<Option<String>>) -> Self {
+        self.colorbar_label = label.into();
+        self
+    }
+    pub fn zorder(mut self, zorder: i32) -> Self {
+        self.zorder = zorder;
+        self
+    }
+    /// Adds a keyword argument passed directly to the hexbin call.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn hexbin(self) -> Result<(), PlotError> {
+        Python::with_gil(|py| {
+            let plt = pyplot(py)?;
+            let image = call(
+                plt,
+                "hexbin",
+                (self.x_values.clone(), self.y_values.clone()),
+                Some(self.hexbin_config(py)?),
+            )?;
+            if self.colorbar {
+                let label = self
+                    .colorbar_label
+                    .clone()
+                    .unwrap_or_else(|| String::from(if self.log { "log10(N)" } else { "N" }));
+                call(
+                    plt,
+                    "colorbar",
+                    (image,),
+                    Some([("label", label)].into_py_dict(py)),
+                )?;
+            }
+            Ok(())
+        })
+    }
+    fn hexbin_config<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let pydict = PyDict::new(py);
+        pydict.set_item("gridsize", self.gridsize)?;
+        pydict.set_item("cmap", &self.colormap)?;
+        if self.log {
+            pydict.set_item("bins", "log")?;
+        }
+        if let Some(mincount) = self.mincount {
+            pydict.set_item("mincnt", mincount)?;
+        }
+        pydict.set_item("zorder", self.zorder)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
+        Ok(pydict)
+    }
+}
+
+// Histogram: bars centered at x whose width is twice the x error, as returned
+// by Measure::histogram.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    x_values: Vec<f64>,
+    heights: Vec<f64>,
+    widths: Vec<f64>,
+    yerrorbar: Option<Vec<f64>>,
+    color: String,
+    edgecolor: String,
+    errorbarcolor: String,
+    label: Option<String>,
+    zorder: i32,
+    extra_kwargs: Vec<(String, KwargValue)>,
+}
+
+impl Histogram {
+    pub fn new(centers: &Measure, counts: &Measure) -> Self {
+        Histogram {
+            x_values: centers.value().clone(),
+            heights: counts.value().clone(),
+            widths: centers.error().iter().map(|err| 2.0 * err).collect(),
+            yerrorbar: Some(counts.error().clone()),
+            color: String::from("tab:blue"),
+            edgecolor: String::from("black"),
+            errorbarcolor: String::from("tab:red"),
+            label: None,
+            zorder: 0,
+            extra_kwargs: Vec::new(),
+        }
+    }
+    pub fn yerrorbar(mut self, yerrorbar: impl Into<Option<Vec<f64>>>) -> Self {
+        self.yerrorbar = yerrorbar.into();
+        self
+    }
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.color = color.into();
+        self
+    }
+    pub fn edgecolor(mut self, edgecolor: impl Into<String>) -> Self {
+        self.edgecolor = edgecolor.into();
+        self
+    }
+    pub fn errorbarcolor(mut self, errorbarcolor: impl Into<String>) -> Self {
+        self.errorbarcolor = errorbarcolor.into();
+        self
+    }
+    pub fn label(mut self, label: impl Into<Option<String>>) -> Self {
+        self.label = label.into();
+        self
+    }
+    pub fn zorder(mut self, zorder: i32) -> Self {
+        self.zorder = zorder;
+        self
+    }
+    /// Adds a keyword argument passed directly to the bar call.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn histogram(self) -> Result<(), PlotError> {
+        Python::with_gil(|py| {
+            let plt = pyplot(py)?;
+            call(
+                plt,
+                "bar",
+                (self.x_values.clone(), self.heights.clone()),
+                Some(self.histogram_config(py)?),
+            )?;
+            Ok(())
+        })
+    }
+    fn histogram_config(self, py: Python<'_>) -> PyResult<&PyDict> {
+        let pydict = PyDict::new(py);
+        pydict.set_item("width", self.widths)?;
+        pydict.set_item("yerr", self.yerrorbar)?;
+        pydict.set_item("color", self.color)?;
+        pydict.set_item("edgecolor", self.edgecolor)?;
+        pydict.set_item("ecolor", self.errorbarcolor)?;
+        pydict.set_item("label", self.label)?;
+        pydict.set_item("zorder", self.zorder)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
+        Ok(pydict)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Labels {
     fontsize: usize,
+    extra_kwargs: Vec<(String, KwargValue)>,
 }
 
 impl Default for Labels {
     fn default() -> Self {
-        Labels { fontsize: 18 }
+        Labels {
+            fontsize: 18,
+            extra_kwargs: Vec::new(),
+        }
     }
 }
 
@@ -205,37 +565,52 @@ impl Labels {
         self.fontsize = fontsize;
         self
     }
+    /// Adds a keyword argument passed directly to the xlabel and ylabel calls.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
+    fn config<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let pydict = PyDict::new(py);
+        pydict.set_item("fontsize", self.fontsize)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
+        Ok(pydict)
+    }
 
-    pub fn xlabel(self, text: impl Into<String>) -> PyResult<Self> {
+    pub fn xlabel(self, text: impl Into<String>) -> Result<Self, PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("xlabel")?.call(
-                (text.into(),),
-                Some([("fontsize", self.fontsize)].into_py_dict(py)),
-            )?;
+            let plt = pyplot(py)?;
+            call(plt, "xlabel", (text.into(),), Some(self.config(py)?))?;
             Ok(self)
         })
     }
-    pub fn ylabel(self, text: impl Into<String>) -> PyResult<Self> {
+    pub fn xlabel_from(self, measure: &Measure) -> Result<Self, PlotError> {
+        self.xlabel(measure.label())
+    }
+    pub fn ylabel_from(self, measure: &Measure) -> Result<Self, PlotError> {
+        self.ylabel(measure.label())
+    }
+    pub fn ylabel(self, text: impl Into<String>) -> Result<Self, PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("ylabel")?.call(
-                (text.into(),),
-                Some([("fontsize", self.fontsize)].into_py_dict(py)),
-            )?;
+            let plt = pyplot(py)?;
+            call(plt, "ylabel", (text.into(),), Some(self.config(py)?))?;
             Ok(self)
         })
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Ticks {
     fontsize: usize,
+    extra_kwargs: Vec<(String, KwargValue)>,
 }
 
 impl Default for Ticks {
     fn default() -> Self {
-        Ticks { fontsize: 14 }
+        Ticks {
+            fontsize: 14,
+            extra_kwargs: Vec::new(),
+        }
     }
 }
 
@@ -247,30 +622,275 @@ impl Ticks {
         self.fontsize = fontsize;
         self
     }
-    pub fn xticks(self) -> PyResult<Self> {
+    /// Adds a keyword argument passed directly to the xticks and yticks calls.
+    pub fn extra_kwarg(mut self, key: impl Into<String>, value: impl Into<KwargValue>) -> Self {
+        self.extra_kwargs.push((key.into(), value.into()));
+        self
+    }
+    fn config<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let pydict = PyDict::new(py);
+        pydict.set_item("fontsize", self.fontsize)?;
+        set_extra_kwargs(pydict, &self.extra_kwargs)?;
+        Ok(pydict)
+    }
+    pub fn xticks(self) -> Result<Self, PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("xticks")?
-                .call((), Some([("fontsize", self.fontsize)].into_py_dict(py)))?;
+            let plt = pyplot(py)?;
+            call(plt, "xticks", (), Some(self.config(py)?))?;
             Ok(self)
         })
     }
 
-    pub fn yticks(self) -> PyResult<Self> {
+    pub fn yticks(self) -> Result<Self, PlotError> {
         Python::with_gil(|py| {
-            let plt = PyModule::import(py, "matplotlib.pyplot")?;
-            plt.getattr("yticks")?
-                .call((), Some([("fontsize", self.fontsize)].into_py_dict(py)))?;
+            let plt = pyplot(py)?;
+            call(plt, "yticks", (), Some(self.config(py)?))?;
             Ok(self)
         })
     }
+    pub fn xticks_from(self, measure: &Measure) -> Result<Self, PlotError> {
+        self.formatter_from("xaxis", measure)?.xticks()
+    }
+    pub fn yticks_from(self, measure: &Measure) -> Result<Self, PlotError> {
+        self.formatter_from("yaxis", measure)?.yticks()
+    }
+    // Formats the tick labels with the decimals of the first significant figure
+    // of the errors, scaled with SI prefixes if the measure uses Style::SI.
+    fn formatter_from(self, axis: &str, measure: &Measure) -> Result<Self, PlotError> {
+        Python::with_gil(|py| {
+            let plt = pyplot(py)?;
+            let ticker = PyModule::import(py, "matplotlib.ticker")?;
+            let error = measure
+                .error()
+                .iter()
+                .copied()
+                .filter(|err| *err > 0.0)
+                .fold(f64::INFINITY, f64::min);
+            let decimals = |error: f64| {
+                if error.is_finite() {
+                    (-error.log10().floor()).max(0.0) as usize
+                } else {
+                    0
+                }
+            };
+            let formatter = if *measure.style() == Style::SI {
+                let max = measure
+                    .value()
+                    .iter()
+                    .fold(0.0, |acc: f64, val| acc.max(val.abs()));
+                let (exponent, _) = crate::units::si_prefix(max);
+                let pydict = PyDict::new(py);
+                pydict.set_item("unit", measure.unit().symbol())?;
+                pydict.set_item("places", decimals(error / 10.0_f64.powi(exponent)))?;
+                call(ticker, "EngFormatter", (), Some(pydict))?
+            } else {
+                call(
+                    ticker,
+                    "FormatStrFormatter",
+                    (format!("%.{}f", decimals(error)),),
+                    None,
+                )?
+            };
+            let axis = call(plt, "gca", (), None)?.getattr(axis)?;
+            call(axis, "set_major_formatter", (formatter,), None)?;
+            Ok(self)
+        })
+    }
+}
+
+/// Value of a keyword argument passed directly to matplotlib.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KwargValue {
+    /// Python's None.
+    None,
+    /// Boolean.
+    Bool(bool),
+    /// Integer.
+    Int(i64),
+    /// Floating point number.
+    Float(f64),
+    /// String.
+    Text(String),
+    /// List of numbers.
+    List(Vec<f64>),
+}
+
+impl From<bool> for KwargValue {
+    fn from(value: bool) -> Self {
+        KwargValue::Bool(value)
+    }
+}
+
+impl From<i32> for KwargValue {
+    fn from(value: i32) -> Self {
+        KwargValue::Int(value as i64)
+    }
+}
+
+impl From<i64> for KwargValue {
+    fn from(value: i64) -> Self {
+        KwargValue::Int(value)
+    }
+}
+
+impl From<usize> for KwargValue {
+    fn from(value: usize) -> Self {
+        KwargValue::Int(value as i64)
+    }
+}
+
+impl From<f64> for KwargValue {
+    fn from(value: f64) -> Self {
+        KwargValue::Float(value)
+    }
+}
+
+impl From<&str> for KwargValue {
+    fn from(value: &str) -> Self {
+        KwargValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for KwargValue {
+    fn from(value: String) -> Self {
+        KwargValue::Text(value)
+    }
+}
+
+impl From<Vec<f64>> for KwargValue {
+    fn from(value: Vec<f64>) -> Self {
+        KwargValue::List(value)
+    }
+}
+
+impl ToPyObject for KwargValue {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        match self {
+            KwargValue::None => py.None(),
+            KwargValue::Bool(value) => value.to_object(py),
+            KwargValue::Int(value) => value.to_object(py),
+            KwargValue::Float(value) => value.to_object(py),
+            KwargValue::Text(value) => value.to_object(py),
+            KwargValue::List(value) => value.to_object(py),
+        }
+    }
+}
+
+// Adds the extra keyword arguments, overwriting the ones already set.
+fn set_extra_kwargs(pydict: &PyDict, extra_kwargs: &[(String, KwargValue)]) -> PyResult<()> {
+    for (key, value) in extra_kwargs {
+        pydict.set_item(key, value)?;
+    }
+    Ok(())
+}
+
+/// Errors of the functions that draw and save figures.
+#[derive(Debug, thiserror::Error)]
+pub enum PlotError {
+    /// A call to matplotlib failed, with the arguments it received.
+    #[error("Call to {call}({arguments}) failed: {message}")]
+    Call {
+        /// Name of the matplotlib function, e.g. "scatter".
+        call: String,
+        /// Arguments of the call as written in Python, shortened when long.
+        arguments: String,
+        /// Message of the Python exception.
+        message: String,
+    },
+    /// Python failed outside of a call, e.g. importing matplotlib.
+    #[error("Python error: {0}")]
+    Python(String),
+    /// Error of a backend not based on matplotlib.
+    #[error("Backend error: {0}")]
+    Backend(String),
+}
+
+impl From<PyErr> for PlotError {
+    fn from(err: PyErr) -> Self {
+        PlotError::Python(err.to_string())
+    }
+}
+
+// Calls a function of a python module, adding the call and its arguments to
+// the error if it fails.
+fn call<'py>(
+    module: &'py PyAny,
+    function: &str,
+    args: impl IntoPy<Py<PyTuple>>,
+    kwargs: Option<&PyDict>,
+) -> Result<&'py PyAny, PlotError> {
+    let py = module.py();
+    let args: &PyTuple = args.into_py(py).into_ref(py);
+    module
+        .getattr(function)
+        .and_then(|function| function.call(args, kwargs))
+        .map_err(|err| PlotError::Call {
+            call: function.to_string(),
+            arguments: arguments(args, kwargs),
+            message: err.to_string(),
+        })
+}
+
+// Writes the arguments of a call as python would, shortening the long ones.
+fn arguments(args: &PyTuple, kwargs: Option<&PyDict>) -> String {
+    let shorten = |text: String| {
+        if text.chars().count() > 40 {
+            format!("{}...", text.chars().take(37).collect::<String>())
+        } else {
+            text
+        }
+    };
+    let repr = |value: &PyAny| {
+        value
+            .repr()
+            .map(|repr| shorten(repr.to_string()))
+            .unwrap_or_default()
+    };
+    args.iter()
+        .map(repr)
+        .chain(
+            kwargs
+                .into_iter()
+                .flat_map(|kwargs| kwargs.iter())
+                .map(|(key, val)| format!("{}={}", key, repr(val))),
+        )
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Imports pyplot, choosing first the non-interactive Agg backend when there is
+// no display available and no backend was configured, so figures can still be
+// saved on servers and CI machines.
+fn pyplot(py: Python<'_>) -> PyResult<&PyModule> {
+    static HEADLESS: Once = Once::new();
+    let mut result = Ok(());
+    HEADLESS.call_once(|| {
+        let no_display = cfg!(all(unix, not(target_os = "macos")))
+            && std::env::var_os("DISPLAY").is_none()
+            && std::env::var_os("WAYLAND_DISPLAY").is_none();
+        if no_display {
+            result = PyModule::import(py, "os")
+                .and_then(|os| os.getattr("environ"))
+                .and_then(|environ| environ.call_method1("setdefault", ("MPLBACKEND", "Agg")))
+                .map(|_| ());
+        }
+    });
+    result?;
+    PyModule::import(py, "matplotlib.pyplot")
+}
+
+pub fn set_backend(backend: &str) -> Result<(), PlotError> {
+    Python::with_gil(|py| {
+        let matplotlib = PyModule::import(py, "matplotlib")?;
+        call(matplotlib, "use", (backend,), None)?;
+        Ok(())
+    })
 }
 
-pub fn figure(id: usize) -> PyResult<()> {
+pub fn figure(id: usize) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("figure")?
-            .call((), Some([("id", id)].into_py_dict(py)))?;
+        let plt = pyplot(py)?;
+        call(plt, "figure", (), Some([("id", id)].into_py_dict(py)))?;
         Ok(())
     })
 }
@@ -307,39 +927,38 @@ impl PlotSize {
     }
 }
 
-pub fn plot_size(conf: PlotSize) -> PyResult<()> {
+pub fn plot_size(conf: PlotSize) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
+        let plt = pyplot(py)?;
         if conf.autosize {
-            plt.getattr("tight_layout")?.call0()?;
+            call(plt, "tight_layout", (), None)?;
         } else {
-            plt.getattr("subplots_adjust")?
-                .call((), Some(conf.plot_size(py)?))?;
+            call(plt, "subplots_adjust", (), Some(conf.plot_size(py)?))?;
         }
         Ok(())
     })
 }
 
-pub fn xscale(log: impl Into<String>) -> PyResult<()> {
+pub fn xscale(log: impl Into<String>) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("xscale")?.call1((log.into(),))?;
+        let plt = pyplot(py)?;
+        call(plt, "xscale", (log.into(),), None)?;
         Ok(())
     })
 }
 
-pub fn yscale(log: impl Into<String>) -> PyResult<()> {
+pub fn yscale(log: impl Into<String>) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("yscale")?.call1((log.into(),))?;
+        let plt = pyplot(py)?;
+        call(plt, "yscale", (log.into(),), None)?;
         Ok(())
     })
 }
 
-pub fn legend() -> PyResult<()> {
+pub fn legend() -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("legend")?.call0()?;
+        let plt = pyplot(py)?;
+        call(plt, "legend", (), None)?;
         Ok(())
     })
 }
@@ -349,39 +968,74 @@ pub fn limits(
     top: Option<f64>,
     left: Option<f64>,
     right: Option<f64>,
-) -> PyResult<()> {
+) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("xlim")?.call1((left, right))?;
-        plt.getattr("ylim")?.call1((bottom, top))?;
+        let plt = pyplot(py)?;
+        call(plt, "xlim", (left, right), None)?;
+        call(plt, "ylim", (bottom, top), None)?;
         Ok(())
     })
 }
 
-pub fn use_latex(latex: bool) -> PyResult<()> {
+pub fn use_latex(latex: bool) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
+        let plt = pyplot(py)?;
         plt.getattr("rcParams")?.set_item("text.usetex", latex)?;
         Ok(())
     })
 }
 
-pub fn save(path: &str) -> PyResult<()> {
+/// Information of a figure saved with [save].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedFigure {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Width and height in inches.
+    pub size: (f64, f64),
+    /// Resolution in dots per inch.
+    pub dpi: f64,
+    /// Modification time of the file, or when it was saved if unavailable.
+    pub created: SystemTime,
+}
+
+impl SavedFigure {
+    /// Width and height in pixels.
+    pub fn pixels(&self) -> (u32, u32) {
+        (
+            (self.size.0 * self.dpi).round() as u32,
+            (self.size.1 * self.dpi).round() as u32,
+        )
+    }
+}
+
+pub fn save(path: &str) -> Result<SavedFigure, PlotError> {
     show_conf()?;
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("savefig")?
-            .call((path,), Some([("dpi", "figure")].into_py_dict(py)))?;
-        Ok(())
+        let plt = pyplot(py)?;
+        call(
+            plt,
+            "savefig",
+            (path,),
+            Some([("dpi", "figure")].into_py_dict(py)),
+        )?;
+        let figure = call(plt, "gcf", (), None)?;
+        let size: Vec<f64> = call(figure, "get_size_inches", (), None)?.extract()?;
+        Ok(SavedFigure {
+            path: PathBuf::from(path),
+            size: (size[0], size[1]),
+            dpi: figure.getattr("dpi")?.extract()?,
+            created: std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or_else(|_| SystemTime::now()),
+        })
     })
 }
 
-pub fn show() -> PyResult<()> {
+pub fn show() -> Result<(), PlotError> {
     show_conf()?;
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        println!("{:?}", plt.getattr("rcParams")?.get_item("text.usetex")?);
-        plt.getattr("show")?.call0()?;
+        let plt = pyplot(py)?;
+        call(plt, "show", (), None)?;
         Ok(())
     })
 }
@@ -391,21 +1045,31 @@ fn show_conf() -> PyResult<()> {
         loc.getattr("setlocale")?
             .call1((loc.getattr("LC_ALL")?, ""))?;
 
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
+        let plt = pyplot(py)?;
         // plt.getattr("rcParams")?.set_item(ç"text.usetex", true)?;
-        let pyd = PyDict::new(py);
-        pyd.set_item("useLocale", true)?;
-        pyd.set_item("style", "sci")?;
-        pyd.set_item("scilimits", (-2, 2))?;
-        plt.getattr("ticklabel_format")?.call((), Some(pyd))?;
+        for axis in ["x", "y"] {
+            let pyd = PyDict::new(py);
+            pyd.set_item("axis", axis)?;
+            pyd.set_item("useLocale", true)?;
+            pyd.set_item("style", "sci")?;
+            pyd.set_item("scilimits", (-2, 2))?;
+            // Axes formatted from a measure don't use the ScalarFormatter and
+            // keep their own format.
+            match plt.getattr("ticklabel_format")?.call((), Some(pyd)) {
+                Err(err) if err.is_instance_of::<PyAttributeError>(py) => continue,
+                result => result?,
+            };
+        }
         Ok(())
     })
 }
 
-pub fn add_latex_code(code: String) -> PyResult<()> {
+pub fn add_latex_code(code: String) -> Result<(), PlotError> {
     Python::with_gil(|py| {
-        let plt = PyModule::import(py, "matplotlib.pyplot")?;
-        plt.getattr("rc")?.call(
+        let plt = pyplot(py)?;
+        call(
+            plt,
+            "rc",
             ("text.latex",),
             Some([("preamble", String::from("\n").push_str(code.as_ref()))].into_py_dict(py)),
         )?;
@@ -413,7 +1077,12 @@ pub fn add_latex_code(code: String) -> PyResult<()> {
     })
 }
 
-pub fn execute_python(code: &str) -> PyResult<()> {
+// Legend entry "text = value ± error" following the style of the measure.
+fn measure_label(text: String, measure: Measure) -> String {
+    format!("{} = {}", text, measure.aprox())
+}
+
+pub fn execute_python(code: &str) -> Result<(), PlotError> {
     Python::with_gil(|py| {
         py.eval(code, None, None)?;
         Ok(())
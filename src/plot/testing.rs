@@ -0,0 +1,148 @@
+//! Off-screen recording of figures, to verify them without a display server
+//! nor matplotlib installed.
+//!
+//! While recording, matplotlib is replaced by a recorder that stores every
+//! call made by the plot functions as a drawing command, so tests can check
+//! the figure description instead of its pixels. Queries about the figure
+//! answer with the matplotlib defaults, a 6.4 x 4.8 inches figure at 100 dpi.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use ferrilab::{measure, Measure, Scatter, plot::testing};
+//! let x = measure!([1, 2, 3], 0.1);
+//! let y = measure!([2, 4, 6], 0.2);
+//!
+//! let commands = testing::record(|| Scatter::from_measures(&x, &y).scatter()).unwrap();
+//! assert_eq!(commands[1].function, "pyplot.scatter");
+//! assert_eq!(commands[1].args, "[[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]]");
+//! ```
+use super::PlotError;
+use pyo3::prelude::*;
+use std::sync::Mutex;
+
+/// Held while recording, as the recorder replaces matplotlib for the whole
+/// Python interpreter.
+static RECORDING: Mutex<()> = Mutex::new(());
+
+const RECORDER: &str = r#"
+import json
+import sys
+import types
+
+MODULES = {"matplotlib": "matplotlib", "matplotlib.pyplot": "pyplot", "matplotlib.ticker": "ticker"}
+VALUES = {"pyplot.gcf().get_size_inches()": [6.4, 4.8], "pyplot.gcf().dpi": 100.0}
+commands = []
+saved = {}
+
+
+class Recorder:
+    def __init__(self, path):
+        self._path = path
+
+    def __getattr__(self, name):
+        return lookup(self._path + "." + name)
+
+    def __call__(self, *args, **kwargs):
+        record(self._path, list(args), kwargs)
+        return lookup(self._path + "()")
+
+    def __getitem__(self, key):
+        return Recorder(self._path + "[" + repr(key) + "]")
+
+    def __setitem__(self, key, value):
+        record(self._path + ".__setitem__", [key, value], {})
+
+    def __repr__(self):
+        return "<" + self._path + ">"
+
+
+def lookup(path):
+    return VALUES.get(path, Recorder(path))
+
+
+def record(function, args, kwargs):
+    commands.append((function, json.dumps(args, default=repr), json.dumps(kwargs, default=repr)))
+
+
+def install():
+    for name, path in MODULES.items():
+        saved[name] = sys.modules.get(name)
+        module = types.ModuleType(name)
+        module.__getattr__ = Recorder(path).__getattr__
+        sys.modules[name] = module
+
+
+def uninstall():
+    for name, module in saved.items():
+        if module is None:
+            del sys.modules[name]
+        else:
+            sys.modules[name] = module
+    return commands
+"#;
+
+/// Call made to matplotlib while drawing a figure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawCommand {
+    /// Path of the function called, e.g. "pyplot.scatter".
+    pub function: String,
+    /// Positional arguments as a JSON array.
+    pub args: String,
+    /// Keyword arguments as a JSON object.
+    pub kwargs: String,
+}
+
+impl DrawCommand {
+    /// Writes the command as a JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"function\": \"{}\", \"args\": {}, \"kwargs\": {}}}",
+            self.function, self.args, self.kwargs
+        )
+    }
+}
+
+/// Runs the drawing function without rendering anything, returning the
+/// commands it would have sent to matplotlib.
+///
+/// The recorder replaces matplotlib in the whole process, so simultaneous
+/// calls, e.g. from tests running in parallel, wait for each other. Figures
+/// drawn from other threads while recording are recorded too.
+pub fn record<F: FnOnce() -> Result<(), PlotError>>(
+    draw: F,
+) -> Result<Vec<DrawCommand>, PlotError> {
+    // A panic in another recording does not prevent the next ones.
+    let _recording = RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let recorder: Py<PyModule> = Python::with_gil(|py| {
+        let recorder = PyModule::from_code(py, RECORDER, "recorder.py", "ferrilab_recorder")?;
+        recorder.getattr("install")?.call0()?;
+        Ok::<_, PyErr>(recorder.into())
+    })?;
+    let result = draw();
+    let commands: Vec<(String, String, String)> =
+        Python::with_gil(|py| recorder.as_ref(py).getattr("uninstall")?.call0()?.extract())?;
+    result?;
+    Ok(commands
+        .into_iter()
+        .map(|(function, args, kwargs)| DrawCommand {
+            function,
+            args,
+            kwargs,
+        })
+        .collect())
+}
+
+/// Writes a list of commands as a JSON array, one command per line.
+pub fn to_json(commands: &[DrawCommand]) -> String {
+    format!(
+        "[\n{}\n]",
+        commands
+            .iter()
+            .map(|command| format!("    {}", command.to_json()))
+            .collect::<Vec<String>>()
+            .join(",\n")
+    )
+}
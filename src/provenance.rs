@@ -0,0 +1,136 @@
+//! Contains the struct Provenance, used to audit how a derived measure was obtained.
+use std::{fmt::Display, sync::Arc};
+
+/// Node of the operation tree that produced a measure. It is only recorded when
+/// some of the measures involved were marked with [Measure::track](crate::Measure::track).
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure};
+/// let time = measure!([1, 2], 0.1).track("time");
+/// let position = measure!([3, 5], 0.2).track("position");
+/// let speed = (&position / &time).pow(2);
+///
+/// println!("{}", speed.provenance().unwrap());
+///
+/// // Output
+///
+/// /*
+/// pow (exponent = 2)
+///   div
+///     position
+///     time
+///  */
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Provenance {
+    operation: String,
+    parameters: Vec<(String, f64)>,
+    inputs: Vec<Arc<Provenance>>,
+}
+
+impl Provenance {
+    /// Constructs a leaf node, representing a measure obtained directly.
+    pub fn leaf(name: impl Into<String>) -> Provenance {
+        Provenance {
+            operation: name.into(),
+            parameters: Vec::new(),
+            inputs: Vec::new(),
+        }
+    }
+    /// Creates the node of an operation if any of its inputs is being tracked.
+    pub(crate) fn derive(
+        operation: &str,
+        inputs: &[&Option<Arc<Provenance>>],
+        parameters: &[(&str, f64)],
+    ) -> Option<Arc<Provenance>> {
+        if inputs.iter().all(|input| input.is_none()) {
+            return None;
+        }
+        Some(Arc::new(Provenance {
+            operation: operation.to_string(),
+            parameters: parameters
+                .iter()
+                .map(|(name, val)| (name.to_string(), *val))
+                .collect(),
+            inputs: inputs
+                .iter()
+                .map(|input| {
+                    (*input)
+                        .clone()
+                        .unwrap_or_else(|| Arc::new(Provenance::leaf("untracked")))
+                })
+                .collect(),
+        }))
+    }
+    /// Name of the operation, or of the measure for leaf nodes.
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+    /// Numeric parameters of the operation as tuples (name, value).
+    pub fn parameters(&self) -> &[(String, f64)] {
+        &self.parameters
+    }
+    /// Nodes of the measures used as inputs of the operation.
+    pub fn inputs(&self) -> impl Iterator<Item = &Provenance> {
+        self.inputs.iter().map(|input| input.as_ref())
+    }
+    /// Checks if the node has no inputs.
+    pub fn is_leaf(&self) -> bool {
+        self.inputs.is_empty()
+    }
+    /// Writes the operation tree as an indented text, one node per line.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        self.write_text(&mut text, 0);
+        text
+    }
+    fn write_text(&self, text: &mut String, depth: usize) {
+        text.push_str(&format!("{}{}\n", "  ".repeat(depth), self.label()));
+        self.inputs
+            .iter()
+            .for_each(|input| input.write_text(text, depth + 1));
+    }
+    /// Writes the operation tree in the Graphviz dot language.
+    pub fn to_dot(&self) -> String {
+        let mut lines = Vec::new();
+        self.write_dot(&mut lines, &mut 0);
+        format!("digraph provenance {{\n{}}}\n", lines.concat())
+    }
+    fn write_dot(&self, lines: &mut Vec<String>, counter: &mut usize) -> usize {
+        let id = *counter;
+        *counter += 1;
+        lines.push(format!(
+            "    n{} [label=\"{}\"];\n",
+            id,
+            self.label().replace('"', "\\\"")
+        ));
+        for input in self.inputs.iter() {
+            let input_id = input.write_dot(lines, counter);
+            lines.push(format!("    n{} -> n{};\n", input_id, id));
+        }
+        id
+    }
+    fn label(&self) -> String {
+        if self.parameters.is_empty() {
+            self.operation.clone()
+        } else {
+            format!(
+                "{} ({})",
+                self.operation,
+                self.parameters
+                    .iter()
+                    .map(|(name, val)| format!("{} = {}", name, val))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text().trim_end())
+    }
+}
@@ -0,0 +1,57 @@
+//! Pseudorandom numbers for the Monte Carlo methods, without depending on an
+//! external crate.
+
+/// Generator xoshiro256**, seeded with SplitMix64. It is not suitable for
+/// cryptography, only for simulations.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Constructs a generator that always yields the same numbers for the
+    /// same seed.
+    pub(crate) fn new(seed: u64) -> Rng {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            seed = seed.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *word = z ^ (z >> 31);
+        }
+        Rng { state }
+    }
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
+    }
+    /// Uniform number in `[0, 1)`.
+    pub(crate) fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    /// Random permutation of the indices `0..n`, with the Fisher-Yates
+    /// shuffle.
+    pub(crate) fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            let j = ((self.uniform() * (i + 1) as f64) as usize).min(i);
+            indices.swap(i, j);
+        }
+        indices
+    }
+    /// Number of the standard normal distribution, with the Box-Muller
+    /// transform.
+    pub(crate) fn normal(&mut self) -> f64 {
+        let radius = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+        radius * (2.0 * std::f64::consts::PI * self.uniform()).cos()
+    }
+}
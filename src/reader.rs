@@ -1,8 +1,20 @@
 use {
-    crate::Measure,
-    std::{fs::read_to_string, io::Error, path::Path},
+    crate::{Measure, Metadata},
+    std::{
+        fs::read_to_string,
+        io::Error,
+        path::Path,
+        sync::atomic::{AtomicUsize, Ordering},
+    },
 };
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Function called after parsing each chunk of rows with the number of rows
+/// parsed and the total number of rows.
+pub type ReadProgress<'a> = &'a (dyn Fn(usize, usize) + Sync);
+
 /// Object to read data from a file with all required parameters.
 pub struct Reader<'a> {
     file: &'a str,
@@ -11,11 +23,14 @@ pub struct Reader<'a> {
     decimal: &'a str,
     headers: usize,
     by_columns: bool,
+    comment: &'a str,
+    chunk_size: usize,
+    progress: Option<ReadProgress<'a>>,
 }
 
 impl<'a> Reader<'a> {
     /// Constructs a new Reader with some default values that can be changed.
-    pub fn new(file: &str, headers: usize) -> Reader {
+    pub fn new(file: &str, headers: usize) -> Reader<'_> {
         Reader {
             file,
             separator: "\t",
@@ -23,6 +38,9 @@ impl<'a> Reader<'a> {
             decimal: ",",
             headers,
             by_columns: true,
+            comment: "",
+            chunk_size: 10000,
+            progress: None,
         }
     }
     /// Character separating the columns in a row, by default "\t".
@@ -46,75 +64,123 @@ impl<'a> Reader<'a> {
         self.by_columns = by_columns;
         self
     }
+    /// Prefix of the lines with metadata, written as "key: value", which are
+    /// ignored when reading the data after the headers. Empty by default, so
+    /// no line is ignored and no metadata is read.
+    pub fn comment(mut self, comment: &'a str) -> Self {
+        self.comment = comment;
+        self
+    }
+    /// Number of rows parsed together, 10000 by default. With the feature
+    /// `rayon` the chunks are parsed in parallel.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+    /// Function called after parsing each chunk of rows with the number of rows
+    /// parsed and the total number of rows, to report the progress of big files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use ferrilab::Reader;
+    /// let progress = |parsed: usize, total: usize| println!("{}/{} rows", parsed, total);
+    ///
+    /// let measures = Reader::new("logger.csv", 1).progress(&progress).read_to_measures();
+    /// ```
+    pub fn progress(mut self, progress: ReadProgress<'a>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
     /// Extracts data from a file with csv format or similar.
     pub fn read_file(self) -> Result<Vec<Vec<Option<f64>>>, Error> {
         read_file(
-            self.file,
+            &read_to_string(Path::new(self.file))?,
             self.separator,
             self.line,
             self.decimal,
             self.headers,
             self.by_columns,
+            self.comment,
+            self.chunk_size,
+            self.progress,
         )
     }
+    /// Extracts the metadata written in the comment lines of a file.
+    pub fn read_metadata(&self) -> Result<Metadata, Error> {
+        Ok(self.metadata(&read_to_string(Path::new(self.file))?))
+    }
+    fn metadata(&self, text: &str) -> Metadata {
+        if self.comment.is_empty() {
+            Metadata::new()
+        } else {
+            Metadata::from_header(text, self.comment)
+        }
+    }
     /// Extracts data from a file creating measures by asuming each pair of columns
-    /// correspond to the value and error of a measure.
+    /// correspond to the value and error of a measure. The metadata of the file,
+    /// read with the [comment](Reader::comment) prefix, is attached to every
+    /// measure.
     pub fn read_to_measures(self) -> Vec<Measure> {
+        let text = read_to_string(Path::new(self.file)).unwrap();
+        let metadata = self.metadata(&text);
         read_to_measures(
-            self.file,
+            &text,
             self.separator,
             self.line,
             self.decimal,
             self.headers,
-        ).unwrap()
+            self.comment,
+            self.chunk_size,
+            self.progress,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|measure| measure.with_metadata(metadata.clone()))
+        .collect()
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn read_file(
-    file: &str,
+    text: &str,
     separator: &str,
     line: &str,
     decimal: &str,
     headers: usize,
     by_columns: bool,
+    comment: &str,
+    chunk_size: usize,
+    progress: Option<ReadProgress>,
 ) -> Result<Vec<Vec<Option<f64>>>, Error> {
-    let file = read_to_string(Path::new(file))?;
-
-    let rows: Vec<&str> = file
+    // The metadata lines are usually before the headers, so the comment lines
+    // are ignored before skipping them.
+    let rows: Vec<&str> = text
         .split(line)
         .filter(|str| !str.trim().is_empty())
+        .filter(|str| comment.is_empty() || !str.trim().starts_with(comment))
         .skip(headers)
         .collect();
 
-    let mut data: Vec<Vec<Option<f64>>> = rows
-        .into_iter()
-        .map(|row| {
-            row.split(separator)
-                .map(|str| {
-                    if str.trim().is_empty() {
-                        None
-                    } else {
-                        Some(str.trim())
-                    }
-                })
-                .collect()
-        })
-        .collect::<Vec<Vec<Option<&str>>>>()
-        .into_iter()
-        .map(|row_vec| {
-            row_vec
-                .into_iter()
-                .map(|str_option| {
-                    str_option.as_ref().map(|str| {
-                        str.trim()
-                            .replace(decimal, ".")
-                            .parse()
-                            .expect("Non number found")
-                    })
-                })
-                .collect()
-        })
-        .collect();
+    let total = rows.len();
+    let parsed = AtomicUsize::new(0);
+    let parse_chunk = |chunk: &[&str]| {
+        let data = parse_rows(chunk, separator, decimal);
+        if let Some(progress) = progress {
+            progress(
+                parsed.fetch_add(chunk.len(), Ordering::Relaxed) + chunk.len(),
+                total,
+            );
+        }
+        data
+    };
+
+    #[cfg(feature = "rayon")]
+    let chunks: Vec<Vec<Vec<Option<f64>>>> = rows.par_chunks(chunk_size).map(parse_chunk).collect();
+    #[cfg(not(feature = "rayon"))]
+    let chunks: Vec<Vec<Vec<Option<f64>>>> = rows.chunks(chunk_size).map(parse_chunk).collect();
+
+    let mut data: Vec<Vec<Option<f64>>> = chunks.into_iter().flatten().collect();
 
     if by_columns {
         let max_len = data.iter().map(|vec| vec.len()).max().unwrap();
@@ -132,18 +198,53 @@ fn read_file(
     Ok(data)
 }
 
+fn parse_rows(rows: &[&str], separator: &str, decimal: &str) -> Vec<Vec<Option<f64>>> {
+    rows.iter()
+        .map(|row| {
+            row.split(separator)
+                .map(|str| {
+                    if str.trim().is_empty() {
+                        None
+                    } else {
+                        Some(
+                            str.trim()
+                                .replace(decimal, ".")
+                                .parse()
+                                .expect("Non number found"),
+                        )
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn read_to_measures(
-    file: &str,
+    text: &str,
     separator: &str,
     line: &str,
     decimal: &str,
     headers: usize,
+    comment: &str,
+    chunk_size: usize,
+    progress: Option<ReadProgress>,
 ) -> Result<Vec<Measure>, Error> {
-    let data = read_file(file, separator, line, decimal, headers, true)?;
-    Ok(data
+    let data = read_file(
+        text, separator, line, decimal, headers, true, comment, chunk_size, progress,
+    )?;
+    let pairs: Vec<_> = data
         .iter()
         .step_by(2)
         .zip(data.iter().skip(1).step_by(2))
+        .collect();
+
+    #[cfg(feature = "rayon")]
+    let pairs = pairs.into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let pairs = pairs.into_iter();
+
+    Ok(pairs
         .map(|(value, error)| {
             Measure::new(
                 value
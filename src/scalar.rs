@@ -0,0 +1,397 @@
+//! Contains the struct ScalarMeasure and all its methods and traits implementations.
+use {
+    crate::{
+        aprox::{aprox, round},
+        impl_op_scalar, impl_scalar_op_number,
+        objects::{log, sigmas, Measure, MyError},
+    },
+    std::{
+        f64::consts::PI,
+        fmt::Display,
+        ops::{Add, Div, Mul, Sub},
+    },
+};
+
+/// Single value along with its error, avoiding the allocations of a [Measure]
+/// when only one value is needed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, ScalarMeasure};
+/// let length = ScalarMeasure::new(2.0, 0.1);
+/// let times = measure!([1.2, 1.4, 1.3], 0.1);
+///
+/// let speeds = length / &times;
+/// let area = length.pow(2);
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct ScalarMeasure {
+    /// Value of the measure.
+    pub value: f64,
+    /// Error of the measure.
+    pub error: f64,
+}
+
+impl ScalarMeasure {
+    /// Constructor of the struct ScalarMeasure.
+    pub fn new<T, U>(value: T, error: U) -> ScalarMeasure
+    where
+        T: std::convert::Into<f64>,
+        U: std::convert::Into<f64>,
+    {
+        ScalarMeasure {
+            value: value.into(),
+            error: error.into(),
+        }
+    }
+    /// Returns a tuple (value, error).
+    pub fn unpack(&self) -> (f64, f64) {
+        (self.value, self.error)
+    }
+
+    // -------------- Operations ----------------
+
+    /// Aproximate the measure to the first significative figure of the error.
+    pub fn aprox(self) -> Self {
+        let (value, error) = aprox(self.value, self.error);
+        ScalarMeasure { value, error }
+    }
+    /// Aproximate the measure to the decimals indicated.
+    pub fn aprox_to(self, decimals: i32) -> Self {
+        ScalarMeasure {
+            value: round(self.value, decimals),
+            error: round(self.error, decimals),
+        }
+    }
+    /// Checks if a measure is smaller than another one even considering their
+    /// errors, that is, `x + σx < y - σy`.
+    pub fn definitely_less_than(&self, other: &ScalarMeasure) -> bool {
+        self.value + self.error < other.value - other.error
+    }
+    /// Checks if a measure is greater than another one even considering their
+    /// errors, that is, `x - σx > y + σy`.
+    pub fn definitely_greater_than(&self, other: &ScalarMeasure) -> bool {
+        other.definitely_less_than(self)
+    }
+    /// Checks if the intervals `x ± σx` and `y ± σy` of two measures overlap,
+    /// so the measures are compatible.
+    pub fn overlaps(&self, other: &ScalarMeasure) -> bool {
+        (self.value - other.value).abs() <= self.error + other.error
+    }
+    /// Calculates the discrepancy of two measures in number of standard
+    /// deviations, `|x - y| / √(σx² + σy²)`, see [Measure::compatible_with].
+    pub fn compatible_with(&self, other: &ScalarMeasure) -> f64 {
+        sigmas(self.value - other.value, self.error.hypot(other.error))
+    }
+    /// Raises a measure to any number.
+    pub fn pow<T: std::convert::Into<f64>>(&self, other: T) -> ScalarMeasure {
+        let other = other.into();
+        ScalarMeasure {
+            value: self.value.powf(other),
+            error: (other * self.value.powf(other - 1.0) * self.error).abs(),
+        }
+    }
+    /// Raises a measure to an integer power, using the error `|n·x^(n-1)|·σ`.
+    pub fn powi(&self, n: i32) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.powi(n),
+            error: (n as f64 * self.value.powi(n - 1) * self.error).abs(),
+        }
+    }
+    /// Converts grades in radians.
+    pub fn rad(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value * PI / 180.0,
+            error: self.error * PI / 180.0,
+        }
+    }
+    /// Converts radians in grades.
+    pub fn grad(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value * 180.0 / PI,
+            error: self.error * 180.0 / PI,
+        }
+    }
+    /// Returns the square root of a measure.
+    pub fn sqrt(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.sqrt(),
+            error: self.error / (2.0 * self.value.sqrt()),
+        }
+    }
+    /// Computes the absolute value of a measure.
+    pub fn abs(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.abs(),
+            error: self.error,
+        }
+    }
+    /// Computes the sine of a measure in radians.
+    pub fn sin(&self) -> ScalarMeasure {
+        let value = self.value.sin();
+        let error = if value == 1.0 || value == -1.0 {
+            ((self.value + self.error).sin() - value).abs()
+        } else {
+            (self.value.cos() * self.error).abs()
+        };
+        ScalarMeasure { value, error }
+    }
+    /// Computes the cosine of a measure in radians.
+    pub fn cos(&self) -> ScalarMeasure {
+        let value = self.value.cos();
+        let error = if value == 1.0 || value == -1.0 {
+            ((self.value + self.error).cos() - value).abs()
+        } else {
+            (self.value.sin() * self.error).abs()
+        };
+        ScalarMeasure { value, error }
+    }
+    /// Computes the tangent of a measure in radians.
+    pub fn tan(&self) -> ScalarMeasure {
+        let value = self.value.tan();
+        ScalarMeasure {
+            value,
+            error: (1.0 + value.powi(2)) * self.error,
+        }
+    }
+    /// Computes the arcsine of a measure in radians.
+    pub fn asin(&self) -> ScalarMeasure {
+        let (val, err) = self.unpack();
+        ScalarMeasure {
+            value: val.asin(),
+            error: if val != 1.0 && val != -1.0 {
+                err / (1.0 - val.powi(2)).sqrt()
+            } else {
+                ((val - err).asin() - val).abs()
+            },
+        }
+    }
+    /// Computes the arccosine of a measure in radians.
+    pub fn acos(&self) -> ScalarMeasure {
+        let (val, err) = self.unpack();
+        ScalarMeasure {
+            value: val.acos(),
+            error: if val != 1.0 && val != -1.0 {
+                err / (1.0 - val.powi(2)).sqrt()
+            } else {
+                let d = if val > 0.0 { val - err } else { val + err };
+                (d.acos() - val.acos()).abs()
+            },
+        }
+    }
+    /// Computes the arctangent of a measure in radians.
+    pub fn atan(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.atan(),
+            error: self.error / (1.0 + self.value.powi(2)),
+        }
+    }
+    /// Computes the four quadrant arctangent of two measures.
+    pub fn atan2(&self, other: &ScalarMeasure) -> ScalarMeasure {
+        let (sval, serr) = self.unpack();
+        let (oval, oerr) = other.unpack();
+        ScalarMeasure {
+            value: sval.atan2(oval),
+            error: ((oval * serr).powi(2) + (sval * oerr).powi(2)).sqrt()
+                / (sval.powi(2) + oval.powi(2)),
+        }
+    }
+    /// Returns the natural logarithm of a measure.
+    pub fn ln(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.ln(),
+            error: (1.0 / self.value).abs() * self.error,
+        }
+    }
+    /// Returns the logarithm of a measure in any base.
+    pub fn log(&self, base: f64) -> ScalarMeasure {
+        ScalarMeasure {
+            value: log(self.value, base),
+            error: (1.0 / (self.value * base.ln())).abs() * self.error,
+        }
+    }
+    /// Returns the base 10 logarithm of a measure.
+    pub fn log10(&self) -> ScalarMeasure {
+        self.log(10.0)
+    }
+    /// Returns the base 2 logarithm of a measure.
+    pub fn log2(&self) -> ScalarMeasure {
+        self.log(2.0)
+    }
+    /// Returns the exponential function of a measure.
+    pub fn exp(&self) -> ScalarMeasure {
+        ScalarMeasure {
+            value: self.value.exp(),
+            error: self.value.exp() * self.error,
+        }
+    }
+}
+
+impl Display for ScalarMeasure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ± {}", self.value, self.error)
+    }
+}
+
+impl From<ScalarMeasure> for Measure {
+    fn from(m: ScalarMeasure) -> Measure {
+        Measure::new(vec![m.value], vec![m.error], false).unwrap()
+    }
+}
+
+impl From<&ScalarMeasure> for Measure {
+    fn from(m: &ScalarMeasure) -> Measure {
+        Measure::from(*m)
+    }
+}
+
+impl From<(f64, f64)> for ScalarMeasure {
+    fn from((value, error): (f64, f64)) -> ScalarMeasure {
+        ScalarMeasure { value, error }
+    }
+}
+
+impl From<ScalarMeasure> for (f64, f64) {
+    fn from(m: ScalarMeasure) -> (f64, f64) {
+        (m.value, m.error)
+    }
+}
+
+impl TryFrom<&Measure> for ScalarMeasure {
+    type Error = MyError;
+
+    fn try_from(m: &Measure) -> Result<ScalarMeasure, MyError> {
+        match m.get(0) {
+            Some((value, error)) if m.len() == 1 => Ok(ScalarMeasure {
+                value: *value,
+                error: *error,
+            }),
+            _ => Err(MyError::NotScalar(m.len())),
+        }
+    }
+}
+
+impl TryFrom<Measure> for ScalarMeasure {
+    type Error = MyError;
+
+    fn try_from(m: Measure) -> Result<ScalarMeasure, MyError> {
+        ScalarMeasure::try_from(&m)
+    }
+}
+
+// Implementing Add, Sub, Mul, Div:
+
+// Between ScalarMeasure - ScalarMeasure:
+impl Add<ScalarMeasure> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn add(self, other: ScalarMeasure) -> Self::Output {
+        ScalarMeasure {
+            value: self.value + other.value,
+            error: (self.error.powi(2) + other.error.powi(2)).sqrt(),
+        }
+    }
+}
+
+impl Sub<ScalarMeasure> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn sub(self, other: ScalarMeasure) -> Self::Output {
+        ScalarMeasure {
+            value: self.value - other.value,
+            error: (self.error.powi(2) + other.error.powi(2)).sqrt(),
+        }
+    }
+}
+
+impl Mul<ScalarMeasure> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn mul(self, other: ScalarMeasure) -> Self::Output {
+        ScalarMeasure {
+            value: self.value * other.value,
+            error: ((other.value * self.error).powi(2) + (self.value * other.error).powi(2)).sqrt(),
+        }
+    }
+}
+
+impl Div<ScalarMeasure> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn div(self, other: ScalarMeasure) -> Self::Output {
+        ScalarMeasure {
+            value: self.value / other.value,
+            error: ((self.error / other.value).powi(2)
+                + (self.value / other.value.powi(2) * other.error).powi(2))
+            .sqrt(),
+        }
+    }
+}
+
+// Between ScalarMeasure - Number:
+impl<T: std::convert::Into<f64>> Add<T> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn add(self, num: T) -> Self::Output {
+        ScalarMeasure {
+            value: self.value + num.into(),
+            error: self.error,
+        }
+    }
+}
+
+impl<T: std::convert::Into<f64>> Sub<T> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn sub(self, num: T) -> Self::Output {
+        ScalarMeasure {
+            value: self.value - num.into(),
+            error: self.error,
+        }
+    }
+}
+
+impl<T: std::convert::Into<f64>> Mul<T> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn mul(self, num: T) -> Self::Output {
+        let num = num.into();
+        ScalarMeasure {
+            value: self.value * num,
+            error: self.error * num.abs(),
+        }
+    }
+}
+
+impl<T: std::convert::Into<f64>> Div<T> for ScalarMeasure {
+    type Output = ScalarMeasure;
+
+    fn div(self, num: T) -> Self::Output {
+        let num = num.into();
+        ScalarMeasure {
+            value: self.value / num,
+            error: self.error / num.abs(),
+        }
+    }
+}
+
+// Between Number - ScalarMeasure:
+impl_scalar_op_number!(f32);
+impl_scalar_op_number!(f64);
+impl_scalar_op_number!(u8);
+impl_scalar_op_number!(i8);
+impl_scalar_op_number!(u16);
+impl_scalar_op_number!(i16);
+impl_scalar_op_number!(u32);
+impl_scalar_op_number!(i32);
+impl_scalar_op_number!(u64);
+impl_scalar_op_number!(i64);
+impl_scalar_op_number!(u128);
+impl_scalar_op_number!(i128);
+
+// Between Measure - ScalarMeasure:
+impl_op_scalar!(Measure, ScalarMeasure);
+impl_op_scalar!(Measure, &ScalarMeasure);
+impl_op_scalar!(&Measure, ScalarMeasure);
+impl_op_scalar!(&Measure, &ScalarMeasure);
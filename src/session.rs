@@ -0,0 +1,354 @@
+//! Contains the struct Session, used to save an analysis and resume it later.
+use {
+    crate::{Dataset, Measure, Metadata, ScalarMeasure, Style, Unit},
+    std::{
+        collections::BTreeMap,
+        fs,
+        io::{Error, ErrorKind},
+        path::Path,
+    },
+};
+
+const HEADER: &str = "# FerriLab session 1";
+
+// Block of a session file, with its kind, name, first line and fields.
+struct Block {
+    kind: String,
+    name: String,
+    line: usize,
+    fields: Vec<(String, String)>,
+}
+
+/// Named measures, datasets and fit parameters of an analysis, that can be
+/// saved to a single text file and restored, so an interrupted analysis can be
+/// resumed where it was left.
+///
+/// The values, errors, units, names, styles and metadata of the measures are
+/// restored exactly. Their provenance is not saved. The names and metadata may
+/// contain any character, as the line breaks, colons and backslashes are
+/// escaped with a backslash in the file.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use ferrilab::{measure, Measure, LinearFit, Session};
+/// let time = measure!([0.2, 0.3, 0.4], 0.01);
+/// let position = measure!([2.4, 3.4, 5.1], 0.2);
+/// let (slope, intercept) = LinearFit::new(&time, &position).fit();
+///
+/// let mut session = Session::new();
+/// session.insert_measure("time", time);
+/// session.insert_fit("position", vec![slope, intercept]);
+/// session.save("analysis.session").unwrap();
+///
+/// let session = Session::load("analysis.session").unwrap();
+/// let slope = session.fit("position").unwrap()[0];
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    measures: BTreeMap<String, Measure>,
+    datasets: BTreeMap<String, Dataset>,
+    fits: BTreeMap<String, Vec<ScalarMeasure>>,
+}
+
+impl Session {
+    /// Constructs an empty Session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Stores a measure, replacing the one with the same name.
+    pub fn insert_measure(&mut self, name: impl Into<String>, measure: Measure) {
+        self.measures.insert(name.into(), measure);
+    }
+    /// Stores a dataset, replacing the one with the same name.
+    pub fn insert_dataset(&mut self, name: impl Into<String>, dataset: Dataset) {
+        self.datasets.insert(name.into(), dataset);
+    }
+    /// Stores the parameters of a fit, replacing the ones with the same name.
+    pub fn insert_fit(&mut self, name: impl Into<String>, parameters: Vec<ScalarMeasure>) {
+        self.fits.insert(name.into(), parameters);
+    }
+    /// Returns a stored measure.
+    pub fn measure(&self, name: &str) -> Option<&Measure> {
+        self.measures.get(name)
+    }
+    /// Returns a stored dataset.
+    pub fn dataset(&self, name: &str) -> Option<&Dataset> {
+        self.datasets.get(name)
+    }
+    /// Returns the stored parameters of a fit.
+    pub fn fit(&self, name: &str) -> Option<&[ScalarMeasure]> {
+        self.fits.get(name).map(|parameters| parameters.as_slice())
+    }
+    /// Checks if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.measures.is_empty() && self.datasets.is_empty() && self.fits.is_empty()
+    }
+
+    /// Writes the session to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.to_text())
+    }
+    /// Reads a session saved with [Session::save].
+    pub fn load(path: impl AsRef<Path>) -> Result<Session, Error> {
+        Session::from_text(&fs::read_to_string(path)?)
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (name, measure) in &self.measures {
+            text += &format!("[measure] {}\n", escape(name));
+            write_measure(&mut text, "", measure);
+        }
+        for (name, dataset) in &self.datasets {
+            text += &format!("[dataset] {}\n", escape(name));
+            write_measure(&mut text, "x.", dataset.x());
+            write_measure(&mut text, "y.", dataset.y());
+        }
+        for (name, parameters) in &self.fits {
+            text += &format!("[fit] {}\n", escape(name));
+            text += &format!("value: {}\n", floats(parameters.iter().map(|p| p.value)));
+            text += &format!("error: {}\n", floats(parameters.iter().map(|p| p.error)));
+        }
+        text
+    }
+
+    fn from_text(text: &str) -> Result<Session, Error> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line.trim()) != Some(HEADER) {
+            return Err(invalid(1, "not a FerriLab session"));
+        }
+        let mut session = Session::new();
+        let mut block: Option<Block> = None;
+        for (number, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                if let Some(block) = block.take() {
+                    session.add_block(block)?;
+                }
+                let (kind, name) = header
+                    .split_once("] ")
+                    .ok_or_else(|| invalid(number + 1, "expected \"[kind] name\""))?;
+                block = Some(Block {
+                    kind: kind.to_string(),
+                    name: unescape(name),
+                    line: number + 1,
+                    fields: Vec::new(),
+                });
+            } else {
+                let (key, value) = split_field(line)
+                    .ok_or_else(|| invalid(number + 1, "expected \"key: value\""))?;
+                block
+                    .as_mut()
+                    .ok_or_else(|| invalid(number + 1, "field outside of a block"))?
+                    .fields
+                    .push((unescape(key.trim()), unescape(value.trim())));
+            }
+        }
+        if let Some(block) = block {
+            session.add_block(block)?;
+        }
+        Ok(session)
+    }
+
+    fn add_block(&mut self, block: Block) -> Result<(), Error> {
+        let Block {
+            kind,
+            name,
+            line,
+            fields,
+        } = block;
+        match kind.as_str() {
+            "measure" => {
+                let measure = read_measure(&fields, "", line)?;
+                self.insert_measure(name, measure);
+            }
+            "dataset" => {
+                let (x, y) = (
+                    read_measure(&fields, "x.", line)?,
+                    read_measure(&fields, "y.", line)?,
+                );
+                if x.len() != y.len() {
+                    return Err(invalid(line, "dataset measures of diferent lengths"));
+                }
+                self.insert_dataset(name, Dataset::new(x, y));
+            }
+            "fit" => {
+                let value = read_floats(&fields, "value", line)?;
+                let error = read_floats(&fields, "error", line)?;
+                if value.len() != error.len() {
+                    return Err(invalid(line, "diferent number of values and errors"));
+                }
+                self.insert_fit(
+                    name,
+                    value
+                        .into_iter()
+                        .zip(error)
+                        .map(ScalarMeasure::from)
+                        .collect(),
+                );
+            }
+            _ => return Err(invalid(line, &format!("unknown block kind \"{}\"", kind))),
+        }
+        Ok(())
+    }
+}
+
+fn invalid(line: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("Invalid session file, line {}: {}.", line, message),
+    )
+}
+
+// Escapes the characters that would break the lines or the fields of a file.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ':' => escaped.push_str("\\:"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+// Splits a line "key: value" at the first colon that is not escaped.
+fn split_field(line: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            ':' if !escaped => return Some((&line[..i], &line[i + 1..])),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+// Writes the numbers with the shortest representation that reads back exactly.
+fn floats(numbers: impl Iterator<Item = f64>) -> String {
+    numbers
+        .map(|num| format!("{:?}", num))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn write_measure(text: &mut String, prefix: &str, measure: &Measure) {
+    let (value, error) = measure.unpack();
+    *text += &format!("{}value: {}\n", prefix, floats(value.iter().copied()));
+    *text += &format!("{}error: {}\n", prefix, floats(error.iter().copied()));
+    let unit = measure.unit();
+    *text += &format!(
+        "{}unit: {}{}\n",
+        prefix,
+        floats(unit.dimension().iter().copied()),
+        unit.custom_symbol()
+            .map(|symbol| format!(" {}", escape(symbol)))
+            .unwrap_or_default()
+    );
+    *text += &format!("{}style: {:?}\n", prefix, measure.style());
+    if let Some(name) = measure.name() {
+        *text += &format!("{}name: {}\n", prefix, escape(name));
+    }
+    for (key, value) in measure.metadata().iter() {
+        *text += &format!("{}meta {}: {}\n", prefix, escape(key), escape(value));
+    }
+}
+
+fn field<'a>(fields: &'a [(String, String)], key: &str, line: usize) -> Result<&'a str, Error> {
+    fields
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+        .ok_or_else(|| invalid(line, &format!("missing field \"{}\"", key)))
+}
+
+fn read_floats(fields: &[(String, String)], key: &str, line: usize) -> Result<Vec<f64>, Error> {
+    field(fields, key, line)?
+        .split_whitespace()
+        .map(|num| {
+            num.parse()
+                .map_err(|_| invalid(line, &format!("\"{}\" is not a number", num)))
+        })
+        .collect()
+}
+
+fn read_measure(fields: &[(String, String)], prefix: &str, line: usize) -> Result<Measure, Error> {
+    let value = read_floats(fields, &format!("{}value", prefix), line)?;
+    let error = read_floats(fields, &format!("{}error", prefix), line)?;
+    if value.len() != error.len() {
+        return Err(invalid(line, "diferent number of values and errors"));
+    }
+    let unit = field(fields, &format!("{}unit", prefix), line)?;
+    let mut parts = unit.splitn(8, ' ');
+    let mut dimension = [0.0; 7];
+    for exponent in dimension.iter_mut() {
+        *exponent = parts
+            .next()
+            .and_then(|exp| exp.parse().ok())
+            .ok_or_else(|| invalid(line, "expected the 7 exponents of the unit"))?;
+    }
+    let unit = match parts.next() {
+        Some(symbol) => Unit::new(symbol, dimension),
+        None => Unit::from_dimension(dimension),
+    };
+    let style = match field(fields, &format!("{}style", prefix), line)? {
+        "List" => Style::List,
+        "PM" => Style::PM,
+        "Table" => Style::Table,
+        "LatexTable" => Style::LatexTable,
+        "TypstTable" => Style::TypstTable,
+        "SI" => Style::SI,
+        "Aligned" => Style::Aligned,
+        "Scientific" => Style::Scientific,
+        "LatexScientific" => Style::LatexScientific,
+        "TypstScientific" => Style::TypstScientific,
+        "Compact" => Style::Compact,
+        "LatexCompact" => Style::LatexCompact,
+        "TypstCompact" => Style::TypstCompact,
+        style => return Err(invalid(line, &format!("unknown style \"{}\"", style))),
+    };
+    let metadata = fields
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(&format!("{}meta ", prefix))
+                .map(|key| (key, value))
+        })
+        .fold(Metadata::new(), |metadata, (key, value)| {
+            metadata.set(key, value)
+        });
+
+    let mut measure = Measure::new(value, error, false)
+        .unwrap()
+        .with_unit(unit)
+        .with_metadata(metadata)
+        .change_style(style);
+    if let Ok(name) = field(fields, &format!("{}name", prefix), line) {
+        measure = measure.with_name(name);
+    }
+    Ok(measure)
+}
@@ -0,0 +1,202 @@
+//! Small interactive shell to load, operate, fit, tabulate and plot measures
+//! without writing a program, used by the example `analysis-shell`. Requires
+//! the feature `shell`.
+//!
+//! Every line is a command, and the measures are stored by name:
+//!
+//! ```text
+//! > load examples/data.txt
+//! Loaded c0, c1.
+//! > let v = c1 / c0
+//! > fit c1 c0
+//! > table c0 c1 v
+//! > plot c0 c1
+//! > show
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use ferrilab::{measure, Measure, shell::Shell};
+//! let mut shell = Shell::new();
+//! shell.insert("t", measure!([1, 2, 3], 0.1));
+//! shell.execute("let x = t * 2").unwrap();
+//!
+//! assert_eq!(shell.measure("x").unwrap().value(), &vec![2.0, 4.0, 6.0]);
+//! ```
+use {
+    crate::{save, show, LinearFit, Measure, Plot, PlotError, Reader, Scatter, Table},
+    std::{
+        collections::BTreeMap,
+        io::{self, BufRead, Write},
+    },
+};
+
+const HELP: &str = "\
+Commands:
+  load <file> [headers]          reads the measures of a file as c0, c1, ...
+  let <name> = <a> [<op> <b>]    operates measures or numbers with + - * /
+  print <name>                   prints a measure
+  list                           lists the stored measures
+  fit <y> <x>                    linear fit of y over x
+  table <name>...                typst table of the measures
+  plot <x> <y> [fit]             scatter plot, with the linear fit if asked
+  save <file>                    saves the figure
+  show                           shows the figure
+  help                           prints this message
+  quit                           exits the shell";
+
+/// Error of a command of the [Shell].
+#[derive(Debug, thiserror::Error)]
+pub enum ShellError {
+    #[error("Unknown command \"{0}\", try \"help\".")]
+    UnknownCommand(String),
+    #[error("Unknown measure \"{0}\".")]
+    UnknownMeasure(String),
+    #[error("Usage: {0}")]
+    Usage(&'static str),
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Plot(#[from] PlotError),
+}
+
+/// Interactive shell storing measures by name.
+#[derive(Debug, Clone, Default)]
+pub struct Shell {
+    measures: BTreeMap<String, Measure>,
+}
+
+impl Shell {
+    /// Constructs a new Shell without measures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Stores a measure, replacing the one with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, measure: Measure) {
+        self.measures.insert(name.into(), measure);
+    }
+    /// Returns a stored measure.
+    pub fn measure(&self, name: &str) -> Option<&Measure> {
+        self.measures.get(name)
+    }
+
+    /// Reads commands line by line until the input ends or the command `quit`,
+    /// writing their output and errors.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            if matches!(line.trim(), "quit" | "exit") {
+                break;
+            }
+            match self.execute(&line) {
+                Ok(text) if text.is_empty() => (),
+                Ok(text) => writeln!(output, "{}", text)?,
+                Err(err) => writeln!(output, "{}", err)?,
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Executes a command, returning the text to print.
+    pub fn execute(&mut self, line: &str) -> Result<String, ShellError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_string()),
+            ["load", file, rest @ ..] => {
+                let headers = match rest {
+                    [] => 0,
+                    [headers] => headers
+                        .parse()
+                        .map_err(|_| ShellError::Usage("load <file> [headers]"))?,
+                    _ => return Err(ShellError::Usage("load <file> [headers]")),
+                };
+                let reader = Reader::new(file, headers);
+                reader.read_metadata()?;
+                let names: Vec<String> = reader
+                    .read_to_measures()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, measure)| {
+                        let name = format!("c{}", i);
+                        self.insert(name.clone(), measure);
+                        name
+                    })
+                    .collect();
+                Ok(format!("Loaded {}.", names.join(", ")))
+            }
+            ["let", name, "=", a] => {
+                let measure = self.operand(a)?;
+                self.insert(*name, measure);
+                Ok(String::new())
+            }
+            ["let", name, "=", a, op, b] => {
+                let (a, b) = (self.operand(a)?, self.operand(b)?);
+                let measure = match *op {
+                    "+" => a + b,
+                    "-" => a - b,
+                    "*" => a * b,
+                    "/" => a / b,
+                    _ => return Err(ShellError::Usage("let <name> = <a> [<op> <b>]")),
+                };
+                self.insert(*name, measure);
+                Ok(String::new())
+            }
+            ["let", ..] => Err(ShellError::Usage("let <name> = <a> [<op> <b>]")),
+            ["print", name] => Ok(self.get(name)?.to_string()),
+            ["list"] => Ok(self
+                .measures
+                .iter()
+                .map(|(name, measure)| format!("{} ({} values)", name, measure.len()))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            ["fit", y, x] => {
+                let (slope, intercept) = LinearFit::new(self.get(x)?, self.get(y)?).fit();
+                Ok(format!("slope = {}\nintercept = {}", slope, intercept))
+            }
+            ["table", names @ ..] if !names.is_empty() => {
+                let measures = names
+                    .iter()
+                    .map(|name| self.get(name).cloned())
+                    .collect::<Result<Vec<Measure>, ShellError>>()?;
+                Ok(Table::new(measures, names.to_vec()).typst())
+            }
+            ["plot", x, y, rest @ ..] => {
+                let (x, y) = (self.get(x)?, self.get(y)?);
+                Scatter::from_measures(x, y).scatter()?;
+                match rest {
+                    [] => (),
+                    ["fit"] => {
+                        let (slope, intercept) = LinearFit::new(x, y).fit();
+                        Plot::new(x, x * slope + intercept).color("black").plot()?;
+                    }
+                    _ => return Err(ShellError::Usage("plot <x> <y> [fit]")),
+                }
+                Ok(String::new())
+            }
+            ["save", file] => Ok(format!("Saved {}.", save(file)?.path.display())),
+            ["show"] => {
+                show()?;
+                Ok(String::new())
+            }
+            [command, ..] => Err(ShellError::UnknownCommand(command.to_string())),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<&Measure, ShellError> {
+        self.measure(name)
+            .ok_or_else(|| ShellError::UnknownMeasure(name.to_string()))
+    }
+    // A stored measure or a number.
+    fn operand(&self, word: &str) -> Result<Measure, ShellError> {
+        match word.parse::<f64>() {
+            Ok(num) => Ok(Measure::new(vec![num], vec![0.0], false).unwrap()),
+            Err(_) => self.get(word).cloned(),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Special functions needed to evaluate the distributions used on statistics.
+
+/// Natural logarithm of the gamma function, using the Lanczos aproximation.
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000000000190015, |acc, (i, coef)| {
+            acc + coef / (x + 1.0 + i as f64)
+        });
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // The continued fraction converges faster on this side of the distribution.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function by the modified Lentz's method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..300 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // Even step.
+        let numerator = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        result *= d * c;
+
+        // Odd step.
+        let numerator = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        result *= delta;
+
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    result
+}
+
+/// Two-sided p-value of the Student's t distribution.
+pub fn student_t_two_sided(t: f64, dof: f64) -> f64 {
+    beta_inc(dof / 2.0, 0.5, dof / (dof + t.powi(2)))
+}
+
+/// Probability of the F distribution being greater than the statistic.
+pub fn f_upper_tail(f: f64, dof1: f64, dof2: f64) -> f64 {
+    if f <= 0.0 {
+        return 1.0;
+    }
+    beta_inc(dof2 / 2.0, dof1 / 2.0, dof2 / (dof2 + dof1 * f))
+}
+
+/// Probability of the χ² distribution being greater than the statistic.
+pub fn chi_squared_upper_tail(chi2: f64, dof: f64) -> f64 {
+    1.0 - gamma_inc(dof / 2.0, chi2 / 2.0)
+}
+
+/// Regularized lower incomplete gamma function P(a, x).
+pub fn gamma_inc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let front = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series representation.
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..500 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        front * sum
+    } else {
+        // Continued fraction representation by the modified Lentz's method.
+        const TINY: f64 = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut result = d;
+        for n in 1..500 {
+            let n = n as f64;
+            let numerator = -n * (n - a);
+            b += 2.0;
+            d = numerator * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            result *= delta;
+            if (delta - 1.0).abs() < 1e-16 {
+                break;
+            }
+        }
+        1.0 - front * result
+    }
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn normal_cdf(z: f64) -> f64 {
+    let half_erf = 0.5 * gamma_inc(0.5, z.powi(2) / 2.0);
+    if z >= 0.0 {
+        0.5 + half_erf
+    } else {
+        0.5 - half_erf
+    }
+}
+
+/// Inverse of the cumulative distribution function of the standard normal
+/// distribution, using Acklam's aproximation refined with a Newton step.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let x = if p < 0.02425 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.02425 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+    let density = (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    x - (normal_cdf(x) - p) / density
+}
+
+/// Survival function of the Kolmogorov distribution.
+pub fn kolmogorov_upper_tail(lambda: f64) -> f64 {
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..100 {
+        let term = sign * (-2.0 * (j as f64 * lambda).powi(2)).exp();
+        sum += term;
+        if term.abs() < 1e-16 {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ln_gamma_test() {
+        assert!((ln_gamma(1.0)).abs() < 1e-10);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-10);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn distributions_test() {
+        // Critical values from the usual statistical tables.
+        assert!((student_t_two_sided(2.228, 10.0) - 0.05).abs() < 1e-4);
+        assert!((student_t_two_sided(1.96, 1e6) - 0.05).abs() < 1e-4);
+        assert!((f_upper_tail(3.326, 5.0, 10.0) - 0.05).abs() < 1e-4);
+        assert!((beta_inc(2.0, 3.0, 0.4) - 0.5248).abs() < 1e-10);
+        assert!((normal_cdf(1.959964) - 0.975).abs() < 1e-7);
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-6);
+        assert!((normal_quantile(normal_cdf(-3.2)) + 3.2).abs() < 1e-10);
+        assert!((gamma_inc(3.0, 2.0) - 0.3233235838169366).abs() < 1e-12);
+        assert!((kolmogorov_upper_tail(1.358) - 0.05).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,608 @@
+//! Statistical tests and tools to compare datasets stored as measures.
+use crate::{
+    special::{
+        f_upper_tail, kolmogorov_upper_tail, normal_cdf, normal_quantile, student_t_two_sided,
+    },
+    Measure, ScalarMeasure, TaggedMeasure,
+};
+
+/// Diferent kinds of t-test available.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TTestKind {
+    /// Both measures are the same samples measured twice, so they are compared
+    /// value by value.
+    Paired,
+    /// Independent samples assuming both have the same variance.
+    Pooled,
+    /// Independent samples without assuming equal variances.
+    Welch,
+}
+
+/// Result of a statistical test.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TestResult {
+    /// Value of the statistic of the test.
+    pub statistic: f64,
+    /// Degrees of freedom of the distribution, the second one is only used by
+    /// distributions with two of them like the F distribution.
+    pub dof: (f64, Option<f64>),
+    /// Probability of obtaining a statistic at least as extreme assuming the
+    /// null hypothesis is true.
+    pub p_value: f64,
+}
+
+impl TestResult {
+    /// Checks if the null hypothesis is rejected with the given significance level.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Two-sided t-test comparing the means of the values of two measures.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, stats::{t_test, TTestKind}};
+/// let run1 = measure!([9.79, 9.81, 9.80, 9.83, 9.78]);
+/// let run2 = measure!([9.92, 9.95, 9.90, 9.94, 9.93]);
+///
+/// assert!(t_test(&run1, &run2, TTestKind::Welch).is_significant(0.05));
+/// ```
+pub fn t_test(a: &Measure, b: &Measure, kind: TTestKind) -> TestResult {
+    let (statistic, dof) = match kind {
+        TTestKind::Paired => {
+            assert_eq!(
+                a.len(),
+                b.len(),
+                "Paired t-test requires measures of the same length, obtained {} and {}.",
+                a.len(),
+                b.len()
+            );
+            let differences: Measure = a
+                .value()
+                .iter()
+                .zip(b.value().iter())
+                .map(|(aval, bval)| (aval - bval, 0.0))
+                .collect();
+            (
+                differences.mean() / differences.standard_error(),
+                a.len() as f64 - 1.0,
+            )
+        }
+        TTestKind::Pooled => {
+            let (na, nb) = (a.len() as f64, b.len() as f64);
+            let dof = na + nb - 2.0;
+            let pooled_variance = ((na - 1.0) * a.standard_deviation().powi(2)
+                + (nb - 1.0) * b.standard_deviation().powi(2))
+                / dof;
+            (
+                (a.mean() - b.mean()) / (pooled_variance * (1.0 / na + 1.0 / nb)).sqrt(),
+                dof,
+            )
+        }
+        TTestKind::Welch => {
+            let (na, nb) = (a.len() as f64, b.len() as f64);
+            let va = a.standard_deviation().powi(2) / na;
+            let vb = b.standard_deviation().powi(2) / nb;
+            (
+                (a.mean() - b.mean()) / (va + vb).sqrt(),
+                (va + vb).powi(2) / (va.powi(2) / (na - 1.0) + vb.powi(2) / (nb - 1.0)),
+            )
+        }
+    };
+
+    TestResult {
+        statistic,
+        dof: (dof, None),
+        p_value: student_t_two_sided(statistic, dof),
+    }
+}
+
+/// Two-sided F-test comparing the variances of the values of two measures.
+pub fn f_test(a: &Measure, b: &Measure) -> TestResult {
+    let statistic = a.standard_deviation().powi(2) / b.standard_deviation().powi(2);
+    let dof1 = a.len() as f64 - 1.0;
+    let dof2 = b.len() as f64 - 1.0;
+    let upper = f_upper_tail(statistic, dof1, dof2);
+
+    TestResult {
+        statistic,
+        dof: (dof1, Some(dof2)),
+        p_value: (2.0 * upper.min(1.0 - upper)).min(1.0),
+    }
+}
+
+/// Result of a one-way analysis of variance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anova {
+    /// F-test comparing the variance between groups with the variance within them.
+    pub test: TestResult,
+    /// Mean of each group along with its standard error.
+    pub group_means: Vec<ScalarMeasure>,
+}
+
+/// One-way analysis of variance between the values of several measures, each
+/// one being a group of replicated measurements under a certain condition.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, stats::anova};
+/// let cold = measure!([1.02, 0.98, 1.01, 0.99]);
+/// let warm = measure!([1.05, 1.07, 1.04, 1.06]);
+/// let hot = measure!([1.12, 1.10, 1.13, 1.11]);
+///
+/// let result = anova(&[cold, warm, hot]);
+/// assert!(result.test.is_significant(0.01));
+/// ```
+pub fn anova(groups: &[Measure]) -> Anova {
+    assert!(
+        groups.len() >= 2,
+        "ANOVA requires at least two groups, obtained {}.",
+        groups.len()
+    );
+    assert!(
+        groups.iter().all(|group| !group.is_empty()),
+        "ANOVA requires at least one value in every group."
+    );
+    let total_len: usize = groups.iter().map(|group| group.len()).sum();
+    let means: Vec<f64> = groups.iter().map(|group| group.mean()).collect();
+    let grand_mean = groups
+        .iter()
+        .flat_map(|group| group.value().iter())
+        .sum::<f64>()
+        / total_len as f64;
+
+    let ss_between: f64 = groups
+        .iter()
+        .zip(&means)
+        .map(|(group, mean)| group.len() as f64 * (mean - grand_mean).powi(2))
+        .sum();
+    let ss_within: f64 = groups
+        .iter()
+        .zip(&means)
+        .map(|(group, mean)| {
+            group
+                .value()
+                .iter()
+                .map(|val| (val - mean).powi(2))
+                .sum::<f64>()
+        })
+        .sum();
+
+    let dof_between = (groups.len() - 1) as f64;
+    let dof_within = (total_len - groups.len()) as f64;
+    let statistic = (ss_between / dof_between) / (ss_within / dof_within);
+
+    Anova {
+        test: TestResult {
+            statistic,
+            dof: (dof_between, Some(dof_within)),
+            p_value: f_upper_tail(statistic, dof_between, dof_within),
+        },
+        group_means: groups
+            .iter()
+            .zip(means)
+            .map(|(group, mean)| ScalarMeasure::new(mean, group.standard_error()))
+            .collect(),
+    }
+}
+
+/// Kolmogorov-Smirnov test comparing the values of a measure with any
+/// cumulative distribution function.
+pub fn ks_test<F: Fn(f64) -> f64>(measure: &Measure, cdf: F) -> TestResult {
+    let n = measure.len() as f64;
+    let mut sorted = measure.value().clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let statistic = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, val)| {
+            let f = cdf(*val);
+            ((i as f64 + 1.0) / n - f).max(f - i as f64 / n)
+        })
+        .fold(0.0, f64::max);
+    let lambda = (n.sqrt() + 0.12 + 0.11 / n.sqrt()) * statistic;
+
+    TestResult {
+        statistic,
+        dof: (n, None),
+        p_value: kolmogorov_upper_tail(lambda),
+    }
+}
+
+/// Kolmogorov-Smirnov test checking if the values of a measure follow a normal
+/// distribution with their mean and standard deviation.
+///
+/// As the parameters are estimated from the same data the p-value is
+/// conservative, prefer [shapiro_wilk] for small samples.
+pub fn ks_normality(measure: &Measure) -> TestResult {
+    let mean = measure.mean();
+    let deviation = measure.standard_deviation();
+    ks_test(measure, |val| normal_cdf((val - mean) / deviation))
+}
+
+/// Shapiro-Wilk test checking if the values of a measure follow a normal
+/// distribution, using Royston's aproximation for the coefficients and the
+/// p-value. Requires at least 3 values.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, stats::shapiro_wilk};
+/// let periods = measure!([2.01, 1.98, 2.03, 1.99, 2.00, 2.02, 1.97, 2.00, 2.01, 1.99]);
+///
+/// assert!(!shapiro_wilk(&periods).is_significant(0.05));
+/// ```
+pub fn shapiro_wilk(measure: &Measure) -> TestResult {
+    let n = measure.len();
+    assert!(
+        n >= 3,
+        "Shapiro-Wilk test requires at least 3 values, obtained {}.",
+        n
+    );
+    let nf = n as f64;
+    let mut sorted = measure.value().clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let coefficients: Vec<f64> = if n == 3 {
+        vec![-0.5_f64.sqrt(), 0.0, 0.5_f64.sqrt()]
+    } else {
+        let m: Vec<f64> = (1..=n)
+            .map(|i| normal_quantile((i as f64 - 0.375) / (nf + 0.25)))
+            .collect();
+        let mm: f64 = m.iter().map(|val| val.powi(2)).sum();
+        let u = 1.0 / nf.sqrt();
+        let polynomial = |c: f64, coefs: [f64; 5]| {
+            c + coefs
+                .iter()
+                .enumerate()
+                .map(|(i, coef)| coef * u.powi(i as i32 + 1))
+                .sum::<f64>()
+        };
+        let an = polynomial(
+            m[n - 1] / mm.sqrt(),
+            [0.221157, -0.147981, -2.071190, 4.434685, -2.706056],
+        );
+        let mut a: Vec<f64>;
+        if n > 5 {
+            let an1 = polynomial(
+                m[n - 2] / mm.sqrt(),
+                [0.042981, -0.293762, -1.752461, 5.682633, -3.582633],
+            );
+            let phi = (mm - 2.0 * m[n - 1].powi(2) - 2.0 * m[n - 2].powi(2))
+                / (1.0 - 2.0 * an.powi(2) - 2.0 * an1.powi(2));
+            a = m.iter().map(|val| val / phi.sqrt()).collect();
+            a[n - 2] = an1;
+            a[1] = -an1;
+        } else {
+            let phi = (mm - 2.0 * m[n - 1].powi(2)) / (1.0 - 2.0 * an.powi(2));
+            a = m.iter().map(|val| val / phi.sqrt()).collect();
+        }
+        a[n - 1] = an;
+        a[0] = -an;
+        a
+    };
+
+    let mean = measure.mean();
+    let statistic = (coefficients
+        .iter()
+        .zip(sorted.iter())
+        .map(|(a, x)| a * x)
+        .sum::<f64>())
+    .powi(2)
+        / sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+
+    let p_value = if n == 3 {
+        (6.0 / std::f64::consts::PI * (statistic.sqrt().asin() - 0.75_f64.sqrt().asin()))
+            .clamp(0.0, 1.0)
+    } else if n <= 11 {
+        let gamma = 0.459 * nf - 2.273;
+        let mu = 0.5440 - 0.39978 * nf + 0.025054 * nf.powi(2) - 0.0006714 * nf.powi(3);
+        let sigma = (1.3822 - 0.77857 * nf + 0.062767 * nf.powi(2) - 0.0020322 * nf.powi(3)).exp();
+        1.0 - normal_cdf((-(gamma - (1.0 - statistic).ln()).ln() - mu) / sigma)
+    } else {
+        let ln_n = nf.ln();
+        let mu = -1.5861 - 0.31082 * ln_n - 0.083751 * ln_n.powi(2) + 0.0038915 * ln_n.powi(3);
+        let sigma = (-0.4803 - 0.082676 * ln_n + 0.0030302 * ln_n.powi(2)).exp();
+        1.0 - normal_cdf(((1.0 - statistic).ln() - mu) / sigma)
+    };
+
+    TestResult {
+        statistic,
+        dof: (nf, None),
+        p_value,
+    }
+}
+
+/// Normalized cross-correlation between the values of two measures of the same
+/// length, as tuples (lag, coefficient) for every lag from -(n - 1) to n - 1.
+///
+/// A positive lag means that `b` is delayed with respect to `a`, that is
+/// `b[i + lag]` is compared with `a[i]`. Coefficients are normalized with the
+/// whole series, so lags with less overlap are penalized. If any of the series
+/// is constant the correlation is undefined and every coefficient is NaN.
+pub fn cross_correlate(a: &Measure, b: &Measure) -> Vec<(isize, f64)> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Measures lengths must be equals, obtained {} and {}.",
+        a.len(),
+        b.len()
+    );
+    let n = a.len() as isize;
+    let (amean, bmean) = (a.mean(), b.mean());
+    let norm = (a
+        .value()
+        .iter()
+        .map(|val| (val - amean).powi(2))
+        .sum::<f64>()
+        * b.value()
+            .iter()
+            .map(|val| (val - bmean).powi(2))
+            .sum::<f64>())
+    .sqrt();
+
+    (-(n - 1)..n)
+        .map(|lag| {
+            let sum: f64 = (0.max(-lag)..n.min(n - lag))
+                .map(|i| (a.value()[i as usize] - amean) * (b.value()[(i + lag) as usize] - bmean))
+                .sum();
+            (lag, if norm > 0.0 { sum / norm } else { f64::NAN })
+        })
+        .collect()
+}
+
+/// Estimates the lag, in number of samples, by which `b` is delayed with
+/// respect to `a`, so two instruments' series can be synchronized.
+///
+/// The peak of the [cross_correlate] is refined with a parabola through its
+/// neighbours, and the error is obtained from its curvature ρ'' and height ρ
+/// as sqrt((1 - ρ²) / (n |ρ''|)). Multiply by the sampling period to obtain it
+/// in time units. If any of the series is constant there is no peak and both
+/// the lag and its error are NaN.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{Measure, stats::estimate_lag};
+/// let a: Measure = (0..50).map(|i| ((i as f64 / 4.0).sin(), 0.01)).collect();
+/// let b: Measure = (0..50).map(|i| (((i - 3) as f64 / 4.0).sin(), 0.01)).collect();
+///
+/// assert_eq!(estimate_lag(&a, &b).value.round(), 3.0);
+/// ```
+pub fn estimate_lag(a: &Measure, b: &Measure) -> ScalarMeasure {
+    let correlation = cross_correlate(a, b);
+    assert!(
+        !correlation.is_empty(),
+        "Cross-correlation requires non-empty measures."
+    );
+    let Some(peak) = correlation
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, c))| !c.is_nan())
+        .max_by(|(_, (_, c1)), (_, (_, c2))| c1.total_cmp(c2))
+        .map(|(i, _)| i)
+    else {
+        return ScalarMeasure::new(f64::NAN, f64::NAN);
+    };
+    let (lag, height) = correlation[peak];
+
+    if peak == 0 || peak == correlation.len() - 1 {
+        return ScalarMeasure::new(lag as f64, 0.5);
+    }
+    let (before, after) = (correlation[peak - 1].1, correlation[peak + 1].1);
+    let curvature = before - 2.0 * height + after;
+    if curvature >= 0.0 {
+        return ScalarMeasure::new(lag as f64, 0.5);
+    }
+    let shift = 0.5 * (before - after) / curvature;
+
+    ScalarMeasure::new(
+        lag as f64 + shift,
+        ((1.0 - height.powi(2)).max(0.0) / (a.len() as f64 * curvature.abs())).sqrt(),
+    )
+}
+
+/// Weighted average of independent results of the same magnitude.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeightedMean {
+    /// Average of the results weighted by the inverse of their variances.
+    pub mean: f64,
+    /// Error of the mean propagated from the errors of the results.
+    pub internal_error: f64,
+    /// Error of the mean estimated from the scatter of the results, NaN for a
+    /// single result.
+    pub external_error: f64,
+    /// Chi-squared of the results around the mean divided by `n - 1`, the
+    /// squared ratio between the external and internal errors. Values much
+    /// greater than 1 mean the errors of the results are underestimated.
+    pub reduced_chi_squared: f64,
+}
+
+impl WeightedMean {
+    /// Mean with the largest of the internal and external errors.
+    pub fn estimation(&self) -> ScalarMeasure {
+        ScalarMeasure::new(self.mean, self.internal_error.max(self.external_error))
+    }
+}
+
+/// Combines the results of several independent experiments with the inverse
+/// variance weighted average. Panics if there are no results or some of them
+/// have no error.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{ScalarMeasure, stats::combine};
+/// let runs = [
+///     ScalarMeasure::new(9.78, 0.02),
+///     ScalarMeasure::new(9.83, 0.04),
+///     ScalarMeasure::new(9.81, 0.03),
+/// ];
+///
+/// let g = combine(&runs);
+/// assert!(g.reduced_chi_squared < 2.0);
+/// let g = g.estimation();
+/// ```
+pub fn combine(results: &[ScalarMeasure]) -> WeightedMean {
+    assert!(
+        !results.is_empty(),
+        "Can't combine an empty list of results."
+    );
+    assert!(
+        results.iter().all(|result| result.error > 0.0),
+        "Every result must have a positive error to be weighted."
+    );
+    let weights: Vec<f64> = results.iter().map(|result| result.error.powi(-2)).collect();
+    let total: f64 = weights.iter().sum();
+    let mean = results
+        .iter()
+        .zip(&weights)
+        .map(|(result, weight)| weight * result.value)
+        .sum::<f64>()
+        / total;
+    let chi_squared: f64 = results
+        .iter()
+        .zip(&weights)
+        .map(|(result, weight)| weight * (result.value - mean).powi(2))
+        .sum();
+    let dof = (results.len() - 1) as f64;
+
+    WeightedMean {
+        mean,
+        internal_error: total.sqrt().recip(),
+        external_error: (chi_squared / (dof * total)).sqrt(),
+        reduced_chi_squared: chi_squared / dof,
+    }
+}
+
+/// Amount an input is shifted in a [sensitivity_scan].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Shift {
+    /// Each value is shifted by its systematic error.
+    Systematic,
+    /// Every value is shifted by the same amount.
+    Absolute(f64),
+}
+
+/// Shifts induced in a result by one of the inputs of a [sensitivity_scan].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceShift {
+    /// Index of the input shifted.
+    pub source: usize,
+    /// Change of each value of the result when the input is shifted upwards.
+    pub up: Vec<f64>,
+    /// Change of each value of the result when the input is shifted downwards.
+    pub down: Vec<f64>,
+}
+
+impl SourceShift {
+    /// Largest change of each value of the result in both directions, taken as
+    /// the systematic error due to this input.
+    pub fn symmetric(&self) -> Vec<f64> {
+        self.up
+            .iter()
+            .zip(&self.down)
+            .map(|(up, down)| up.abs().max(down.abs()))
+            .collect()
+    }
+}
+
+/// Result of a [sensitivity_scan].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityScan {
+    /// Result computed with the inputs without shifting.
+    pub nominal: Measure,
+    /// Shifts induced by each input, in the same order as the inputs.
+    pub sources: Vec<SourceShift>,
+}
+
+impl SensitivityScan {
+    /// Systematic error of each value of the result, adding in quadrature the
+    /// shifts induced by all the inputs, assuming they are independent.
+    pub fn total(&self) -> Vec<f64> {
+        (0..self.nominal.len())
+            .map(|i| {
+                self.sources
+                    .iter()
+                    .map(|source| source.symmetric()[i].powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect()
+    }
+}
+
+/// Estimates the systematic error of a result by shifting each input upwards
+/// and downwards, one at a time, and computing the result again. The function
+/// receives the inputs with their total errors, and there must be one shift for
+/// each input.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, TaggedMeasure, stats::{sensitivity_scan, Shift}};
+/// let voltage = TaggedMeasure::from_errors(vec![10.0], vec![0.1], vec![0.2]).unwrap();
+/// let current = TaggedMeasure::from_errors(vec![2.0], vec![0.01], vec![0.0]).unwrap();
+///
+/// let scan = sensitivity_scan(
+///     &[&voltage, &current],
+///     &[Shift::Systematic, Shift::Absolute(0.02)],
+///     |inputs| &inputs[0] / &inputs[1],
+/// );
+/// assert_eq!(scan.nominal.value(), &vec![5.0]);
+/// assert!((scan.sources[0].symmetric()[0] - 0.1).abs() < 1e-12);
+/// ```
+pub fn sensitivity_scan<F>(
+    inputs: &[&TaggedMeasure],
+    shifts: &[Shift],
+    function: F,
+) -> SensitivityScan
+where
+    F: Fn(&[Measure]) -> Measure,
+{
+    assert_eq!(
+        inputs.len(),
+        shifts.len(),
+        "There must be one shift for each input, obtained {} and {}.",
+        inputs.len(),
+        shifts.len()
+    );
+    let nominal_inputs: Vec<Measure> = inputs.iter().map(|input| input.total()).collect();
+    let nominal = function(&nominal_inputs);
+    let induced = |shifted: &[Measure]| -> Vec<f64> {
+        function(shifted)
+            .value()
+            .iter()
+            .zip(nominal.value())
+            .map(|(shifted, nominal)| shifted - nominal)
+            .collect()
+    };
+    let sources = inputs
+        .iter()
+        .zip(shifts)
+        .enumerate()
+        .map(|(source, (input, shift))| {
+            let amounts: Vec<f64> = match shift {
+                Shift::Systematic => input.systematic().clone(),
+                Shift::Absolute(amount) => vec![*amount; input.len()],
+            };
+            let mut shifted = nominal_inputs.clone();
+            let mut scan = |sign: f64| {
+                for ((val, _), amount) in (&mut shifted[source]).into_iter().zip(&amounts) {
+                    *val += sign * amount;
+                }
+                let result = induced(&shifted);
+                shifted[source] = nominal_inputs[source].clone();
+                result
+            };
+            let up = scan(1.0);
+            let down = scan(-1.0);
+            SourceShift { source, up, down }
+        })
+        .collect();
+    SensitivityScan { nominal, sources }
+}
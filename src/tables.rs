@@ -1,53 +1,201 @@
-use crate::objects::{Measure, Style};
+use crate::{
+    language::Message,
+    objects::{autoscale_prefix, Measure, Style},
+    Dataset, Metadata, Unit,
+};
 
 /// Object to create a table with all required parameters, either in latex or
 /// typst format.
 pub struct Table<'a> {
     data: Vec<Measure>,
-    header: Vec<&'a str>,
+    header: Vec<String>,
+    units_in_header: bool,
+    autoscale: bool,
+    scientific: bool,
+    compact: bool,
     transpose: bool,
-    caption: &'a str,
-    label: &'a str,
+    caption: Option<&'a str>,
+    label: Option<&'a str>,
+    metadata: Metadata,
 }
 
 impl<'a> Table<'a> {
     /// Constructs a new Table with some default values that can be changed.
-    pub fn new(data: Vec<Measure>, header: Vec<&str>) -> Table {
+    pub fn new(data: Vec<Measure>, header: Vec<&str>) -> Table<'_> {
         Table {
             data,
-            header,
+            header: header.into_iter().map(String::from).collect(),
+            units_in_header: false,
+            autoscale: false,
+            scientific: false,
+            compact: false,
             transpose: true,
-            caption: "caption",
-            label: "label",
+            caption: None,
+            label: None,
+            metadata: Metadata::new(),
         }
     }
+    /// Constructs a new Table with the x and y measures of a dataset as
+    /// columns, with the header generated from their names and units, see
+    /// [Table::header_from_measures].
+    pub fn from_dataset(data: &Dataset) -> Self {
+        Table::new(vec![data.x().clone(), data.y().clone()], vec![]).header_from_measures()
+    }
     /// Changes table disposal, true for vertical and false for horizontal, by
     /// default is set to true.
     pub fn transpose(mut self, transpose: bool) -> Self {
         self.transpose = transpose;
         self
     }
-    /// Set a caption for latex table, "caption" by default.
+    /// Set a caption for the table. Latex tables use "caption", in the
+    /// [language](crate::set_language) of the library, by default, and
+    /// typst tables with a caption are placed inside a figure.
     pub fn caption(mut self, caption: &'a str) -> Self {
-        self.caption = caption;
+        self.caption = Some(caption);
         self
     }
-    /// Set a label for latex table, "label" by default.
+    /// Set a label for the table. Latex tables use "label" by default, and
+    /// typst tables with a label are placed inside a figure.
     pub fn label(mut self, label: &'a str) -> Self {
-        self.label = label;
+        self.label = Some(label);
+        self
+    }
+    /// Set the measurement protocol metadata emitted as comments before the
+    /// table and appended to the caption. It is merged over the metadata
+    /// of the measures of the table.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+    /// Generates the header from the name and unit of every measure, e.g.
+    /// "t / s", showing the cells without units.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Table, Unit};
+    /// let time = measure!([0.2, 0.3], 0.01).with_name("t").with_unit(Unit::second());
+    /// let position = measure!([2.4, 3.4], 0.2).with_name("x").with_unit(Unit::meter());
+    ///
+    /// let table = Table::new(vec![time, position], vec![]).header_from_measures().typst();
+    /// assert!(table.contains("[$t$ / $upright(\"s\")$], [$x$ / $upright(\"m\")$]"));
+    /// ```
+    pub fn header_from_measures(mut self) -> Self {
+        self.header.clear();
+        self.units_in_header = true;
         self
     }
+    /// Rescales every measure with the SI prefix that best fits its values,
+    /// see [autoscale_prefix](crate::autoscale_prefix). By default is set to
+    /// false.
+    pub fn autoscale(mut self, autoscale: bool) -> Self {
+        self.autoscale = autoscale;
+        self
+    }
+    /// Factors out the power of ten of every value, e.g.
+    /// `(1.23 ± 0.04)×10⁻³`, see [Style::LatexScientific] and
+    /// [Style::TypstScientific]. By default is set to false.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Table};
+    /// let current = measure!([0.00123, 0.00456], 0.00004);
+    ///
+    /// let table = Table::new(vec![current], vec!["I/A"]).scientific(true).typst();
+    /// assert!(table.contains("[$(1.23 plus.minus 0.04) times 10^(-3)$]"));
+    /// ```
+    pub fn scientific(mut self, scientific: bool) -> Self {
+        self.scientific = scientific;
+        self
+    }
+    /// Writes every error in parentheses in units of the last digit of its
+    /// value, e.g. `1.234(5)`, see [Style::LatexCompact] and
+    /// [Style::TypstCompact]. It takes precedence over
+    /// [scientific](Table::scientific). By default is set to false.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Table};
+    /// let length = measure!([1.234, 2.5], [0.005, 0.1]);
+    ///
+    /// let table = Table::new(vec![length], vec!["L/m"]).compact(true).latex();
+    /// assert!(table.contains("$1.234(5)$"));
+    /// assert!(table.contains("$2.5(1)$"));
+    /// ```
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+    pub(crate) fn get_caption(&self) -> Option<&'a str> {
+        self.caption
+    }
+    pub(crate) fn get_label(&self) -> Option<&'a str> {
+        self.label
+    }
+    fn data(&self) -> Vec<Measure> {
+        if self.autoscale {
+            self.data
+                .iter()
+                .map(|measure| autoscale_prefix(measure).0)
+                .collect()
+        } else {
+            self.data.clone()
+        }
+    }
+    pub(crate) fn headers(&self, style: Style) -> Vec<String> {
+        if self.units_in_header {
+            self.data()
+                .iter()
+                .map(|measure| measure.styled_label(style))
+                .collect()
+        } else {
+            self.header.clone()
+        }
+    }
+    pub(crate) fn cells(&self) -> Vec<Measure> {
+        if self.units_in_header {
+            self.data()
+                .into_iter()
+                .map(|measure| measure.with_unit(Unit::dimensionless()))
+                .collect()
+        } else {
+            self.data()
+        }
+    }
+    fn collect_metadata(&self) -> Metadata {
+        self.data
+            .iter()
+            .fold(Metadata::new(), |acc, measure| {
+                acc.merge(measure.metadata())
+            })
+            .merge(&self.metadata)
+    }
+    // Caption followed by the metadata, whose standard keys are translated to
+    // the language of the library.
+    fn full_caption(caption: &str, metadata: &Metadata) -> String {
+        if metadata.is_empty() {
+            caption.to_string()
+        } else {
+            let entries: Vec<String> = metadata
+                .iter()
+                .map(|(key, val)| format!("{}: {}", Message::metadata_key(key), val))
+                .collect();
+            format!("{} ({})", caption, entries.join("; "))
+        }
+    }
     /// Creates a table using your measures in typst format.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use ferrilab::{measure, Measure, tables::typst};
+    /// # use ferrilab::{measure, Measure, Table};
     /// let time = measure!([0.2, 0.3, 0.40, 0.5], [0.01, 0.02, 0.02, 0.04]);
     /// let position = measure!([2.4, 3.4, 5.1, 7.2], [0.2, 0.4, 0.5, 0.8]);
     /// let speed = &position / &time;
     ///
-    /// println!("{}", typst(vec![time, position, speed], vec!["t/s", "x/m", "v/ms-1"], true))
+    /// println!("{}", Table::new(vec![time, position, speed], vec!["t/s", "x/m", "v/ms-1"]).typst())
     ///
     /// // Output
     ///
@@ -63,20 +211,61 @@ impl<'a> Table<'a> {
     /// )
     ///  */
     /// ```
+    ///
+    /// With a caption or a label the table is placed inside a figure:
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, Table};
+    /// let time = measure!([0.2, 0.3], 0.01);
+    ///
+    /// let table = Table::new(vec![time], vec!["t/s"]).caption("Times").label("tab:times").typst();
+    /// assert!(table.starts_with("#figure("));
+    /// assert!(table.ends_with("caption: [Times],\n) <tab:times>"));
+    /// ```
     pub fn typst(self) -> String {
-        typst(self.data, self.header, self.transpose)
+        let metadata = self.collect_metadata();
+        let style = match (self.compact, self.scientific) {
+            (true, _) => Style::TypstCompact,
+            (false, true) => Style::TypstScientific,
+            (false, false) => Style::TypstTable,
+        };
+        let table = typst(
+            self.cells(),
+            self.headers(Style::TypstTable),
+            style,
+            self.transpose,
+        );
+        let table = if self.caption.is_none() && self.label.is_none() {
+            table
+        } else {
+            let caption = self
+                .caption
+                .map(|caption| {
+                    format!(
+                        "\t caption: [{}],\n",
+                        Table::full_caption(caption, &metadata)
+                    )
+                })
+                .unwrap_or_default();
+            let label = self
+                .label
+                .map(|label| format!(" <{}>", label))
+                .unwrap_or_default();
+            format!("#figure(\n{},\n{}){}", table, caption, label)
+        };
+        format!("{}{}", metadata.to_header("//"), table)
     }
     /// Creates a table using your measures in latex format.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use ferrilab::{measure, Measure, tables::latex};
+    /// # use ferrilab::{measure, Measure, Table};
     /// let time = measure!([0.2, 0.3, 0.40, 0.5], [0.01, 0.02, 0.02, 0.04]);
     /// let position = measure!([2.4, 3.4, 5.1, 7.2], [0.2, 0.4, 0.5, 0.8]);
     /// let speed = &position / &time;
     ///
-    /// println!("{}", latex(vec![time, position, speed], vec!["t/s", "x/m", "v/ms-1"], "Caption", "label", true))
+    /// println!("{}", Table::new(vec![time, position, speed], vec!["t/s", "x/m", "v/ms-1"]).caption("Caption").latex())
     ///
     /// // Output
     ///
@@ -96,18 +285,31 @@ impl<'a> Table<'a> {
     ///  */
     /// ```
     pub fn latex(self) -> String {
-        latex(
-            self.data,
-            self.header,
-            self.caption,
-            self.label,
-            self.transpose,
+        let metadata = self.collect_metadata();
+        let style = match (self.compact, self.scientific) {
+            (true, _) => Style::LatexCompact,
+            (false, true) => Style::LatexScientific,
+            (false, false) => Style::LatexTable,
+        };
+        let caption =
+            Table::full_caption(self.caption.unwrap_or(Message::Caption.text()), &metadata);
+        format!(
+            "{}{}",
+            metadata.to_header("%"),
+            latex(
+                self.cells(),
+                self.headers(Style::LatexTable),
+                style,
+                &caption,
+                self.label.unwrap_or("label"),
+                self.transpose,
+            )
         )
     }
 }
 
-fn typst(data: Vec<Measure>, header: Vec<&str>, transpose: bool) -> String {
-    let mut data = create_table_list(data, header, transpose, Style::TypstTable);
+fn typst(data: Vec<Measure>, header: Vec<String>, style: Style, transpose: bool) -> String {
+    let mut data = create_table_list(data, header, transpose, style);
 
     data = data
         .into_iter()
@@ -130,12 +332,13 @@ fn typst(data: Vec<Measure>, header: Vec<&str>, transpose: bool) -> String {
 
 fn latex(
     data: Vec<Measure>,
-    header: Vec<&str>,
+    header: Vec<String>,
+    style: Style,
     caption: &str,
     label: &str,
     transpose: bool,
 ) -> String {
-    let data = create_table_list(data, header, transpose, Style::LatexTable);
+    let data = create_table_list(data, header, transpose, style);
 
     let tabular: Vec<String> = data
         .iter()
@@ -169,7 +372,7 @@ fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
 
 fn create_table_list(
     data: Vec<Measure>,
-    mut header: Vec<&str>,
+    mut header: Vec<String>,
     transposed: bool,
     style: Style,
 ) -> Vec<Vec<String>> {
@@ -200,13 +403,13 @@ fn create_table_list(
 
     if !header.is_empty() {
         if header.len() < data.len() {
-            header.extend(vec![""; data.len() - header.len()]);
+            header.extend(vec![String::new(); data.len() - header.len()]);
         }
         data = data
             .into_iter()
             .zip(header)
             .map(|(mut data, head)| {
-                data.insert(0, head.to_string());
+                data.insert(0, head);
                 data
             })
             .collect::<Vec<Vec<String>>>();
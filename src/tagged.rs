@@ -0,0 +1,203 @@
+//! Contains the struct TaggedMeasure, used to carry the statistical and
+//! systematic errors of a measure separately.
+use {
+    crate::budget::{impl_sourced_op, SourcedMeasure},
+    crate::objects::{Measure, MyError},
+    crate::units::Unit,
+    std::{
+        fmt::Display,
+        ops::{Add, Div, Mul, Sub},
+    },
+};
+
+/// Type of the error of a measure.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorTag {
+    /// Error from the dispersion of the readings, that decreases when repeating
+    /// the measure.
+    Statistical,
+    /// Error from the instrument or the method, that doesn't decrease when
+    /// repeating the measure.
+    Systematic,
+    /// Error already combining both types, that can't be separated.
+    Combined,
+}
+
+/// Measure whose statistical, systematic and combined errors are propagated
+/// separately through the operations, to be reported as `x ± stat ± sys`.
+///
+/// It is a [SourcedMeasure] with the fixed sources "stat", "sys" and
+/// "combined", so any operation of the measures can be applied with
+/// [TaggedMeasure::map]. The propagation is to first order, so the total error
+/// is the sum in quadrature of the three types.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, ErrorTag, TaggedMeasure};
+/// let length = measure!([10.0, 20.0], 0.3; false).tag(ErrorTag::Statistical)
+///     + measure!([0.0], 0.4; false).tag(ErrorTag::Systematic);
+/// let double = &length * 2.0;
+///
+/// assert_eq!(double.statistical(), &vec![0.6, 0.6]);
+/// assert_eq!(double.systematic(), &vec![0.8, 0.8]);
+/// assert_eq!(double.total().error(), &vec![1.0, 1.0]);
+/// assert_eq!(double.to_string(), "20 ± 0.6 (stat) ± 0.8 (sys), 40 ± 0.6 (stat) ± 0.8 (sys)");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedMeasure(SourcedMeasure);
+
+impl ErrorTag {
+    /// Name of the source of a [SourcedMeasure] with the errors of this type.
+    fn source(self) -> &'static str {
+        match self {
+            ErrorTag::Statistical => "stat",
+            ErrorTag::Systematic => "sys",
+            ErrorTag::Combined => "combined",
+        }
+    }
+}
+
+const TAGS: [ErrorTag; 3] = [
+    ErrorTag::Statistical,
+    ErrorTag::Systematic,
+    ErrorTag::Combined,
+];
+
+impl TaggedMeasure {
+    /// Constructs a TaggedMeasure from a measure and the type of the error of
+    /// each value, or one type for all values.
+    pub fn new(measure: Measure, tags: Vec<ErrorTag>) -> Result<TaggedMeasure, MyError> {
+        if tags.len() != measure.len() && tags.len() != 1 {
+            return Err(MyError::InvalidErrorLen);
+        }
+        let tag = |i: usize| tags[if tags.len() == 1 { 0 } else { i }];
+        let errors = |kind: ErrorTag| {
+            (0..measure.len())
+                .map(|i| {
+                    if tag(i) == kind {
+                        measure.error()[i]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        TAGS.into_iter()
+            .try_fold(SourcedMeasure::new(measure.clone()), |sourced, kind| {
+                sourced.with_source(kind.source(), errors(kind))
+            })
+            .map(TaggedMeasure)
+    }
+    /// Constructs a TaggedMeasure from its values, its statistical errors and
+    /// its systematic errors. There can be one error for all values.
+    pub fn from_errors(
+        value: Vec<f64>,
+        statistical: Vec<f64>,
+        systematic: Vec<f64>,
+    ) -> Result<TaggedMeasure, MyError> {
+        Ok(TaggedMeasure(
+            SourcedMeasure::new(Measure::new(value, vec![0.0], false)?)
+                .with_source(ErrorTag::Statistical.source(), statistical)?
+                .with_source(ErrorTag::Systematic.source(), systematic)?
+                .with_source(ErrorTag::Combined.source(), vec![0.0])?,
+        ))
+    }
+
+    /// Returns the values.
+    pub fn value(&self) -> &Vec<f64> {
+        self.0.value()
+    }
+    /// Returns the statistical errors.
+    pub fn statistical(&self) -> &Vec<f64> {
+        self.component(ErrorTag::Statistical).error()
+    }
+    /// Returns the systematic errors.
+    pub fn systematic(&self) -> &Vec<f64> {
+        self.component(ErrorTag::Systematic).error()
+    }
+    /// Returns the errors that were already combined.
+    pub fn combined(&self) -> &Vec<f64> {
+        self.component(ErrorTag::Combined).error()
+    }
+    /// Returns the measure with only the errors of one type.
+    pub fn component(&self, tag: ErrorTag) -> &Measure {
+        self.0.component(tag.source()).unwrap()
+    }
+    /// Returns the errors as a [SourcedMeasure].
+    pub fn sourced(&self) -> &SourcedMeasure {
+        &self.0
+    }
+    /// Returns the unit.
+    pub fn unit(&self) -> &Unit {
+        self.0.unit()
+    }
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the measure with the three types of errors added in quadrature.
+    pub fn total(&self) -> Measure {
+        self.0.total()
+    }
+
+    /// Applies an operation of measures to every type of error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ferrilab::{measure, Measure, TaggedMeasure};
+    /// let angle = TaggedMeasure::from_errors(vec![0.0], vec![0.1], vec![0.2]).unwrap();
+    /// let sine = angle.map(Measure::sin);
+    ///
+    /// assert_eq!(sine.statistical(), &vec![0.1]);
+    /// assert_eq!(sine.systematic(), &vec![0.2]);
+    /// ```
+    pub fn map(&self, function: impl Fn(&Measure) -> Measure) -> TaggedMeasure {
+        TaggedMeasure(self.0.map(function))
+    }
+    /// Applies an operation of two measures to every type of error.
+    pub fn zip_with(
+        &self,
+        other: &TaggedMeasure,
+        function: impl Fn(&Measure, &Measure) -> Measure,
+    ) -> TaggedMeasure {
+        TaggedMeasure(self.0.zip_with(&other.0, function))
+    }
+}
+
+impl Measure {
+    /// Tags all the errors of the measure with a type, see [TaggedMeasure].
+    pub fn tag(self, tag: ErrorTag) -> TaggedMeasure {
+        TaggedMeasure::new(self, vec![tag]).unwrap()
+    }
+}
+
+impl From<Measure> for TaggedMeasure {
+    /// The errors of a measure are combined unless tagged.
+    fn from(measure: Measure) -> TaggedMeasure {
+        measure.tag(ErrorTag::Combined)
+    }
+}
+
+impl Display for TaggedMeasure {
+    /// Written as the [SourcedMeasure], omitting the combined errors when all
+    /// of them are zero.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.combined().iter().all(|err| *err == 0.0) {
+            Display::fmt(&self.0.without_source(ErrorTag::Combined.source()), f)
+        } else {
+            Display::fmt(&self.0, f)
+        }
+    }
+}
+
+impl_sourced_op!(TaggedMeasure, Add, add);
+impl_sourced_op!(TaggedMeasure, Sub, sub);
+impl_sourced_op!(TaggedMeasure, Mul, mul);
+impl_sourced_op!(TaggedMeasure, Div, div);
@@ -0,0 +1,145 @@
+//! Contains the struct TimeSeries, measures taken by a logger at given times.
+use crate::{dataset::Dataset, objects::Measure, units::Unit};
+
+/// Interval without readings in a [TimeSeries].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gap {
+    /// Index of the first reading after the gap.
+    pub index: usize,
+    /// Time of the last reading before the gap.
+    pub start: f64,
+    /// Time of the first reading after the gap.
+    pub end: f64,
+}
+
+impl Gap {
+    /// Time elapsed between both readings.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Measure whose values were taken at the given timestamps, in seconds, by a
+/// logger that may stop recording for a while. The readings can be split at
+/// the gaps so every segment is fitted separately.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::{measure, Measure, TimeSeries};
+/// let temperature = TimeSeries::new(
+///     vec![100.0, 101.0, 102.0, 250.0, 251.0],
+///     measure!([20.1, 20.3, 20.4, 25.0, 25.2], 0.1),
+/// );
+///
+/// assert_eq!(temperature.gaps(None)[0].duration(), 148.0);
+/// let segments = temperature.segments(None);
+/// assert_eq!(segments[1].elapsed(), vec![0.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    timestamps: Vec<f64>,
+    measure: Measure,
+}
+
+impl TimeSeries {
+    /// Constructs a new TimeSeries. Panics if the lengths are diferent or the
+    /// timestamps are not increasing.
+    pub fn new(timestamps: Vec<f64>, measure: Measure) -> TimeSeries {
+        assert_eq!(
+            timestamps.len(),
+            measure.len(),
+            "Measures lengths must be equals, obtained {} and {}.",
+            timestamps.len(),
+            measure.len()
+        );
+        assert!(
+            timestamps.windows(2).all(|pair| pair[0] < pair[1]),
+            "The timestamps must be increasing."
+        );
+        TimeSeries {
+            timestamps,
+            measure,
+        }
+    }
+    /// Length of the series.
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+    /// Checks if the series is empty.
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+    /// Returns the timestamps.
+    pub fn timestamps(&self) -> &Vec<f64> {
+        &self.timestamps
+    }
+    /// Returns the measure.
+    pub fn measure(&self) -> &Measure {
+        &self.measure
+    }
+
+    /// Median of the time between consecutive readings, which is the sampling
+    /// period of the logger, or NaN with less than two readings.
+    pub fn median_step(&self) -> f64 {
+        let mut steps: Vec<f64> = self
+            .timestamps
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect();
+        if steps.is_empty() {
+            return f64::NAN;
+        }
+        steps.sort_by(f64::total_cmp);
+        let middle = steps.len() / 2;
+        if steps.len().is_multiple_of(2) {
+            (steps[middle - 1] + steps[middle]) / 2.0
+        } else {
+            steps[middle]
+        }
+    }
+    /// Finds the intervals between consecutive readings longer than a maximum
+    /// step, by default 1.5 times the median step.
+    pub fn gaps(&self, max_step: impl Into<Option<f64>>) -> Vec<Gap> {
+        let max_step = max_step.into().unwrap_or(1.5 * self.median_step());
+        self.timestamps
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[1] - pair[0] > max_step)
+            .map(|(i, pair)| Gap {
+                index: i + 1,
+                start: pair[0],
+                end: pair[1],
+            })
+            .collect()
+    }
+    /// Splits the series at the gaps longer than a maximum step, by default
+    /// 1.5 times the median step.
+    pub fn segments(&self, max_step: impl Into<Option<f64>>) -> Vec<TimeSeries> {
+        let mut bounds: Vec<usize> = vec![0];
+        bounds.extend(self.gaps(max_step).iter().map(|gap| gap.index));
+        bounds.push(self.len());
+        bounds
+            .windows(2)
+            .filter(|bound| bound[0] < bound[1])
+            .map(|bound| TimeSeries {
+                timestamps: self.timestamps[bound[0]..bound[1]].to_vec(),
+                measure: self.measure.slice(bound[0]..bound[1]).to_measure(),
+            })
+            .collect()
+    }
+    /// Time of every reading since the first one.
+    pub fn elapsed(&self) -> Vec<f64> {
+        let start = self.timestamps.first().copied().unwrap_or(0.0);
+        self.timestamps.iter().map(|time| time - start).collect()
+    }
+    /// Dataset of the elapsed time, in seconds and without error, and the
+    /// measure, ready to be fitted.
+    pub fn to_dataset(&self) -> Dataset {
+        let n = self.len();
+        let time = Measure::new(self.elapsed(), vec![0.0; n], false)
+            .unwrap()
+            .with_unit(Unit::second());
+        Dataset::new(time, self.measure.clone())
+    }
+}
@@ -0,0 +1,227 @@
+//! Contains the struct Unit, used to attach physical dimensions to measures.
+use std::{
+    fmt::Display,
+    ops::{Div, Mul},
+};
+
+/// Symbols of the SI base units, in the same order as the exponents of a [Unit].
+const BASE_SYMBOLS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+/// SI prefixes from 10^-24 to 10^24 in steps of 10^3.
+const PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Returns the power of ten multiple of 3 and the SI prefix that best fits the
+/// magnitude of a value.
+pub(crate) fn si_prefix(value: f64) -> (i32, &'static str) {
+    if value == 0.0 || !value.is_finite() {
+        return (0, "");
+    }
+    let exponent = ((value.abs().log10() / 3.0).floor() as i32).clamp(-8, 8);
+    (exponent * 3, PREFIXES[(exponent + 8) as usize])
+}
+
+/// Returns the power of ten of an SI prefix, or None if it is not a prefix.
+pub(crate) fn si_exponent(prefix: &str) -> Option<i32> {
+    PREFIXES
+        .iter()
+        .position(|p| *p == prefix)
+        .map(|i| (i as i32 - 8) * 3)
+}
+
+/// Whether an SI prefix can be attached to a unit symbol, which is only the
+/// case of a single unit with exponent 1, as a prefix of `m^2` or `m·s^-1`
+/// would scale only its first factor.
+pub(crate) fn accepts_prefix(symbol: &str) -> bool {
+    !symbol.is_empty() && !symbol.contains(['·', '^', ' ', '/', '*', '²', '³'])
+}
+
+/// Physical unit of a measure, stored as the exponents of the SI base units
+/// (m, kg, s, A, K, mol, cd) along with an optional custom symbol.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ferrilab::Unit;
+/// let speed = &Unit::meter() / &Unit::second();
+/// assert_eq!(speed.to_string(), "m·s^-1");
+///
+/// let force = Unit::new("N", [1.0, 1.0, -2.0, 0.0, 0.0, 0.0, 0.0]);
+/// assert!(force.is_compatible(&(&(&Unit::kilogram() * &Unit::meter()) / &Unit::second().powf(2.0))));
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct Unit {
+    dimension: [f64; 7],
+    symbol: Option<String>,
+}
+
+impl Unit {
+    /// Constructs a new unit with a custom symbol and its SI base units exponents.
+    pub fn new(symbol: impl Into<String>, dimension: [f64; 7]) -> Unit {
+        Unit {
+            dimension,
+            symbol: Some(symbol.into()),
+        }
+    }
+    /// Unit without dimensions, used by default in every measure.
+    pub fn dimensionless() -> Unit {
+        Unit::default()
+    }
+    /// Constructs a unit from its SI base units exponents, generating the symbol.
+    pub fn from_dimension(dimension: [f64; 7]) -> Unit {
+        Unit {
+            dimension,
+            symbol: None,
+        }
+    }
+    /// Metre, SI unit of length.
+    pub fn meter() -> Unit {
+        Unit::base(0)
+    }
+    /// Kilogram, SI unit of mass.
+    pub fn kilogram() -> Unit {
+        Unit::base(1)
+    }
+    /// Second, SI unit of time.
+    pub fn second() -> Unit {
+        Unit::base(2)
+    }
+    /// Ampere, SI unit of electric current.
+    pub fn ampere() -> Unit {
+        Unit::base(3)
+    }
+    /// Kelvin, SI unit of temperature.
+    pub fn kelvin() -> Unit {
+        Unit::base(4)
+    }
+    /// Mole, SI unit of amount of substance.
+    pub fn mole() -> Unit {
+        Unit::base(5)
+    }
+    /// Candela, SI unit of luminous intensity.
+    pub fn candela() -> Unit {
+        Unit::base(6)
+    }
+    fn base(index: usize) -> Unit {
+        let mut dimension = [0.0; 7];
+        dimension[index] = 1.0;
+        Unit::from_dimension(dimension)
+    }
+    /// Returns the exponents of the SI base units.
+    pub fn dimension(&self) -> &[f64; 7] {
+        &self.dimension
+    }
+    /// Checks if the unit has no dimensions.
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimension.iter().all(|exp| exp.abs() < 1e-9)
+    }
+    /// Checks if two units have the same dimensions, so they can be added or
+    /// subtracted.
+    pub fn is_compatible(&self, other: &Unit) -> bool {
+        self.dimension
+            .iter()
+            .zip(other.dimension.iter())
+            .all(|(sexp, oexp)| (sexp - oexp).abs() < 1e-9)
+    }
+    /// Raises a unit to any number.
+    pub fn powf(&self, exponent: f64) -> Unit {
+        if exponent == 1.0 {
+            return self.clone();
+        }
+        Unit::from_dimension(self.dimension.map(|exp| exp * exponent))
+    }
+    /// Returns the symbol of the unit, either the custom one or one generated
+    /// from the SI base units.
+    pub fn symbol(&self) -> String {
+        if let Some(symbol) = &self.symbol {
+            return symbol.clone();
+        }
+        self.factors()
+            .map(|(symbol, exp)| {
+                if exp == 1.0 {
+                    symbol.to_string()
+                } else {
+                    format!("{}^{}", symbol, exp)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("·")
+    }
+    /// Returns the custom symbol of the unit, if it has one.
+    pub(crate) fn custom_symbol(&self) -> Option<&str> {
+        self.symbol.as_deref()
+    }
+    /// Returns the symbol of the unit formatted for latex math mode.
+    pub fn latex(&self) -> String {
+        if let Some(symbol) = &self.symbol {
+            return format!("\\mathrm{{{}}}", symbol);
+        }
+        self.factors()
+            .map(|(symbol, exp)| {
+                if exp == 1.0 {
+                    format!("\\mathrm{{{}}}", symbol)
+                } else {
+                    format!("\\mathrm{{{}}}^{{{}}}", symbol, exp)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\\,")
+    }
+    /// Returns the symbol of the unit formatted for typst math mode.
+    pub fn typst(&self) -> String {
+        if let Some(symbol) = &self.symbol {
+            return format!("upright(\"{}\")", symbol);
+        }
+        self.factors()
+            .map(|(symbol, exp)| {
+                if exp == 1.0 {
+                    format!("upright(\"{}\")", symbol)
+                } else {
+                    format!("upright(\"{}\")^({})", symbol, exp)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+    fn factors(&self) -> impl Iterator<Item = (&'static str, f64)> + '_ {
+        BASE_SYMBOLS
+            .iter()
+            .zip(self.dimension.iter())
+            .filter(|(_, exp)| exp.abs() >= 1e-9)
+            .map(|(symbol, exp)| (*symbol, *exp))
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl Mul<&Unit> for &Unit {
+    type Output = Unit;
+
+    fn mul(self, other: &Unit) -> Self::Output {
+        if other.is_dimensionless() {
+            return self.clone();
+        }
+        if self.is_dimensionless() {
+            return other.clone();
+        }
+        let mut dimension = self.dimension;
+        dimension
+            .iter_mut()
+            .zip(other.dimension.iter())
+            .for_each(|(sexp, oexp)| *sexp += oexp);
+        Unit::from_dimension(dimension)
+    }
+}
+
+impl Div<&Unit> for &Unit {
+    type Output = Unit;
+
+    fn div(self, other: &Unit) -> Self::Output {
+        self * &other.powf(-1.0)
+    }
+}
@@ -0,0 +1,79 @@
+//! Contains the struct MeasureView, a borrowed range of a measure.
+use crate::{objects::Measure, ScalarMeasure};
+
+/// Borrowed range of a [Measure], obtained with [Measure::slice],
+/// [Measure::take] or [Measure::skip], that avoids copying the values and errors
+/// until a new measure is needed.
+#[derive(Debug, Copy, Clone)]
+pub struct MeasureView<'a> {
+    pub(crate) measure: &'a Measure,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl<'a> MeasureView<'a> {
+    pub(crate) fn new(measure: &'a Measure, start: usize, end: usize) -> MeasureView<'a> {
+        MeasureView {
+            measure,
+            start,
+            end,
+        }
+    }
+    /// Length of the view.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    /// Checks if the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the values of the view.
+    pub fn value(&self) -> &'a [f64] {
+        &self.measure.value()[self.start..self.end]
+    }
+    /// Returns the errors of the view.
+    pub fn error(&self) -> &'a [f64] {
+        &self.measure.error()[self.start..self.end]
+    }
+    /// Returns a tuple (values, error).
+    pub fn unpack(&self) -> (&'a [f64], &'a [f64]) {
+        (self.value(), self.error())
+    }
+    /// Returns the measure the view borrows from.
+    pub fn measure(&self) -> &'a Measure {
+        self.measure
+    }
+    /// Iterates over the view as a tuple (value, error).
+    pub fn iter(&self) -> impl Iterator<Item = (&'a f64, &'a f64)> {
+        self.value().iter().zip(self.error().iter())
+    }
+    /// Returns the value and error of a certain index of the view.
+    pub fn get(&self, index: usize) -> Option<(&'a f64, &'a f64)> {
+        Some((self.value().get(index)?, self.error().get(index)?))
+    }
+    /// Calculates the mean of the values of the view.
+    pub fn mean(&self) -> f64 {
+        self.value().iter().sum::<f64>() / (self.len() as f64)
+    }
+    /// Calculates the mean and its error, taking into account both the standard
+    /// error of the values and the biggest of the errors.
+    pub fn estimation(&self) -> ScalarMeasure {
+        self.to_measure().estimation()
+    }
+    /// Copies the view into a new measure, keeping the style, unit and metadata.
+    pub fn to_measure(&self) -> Measure {
+        Measure::from(*self)
+    }
+}
+
+impl From<MeasureView<'_>> for Vec<f64> {
+    fn from(m: MeasureView<'_>) -> Vec<f64> {
+        m.value().to_vec()
+    }
+}
+
+impl From<MeasureView<'_>> for (Vec<f64>, Vec<f64>) {
+    fn from(m: MeasureView<'_>) -> (Vec<f64>, Vec<f64>) {
+        (m.value().to_vec(), m.error().to_vec())
+    }
+}
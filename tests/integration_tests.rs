@@ -1,4 +1,22 @@
-use ferrilab::{measure, CurveFit, LinearFit, Measure};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use ferrilab::{
+    autoscale_prefix, execute_python, measure, models,
+    plot::testing,
+    ratio, read_binary, save, set_backend,
+    stats::{
+        anova, combine, cross_correlate, estimate_lag, f_test, ks_normality, sensitivity_scan,
+        shapiro_wilk, t_test, Shift, TTestKind,
+    },
+    write_binary, CurveFit, Dataset, ErrorTag, FitResult, GenericMeasure, Hexbin, Histogram,
+    InstrumentProfile, LinearFit, Measure, Measure2D, MeasureKey, Metadata, Moments, MyError,
+    Optimizer, OrdByValue, OutlierCriterion, Plot, PlotError, PowerLawFit, Reader, ResampleMethod,
+    ScalarMeasure, Scatter, Session, SourcedMeasure, Style, Table, TaggedMeasure, TimeSeries, Unit,
+};
 
 #[test]
 fn macro_test() {
@@ -57,6 +75,55 @@ fn get_and_set() {
         measure1,
         Measure::new(vec![15., 2., 3.], vec![2., 0., 2.], false).unwrap()
     );
+
+    if let Some((value, error)) = measure1.get_mut(0) {
+        *value = 1.;
+        *error = 0.5;
+    }
+    assert_eq!(measure1.get(0), Some((&1., &0.5)));
+    assert_eq!(measure1.get_mut(3), None);
+
+    assert_eq!(measure2.get_range(1..), Some(measure!([5, 0], [1, 0])));
+    assert_eq!(measure2.get_range(..=1), Some(measure!([4, 5], 1)));
+    assert_eq!(measure2.get_range(2..4), None);
+}
+
+#[test]
+fn sort_test() {
+    let x = measure!([0.3, -1.0, 2.5, 0.3], [0.03, 0.1, 0.25, 0.3]);
+    let y = measure!([1, 2, 3, 4], [0.1, 0.2, 0.3, 0.4]);
+
+    let indices = x.argsort();
+    assert_eq!(indices, vec![1, 0, 3, 2]);
+    assert_eq!(
+        x.clone().sort_by_value(),
+        measure!([-1.0, 0.3, 0.3, 2.5], [0.1, 0.03, 0.3, 0.25])
+    );
+    assert_eq!(
+        y.reorder(&indices),
+        measure!([2, 1, 4, 3], [0.2, 0.1, 0.4, 0.3])
+    );
+    assert_eq!(y.reorder(&[3, 3]), measure!([4, 4], 0.4));
+}
+
+#[test]
+fn view_test() {
+    let measure = measure!([1, 2, 3, 4, 5], [0.1, 0.2, 0.3, 0.4, 0.5]);
+
+    let view = measure.slice(1..4);
+    assert_eq!(view.len(), 3);
+    assert_eq!(view.value(), &[2., 3., 4.]);
+    assert_eq!(view.error(), &[0.2, 0.3, 0.4]);
+    assert_eq!(view.mean(), 3.);
+    assert_eq!(view.to_measure(), measure!([2, 3, 4], [0.2, 0.3, 0.4]));
+
+    assert_eq!(measure.take(2).value(), &[1., 2.]);
+    assert_eq!(measure.take(10).len(), 5);
+    assert_eq!(measure.skip(3).value(), &[4., 5.]);
+    assert!(measure.skip(10).is_empty());
+
+    let (slope, intercept) = LinearFit::new(measure.skip(1), (&measure * 2 + 1).skip(1)).fit();
+    assert!((slope.value - 2.).abs() < 1e-9 && (intercept.value - 1.).abs() < 1e-9);
 }
 
 #[test]
@@ -106,7 +173,7 @@ fn operations() {
 
     assert_eq!(
         x.atan2(&y).aprox(),
-        measure!([1.107, 1.279, 1.342, 1.373], [0.003, 0.005, 0.007, 0.009])
+        measure!([1.11, 1.28, 1.34, 1.37], [0.06, 0.04, 0.03, 0.03])
     );
 
     assert_eq!(
@@ -116,8 +183,9 @@ fn operations() {
 
     assert_eq!(
         x.exp().aprox(),
-        measure!([2.72, 7.4, 20.1, 55.0], [0.1, 0.4, 0.9, 2.0])
+        measure!([2.7, 7.4, 20.0, 55.0], [0.3, 1.5, 6.0, 22.0])
     );
+    assert_eq!(x.exp(), x.propagate(|x| x.exp()));
 
     assert_eq!(
         (&x + &y).aprox(),
@@ -136,7 +204,7 @@ fn operations() {
 
     assert_eq!(
         (&x / &y).aprox(),
-        measure!([2.0, 3.3, 4.3, 5.0], [0.2, 0.4, 0.5, 0.5])
+        measure!([2.0, 3.3, 4.3, 5.0], [0.3, 0.5, 0.6, 0.7])
     );
 
     assert_eq!(
@@ -149,14 +217,466 @@ fn operations() {
     );
 }
 
+#[test]
+fn limits_test() {
+    let x = measure!([0.5, -2, 4, 1], [0.1, 0.2, 0.4, 0.1]; false);
+    assert_eq!(x.argmin(), Some(1));
+    assert_eq!(x.argmax(), Some(2));
+    assert_eq!(measure!([1, 3, 3]).argmax(), Some(1));
+    assert_eq!(Measure::new(vec![], vec![], false).unwrap().argmin(), None);
+
+    let inverse = x.recip();
+    assert_eq!(inverse.value(), &vec![2.0, -0.5, 0.25, 1.0]);
+    assert_eq!(inverse.error(), &vec![0.4, 0.05, 0.025, 0.1]);
+
+    let limit = measure!([1, 0, 2, 1], [0.5, 0.5, 0.5, 0.5]; false);
+    assert_eq!(
+        x.min_with(&limit),
+        measure!([0.5, -2, 2, 1], [0.1, 0.2, 0.5, 0.1]; false)
+    );
+    assert_eq!(
+        x.max_with(&measure!(0, 0.01; false)),
+        measure!([0.5, 0, 4, 1], [0.1, 0.01, 0.4, 0.1]; false)
+    );
+
+    assert_eq!(
+        x.clamp(0, 2),
+        measure!([0.5, 0, 2, 1], [0.1, 0, 0, 0.1]; false)
+    );
+}
+
+#[test]
+fn powi_test() {
+    let side = measure!([2, -3], [0.1, 0.2]; false).with_unit(Unit::meter());
+    let volume = side.powi(3);
+    assert_eq!(volume.value(), &vec![8.0, -27.0]);
+    assert_eq!(volume.error(), &vec![12.0 * 0.1, 27.0 * 0.2]);
+    assert!(volume.unit().is_compatible(&Unit::meter().powf(3.0)));
+    assert_eq!(side.powi(2).error(), side.pow(2).error());
+    assert!(side.powi(2).error()[0] > (&side * &side).error()[0]);
+    assert_eq!(side.powi(0).value(), &vec![1.0, 1.0]);
+
+    let scalar = ScalarMeasure::new(2.0, 0.1).powi(-1);
+    assert_eq!(scalar.value, 0.5);
+    assert!((scalar.error - 0.025).abs() < 1e-15);
+}
+
+#[test]
+fn pow_measure_test() {
+    let base = measure!([2, 4], [0.1, 0.2]);
+    let exact = measure!(3, 0.0);
+    assert_eq!(base.pow_measure(&exact), base.pow(3));
+
+    let exponent = measure!(2, 0.1);
+    let power = base.pow_measure(&exponent);
+    assert_eq!(power.value(), &vec![4.0, 16.0]);
+    let expected = ((2.0 * 2.0 * 0.1_f64).powi(2) + (4.0 * 2_f64.ln() * 0.1_f64).powi(2)).sqrt();
+    assert!((power.error()[0] - expected).abs() < 1e-12);
+
+    let powers = measure!(2, 0.0).pow_measure(&measure!([1, 2, 3], 0.0));
+    assert_eq!(powers.value(), &vec![2.0, 4.0, 8.0]);
+
+    let area = measure!([2], 0.1)
+        .with_unit(Unit::meter())
+        .pow_measure(&exponent);
+    assert!(area.unit().is_compatible(&Unit::meter().powf(2.0)));
+}
+
+#[test]
+fn logarithms() {
+    let x = measure!([10, 100, 1000], [1, 2, 3]);
+    assert_eq!(x.log10().value(), &vec![1.0, 2.0, 3.0]);
+    x.log10()
+        .iter()
+        .zip(x.iter())
+        .for_each(|((_, err), (val, verr))| {
+            assert!((err - verr / (val * 10_f64.ln())).abs() < 1e-15)
+        });
+    assert_eq!(
+        measure!([8], 0.8).log2(),
+        measure!([3], [0.1 / 2_f64.ln()]; false)
+    );
+    let ln = x.ln();
+    let log_e = x.log(std::f64::consts::E);
+    ln.iter()
+        .zip(log_e.iter())
+        .for_each(|((a, ea), (b, eb))| assert!((a - b).abs() < 1e-12 && (ea - eb).abs() < 1e-15));
+
+    let ph = ScalarMeasure::new(1e-7, 1e-8).log10() * -1;
+    assert!((ph.value - 7.0).abs() < 1e-12);
+    assert!((ph.error - 0.1 / 10_f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn assign_operations() {
+    let runs = [
+        measure!([1, 2], 0.3),
+        measure!([2, 3], 0.4),
+        measure!([3, 4], 0.0),
+    ];
+    let mut total = measure!([0, 0], 0.0);
+    for run in runs.iter() {
+        total += run;
+    }
+    assert_eq!(total, &(&runs[0] + &runs[1]) + &runs[2]);
+
+    let mut x = measure!([1, 2], [0.1, 0.2]);
+    x *= 2;
+    assert_eq!(x, measure!([2, 4], [0.2, 0.4]));
+    x -= 1.5;
+    assert_eq!(x, measure!([0.5, 2.5], [0.2, 0.4]; false));
+    x /= ScalarMeasure::new(2.0, 0.0);
+    assert_eq!(x, measure!([0.25, 1.25], [0.1, 0.2]; false));
+    x += measure!([1, 1], 0.0);
+    assert_eq!(x, measure!([1.25, 2.25], [0.1, 0.2]; false));
+}
+
+#[test]
+fn sum_and_product() {
+    let runs = vec![
+        measure!([1, 2], [0.3, 0.1]),
+        measure!([2, 3], [0.4, 0.1]),
+        measure!([3, 4], [0.0, 0.2]),
+    ];
+    assert_eq!(
+        runs.iter().sum::<Measure>(),
+        &(&runs[0] + &runs[1]) + &runs[2]
+    );
+    assert_eq!(
+        runs.iter().product::<Measure>(),
+        &(&runs[0] * &runs[1]) * &runs[2]
+    );
+    assert_eq!(
+        runs.clone().into_iter().sum::<Measure>().value(),
+        &vec![6., 9.]
+    );
+    assert!(Vec::<Measure>::new()
+        .into_iter()
+        .sum::<Measure>()
+        .is_empty());
+}
+
+#[test]
+fn reductions() {
+    let path = measure!([1.5, 2.0, 2.5], [0.3, 0.4, 0.0]).with_unit(Unit::meter());
+    let total = path.sum();
+    assert_eq!(total.value(), &vec![6.0]);
+    assert!((total.error()[0] - 0.5).abs() < 1e-12);
+    assert_eq!(total.unit(), &Unit::meter());
+
+    let product = path.product();
+    assert_eq!(product.value(), &vec![7.5]);
+    assert!((product.error()[0] - (1.5_f64.powi(2) + 1.5_f64.powi(2)).sqrt()).abs() < 1e-12);
+    assert!(product.unit().is_compatible(&Unit::meter().powf(3.0)));
+
+    let with_zero = measure!([0.0, 2.0], [0.1, 0.2]).product();
+    assert_eq!(with_zero, measure!([0.0], [0.2]; false));
+}
+
+#[test]
+fn comparison_test() {
+    let x = measure!([1.0, 2.0, 3.0, 4.0], 0.5; false);
+    let y = measure!([2.5, 2.5, 2.5, 2.5], [0.5, 0.5, 0.0, 0.5]; false);
+    assert_eq!(x.definitely_less_than(&y), vec![true, false, false, false]);
+    assert_eq!(
+        x.definitely_greater_than(&y),
+        vec![false, false, false, true]
+    );
+    assert_eq!(x.overlaps(&y), vec![false, true, true, false]);
+    assert_eq!(
+        x.definitely_less_than(&measure!(3.0, 0.1)),
+        vec![true, true, false, false]
+    );
+
+    // PartialOrd is lexicographic, the value comparison ignores the errors.
+    let (a, b) = (measure!(1.0, 0.5), measure!(1.0, 0.1));
+    assert!(a > b);
+    assert!(a.ord_by_value(&b).is_eq());
+    assert!(measure!([1, 2]).ord_by_value(&measure!([1, 2, 0])).is_lt());
+
+    let mut runs = [
+        measure!(2.0, 0.1),
+        Measure::new(vec![f64::NAN], vec![0.1], false).unwrap(),
+        measure!(-1.0, 0.3),
+    ];
+    runs.sort_by_key(|run| OrdByValue(run.clone()));
+    assert_eq!(runs[0], measure!(-1.0, 0.3));
+    assert!(runs[2].value()[0].is_nan());
+
+    let (c, d) = (ScalarMeasure::new(1.0, 0.1), ScalarMeasure::new(1.5, 0.2));
+    assert!(c.definitely_less_than(&d) && d.definitely_greater_than(&c));
+    assert!(!c.overlaps(&d));
+
+    assert_eq!(
+        x.compatible_with(&measure!(2.0, 0.0; false)),
+        vec![2.0, 0.0, 2.0, 4.0]
+    );
+    assert_eq!(y.compatible_with(&y), vec![0.0; 4]);
+    assert_eq!(
+        measure!(1.0, 0.0; false).compatible_with(&measure!(2.0, 0.0; false)),
+        vec![f64::INFINITY]
+    );
+    assert!((d.compatible_with(&c) - 0.5 / 0.05_f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn key_test() {
+    let run = measure!([1.2, 0.0, 1.3], 0.1);
+    let mut fits = HashMap::new();
+    fits.insert(MeasureKey::new(run.clone().with_name("run")), run.mean());
+
+    let same = measure!([1.2, -0.0, 1.3], 0.1);
+    assert_eq!(fits.get(&MeasureKey::new(same.clone())), Some(&run.mean()));
+    assert_eq!(
+        MeasureKey::new(run.clone()).id(),
+        MeasureKey::new(same).id()
+    );
+    assert_eq!(MeasureKey::new(run.clone()).id(), 0x2065e394036e23d7);
+
+    let with_unit = MeasureKey::new(run.clone().with_unit(Unit::meter()));
+    assert!(!fits.contains_key(&with_unit));
+
+    let nan = Measure::new(vec![f64::NAN], vec![0.1], false).unwrap();
+    assert_ne!(nan, nan.clone());
+    assert_eq!(MeasureKey::new(nan.clone()), MeasureKey::new(nan * -1));
+}
+
+#[test]
+fn integration_test() {
+    let x = measure!([0.0, 0.5, 1.5, 2.0, 3.0], 0.0; false).with_unit(Unit::second());
+    let y = (&x * &x).with_unit(Unit::meter());
+    let parabola = y.integrate_simpson(&x);
+    assert!((parabola.value()[0] - 9.0).abs() < 1e-12);
+    assert!(parabola
+        .unit()
+        .is_compatible(&(&Unit::meter() * &Unit::second())));
+    assert!(
+        (y.take(4)
+            .to_measure()
+            .integrate_simpson(&x.take(4).to_measure())
+            .value()[0]
+            - 8.0 / 3.0)
+            .abs()
+            < 1e-12
+    );
+
+    let line = measure!([1.0, 3.0, 5.0], [0.1, 0.2, 0.1]; false);
+    let t = measure!([0.0, 1.0, 2.0], [0.0, 0.1, 0.0]; false);
+    let area = line.integrate(&t);
+    assert_eq!(area.value(), &vec![6.0]);
+    // Weights 0.5, 1, 0.5 for y and sensitivity (1 - 5) / 2 for the middle x.
+    let expected = (0.05_f64.powi(2) + 0.2_f64.powi(2) + 0.05_f64.powi(2) + 0.2_f64.powi(2)).sqrt();
+    assert!((area.error()[0] - expected).abs() < 1e-12);
+    assert_eq!(line.integrate_simpson(&t).value(), &vec![6.0]);
+}
+
+#[test]
+fn interp_test() {
+    let slow = measure!([0.0, 1.0, 2.0, 3.0], 0.0; false).with_unit(Unit::second());
+    let fast = measure!([0.0, 0.5, 1.0, 2.5, 3.0], 0.0; false).with_unit(Unit::second());
+    let current =
+        measure!([1.0, 3.0, 5.0, 7.0], [0.3, 0.4, 0.0, 0.1]; false).with_unit(Unit::ampere());
+
+    let linear = current.interp(&slow, &fast);
+    assert_eq!(linear.value(), &vec![1.0, 2.0, 3.0, 6.0, 7.0]);
+    assert_eq!(linear.error()[0], 0.3);
+    assert_eq!(linear.error()[1], 0.25);
+    assert!((linear.error()[3] - 0.05_f64.hypot(0.0)).abs() < 1e-12);
+    assert!(linear.unit().is_compatible(&Unit::ampere()));
+
+    // The spline of points on a line is the line itself.
+    let spline = current.interp_spline(&slow, &fast);
+    for (val, expected) in spline.value().iter().zip(linear.value()) {
+        assert!((val - expected).abs() < 1e-12);
+    }
+    assert_eq!(spline.error(), linear.error());
+
+    let parabola = (&slow * &slow).interp_spline(&slow, &measure!([1.5], 0.0; false));
+    assert!((parabola.value()[0] - 2.25).abs() < 0.1);
+}
+
+#[test]
+fn outliers_test() {
+    let weights = measure!(
+        [199.31, 199.53, 200.19, 200.82, 201.92, 201.95, 202.18, 245.57],
+        0.01; false
+    )
+    .with_unit(Unit::kilogram());
+    let (clean, removed) = weights.reject_outliers(OutlierCriterion::Grubbs(0.05));
+    assert_eq!(removed, vec![7]);
+    assert_eq!(clean.len(), 7);
+    assert_eq!(clean.value()[6], 202.18);
+    assert!(clean.unit().is_compatible(&Unit::kilogram()));
+
+    let (_, removed) = weights.reject_outliers(OutlierCriterion::Chauvenet);
+    assert_eq!(removed, vec![7]);
+
+    let repeated = measure!([2.0, 2.0, 2.0, 2.0], 0.1);
+    let (same, removed) = repeated.reject_outliers(OutlierCriterion::Grubbs(0.05));
+    assert!(removed.is_empty());
+    assert_eq!(same, repeated);
+    let (_, removed) = measure!([1.0, 100.0]).reject_outliers(OutlierCriterion::Chauvenet);
+    assert!(removed.is_empty());
+}
+
+#[test]
+fn propagate_test() {
+    let x = measure!([0.3, 1.2, -2.0], [0.01, 0.02, 0.05]; false);
+    // Same propagation as the functions implemented for measures.
+    let propagated = x.propagate(|x| x.sin());
+    assert_eq!(propagated.value(), x.sin().value());
+    for (a, b) in propagated.error().iter().zip(x.sin().error()) {
+        assert!((a - b).abs() < 1e-15);
+    }
+    let y = x.propagate(|x| (x.powi(2) + 1.0).sqrt() / x.exp() - 2.0 * x.atan());
+    let derivative = |x: f64| {
+        x / (x * x + 1.0).sqrt() / x.exp() - (x * x + 1.0).sqrt() / x.exp() - 2.0 / (1.0 + x * x)
+    };
+    for i in 0..3 {
+        let expected = (derivative(x.value()[i]) * x.error()[i]).abs();
+        assert!((y.error()[i] - expected).abs() < 1e-14);
+    }
+
+    let a = measure!([2.0, 4.0], [0.1, 0.2]; false);
+    let b = measure!(3.0, 0.3; false);
+    let ratio = Measure::propagate_multi(&[&a, &b], |v| v[0] / v[1]);
+    assert_eq!(ratio.value(), (&a / &b).value());
+    for i in 0..2 {
+        let (x, y) = (a.value()[i], 3.0);
+        let expected = (a.error()[i] / y).hypot(x / y.powi(2) * 0.3);
+        assert!((ratio.error()[i] - expected).abs() < 1e-15);
+    }
+    let angle = Measure::propagate_multi(&[&a, &b], |v| v[0].atan2(v[1]));
+    let expected = (3.0_f64 * 0.1).hypot(2.0 * 0.3) / (2.0_f64.powi(2) + 3.0_f64.powi(2));
+    assert!((angle.error()[0] - expected).abs() < 1e-15);
+}
+
+#[test]
+fn monte_carlo_test() {
+    let side = measure!(10.0, 0.1; false);
+    let area = side.propagate_mc(100000, |x| x[0].powi(2));
+    let analytic = side.powi(2);
+    assert!((area.value()[0] - analytic.value()[0]).abs() < 0.05);
+    assert!((area.error()[0] - analytic.error()[0]).abs() < 0.05);
+    assert_eq!(area, side.propagate_mc(100000, |x| x[0].powi(2)));
+    assert!(area.unit().is_dimensionless());
+
+    let exact = measure!([1.0, 2.0], 0.0; false);
+    assert_eq!(
+        exact.propagate_mc(10, |x| x[0] + x[1]),
+        measure!(3.0, 0.0; false)
+    );
+
+    let x = measure!([1.0, 2.0, 3.0], 0.02; false);
+    let y = measure!(2.0, 0.01; false);
+    let product = Measure::propagate_mc_multi(&[&x, &y], 50000, |v| v[0] * v[1]);
+    let analytic = &x * &y;
+    assert_eq!(product.len(), 3);
+    for i in 0..3 {
+        assert!((product.value()[i] - analytic.value()[i]).abs() < 1e-3);
+        assert!((product.error()[i] / analytic.error()[i] - 1.0).abs() < 0.02);
+    }
+}
+
+#[test]
+fn cumulative_test() {
+    let dose = measure!([1.5, 2.0, 2.5], [0.3, 0.4, 0.0]).with_unit(Unit::second());
+    let total = dose.cumsum();
+    assert_eq!(total.value(), &vec![1.5, 3.5, 6.0]);
+    assert!((total.error()[2] - dose.sum().error()[0]).abs() < 1e-12);
+    assert_eq!(total.unit(), &Unit::second());
+
+    let gain = measure!([2.0, 0.0, 3.0], [0.1, 0.2, 0.3]; false);
+    let products = gain.cumprod();
+    assert_eq!(products.value(), &vec![2.0, 0.0, 0.0]);
+    assert_eq!(products.error()[0], 0.1);
+    assert!((products.error()[1] - 0.4).abs() < 1e-12);
+    assert!((products.error()[2] - gain.product().error()[0]).abs() < 1e-12);
+}
+
+#[test]
+fn rolling_test() {
+    let signal = measure!([1.0, 3.0, 2.0, 6.0, 5.0], [0.3, 0.4, 0.0, 0.0, 0.0]; false)
+        .with_unit(Unit::ampere());
+    let smooth = signal.rolling_mean(2);
+    assert_eq!(smooth.value(), &vec![2.0, 2.5, 4.0, 5.5]);
+    assert_eq!(smooth.error(), &vec![0.25, 0.2, 0.0, 0.0]);
+    assert_eq!(smooth.unit(), &Unit::ampere());
+    assert_eq!(
+        signal.rolling_mean(1),
+        signal.clone().change_style(Style::PM)
+    );
+    assert!(signal.rolling_mean(6).is_empty());
+
+    let spread = signal.rolling_std(3);
+    assert_eq!(spread.len(), 3);
+    assert_eq!(spread[0], signal.take(3).to_measure().standard_deviation());
+}
+
+#[test]
+fn histogram_test() {
+    let energy = measure!([0.0, 0.5, 1.2, 2.9, 3.0, 2.0, 3.9, 4.0]).with_unit(Unit::meter());
+    let (centers, counts) = energy.histogram(4);
+    assert_eq!(centers.value(), &vec![0.5, 1.5, 2.5, 3.5]);
+    assert_eq!(centers.error(), &vec![0.5; 4]);
+    assert_eq!(centers.unit(), &Unit::meter());
+    assert_eq!(counts.value(), &vec![2.0, 1.0, 2.0, 3.0]);
+    assert_eq!(counts.error()[3], 3_f64.sqrt());
+    assert_eq!(counts.value().iter().sum::<f64>(), energy.len() as f64);
+
+    let (centers, counts) = measure!([2.0, 2.0]).histogram(1);
+    assert_eq!((centers.value()[0], counts.value()[0]), (2.0, 2.0));
+
+    let (centers, counts) = energy.histogram(2);
+    let commands = testing::record(|| Histogram::new(&centers, &counts).histogram()).unwrap();
+    assert_eq!(commands[0].function, "pyplot.bar");
+    assert_eq!(commands[0].args, "[[1.0, 3.0], [3.0, 5.0]]");
+    assert!(commands[0].kwargs.contains("\"width\": [2.0, 2.0]"));
+}
+
+#[test]
+fn moments_test() {
+    let x = measure!([2, 4, 4, 4, 5, 5, 7, 9]);
+    assert_eq!(x.population_variance(), 4.0);
+    assert_eq!(x.variance(), 32.0 / 7.0);
+    assert_eq!(x.standard_deviation(), x.variance().sqrt());
+    assert!((x.skewness() - 0.65625).abs() < 1e-12);
+    assert!((x.kurtosis() + 0.21875).abs() < 1e-12);
+
+    let symmetric = measure!([-3, -1, 0, 1, 3]);
+    assert_eq!(symmetric.skewness(), 0.0);
+    assert!(measure!([1, 1, 1, 10]).skewness() > 0.0);
+}
+
+#[test]
+fn quantile_test() {
+    let x = measure!([7, 1, 3, 5, 9, 100, 11, 13, 15]);
+    assert_eq!(x.median().value, 9.0);
+    assert_eq!(x.quantile(0.0).value, 1.0);
+    assert_eq!(x.quantile(1.0).value, 100.0);
+    assert_eq!(x.quantile(0.25).value, 5.0);
+    assert_eq!(x.quantile(0.3).value, 5.8);
+    assert_eq!(x.iqr().value, 8.0);
+
+    // Ranks 4 ± 1.5 of the sorted values 1, 3, 5, 7, 9, 11, 13, 15, 100.
+    assert_eq!(x.median().error, 3.0);
+    assert!(x.median().error < x.estimation().error);
+    let iqr = x.iqr();
+    assert_eq!(
+        iqr.error,
+        (x.quantile(0.25).error.powi(2) + x.quantile(0.75).error.powi(2)).sqrt()
+    );
+    assert_eq!(measure!([4.2]).median(), ScalarMeasure::new(4.2, 0.0));
+}
+
 #[test]
 
 fn fit_test() {
     assert_eq!(
         LinearFit::new([0.7, 1.8, 2.7, 4.3], [4.6, 5.4, 6.9, 8.1]).fit(),
         (
-            measure!(1.0111550917596268, 0.1158958350259736; false),
-            measure!(3.8485066570708875, 0.31479109479486966; false)
+            ScalarMeasure::new(1.0111550917596268, 0.1158958350259736),
+            ScalarMeasure::new(3.8485066570708875, 0.31479109479486966)
         )
     );
     assert_eq!(
@@ -164,8 +684,8 @@ fn fit_test() {
             .y_error(vec![0.1, 0.3, 0.4, 0.7])
             .fit(),
         (
-            measure!(0.9963598861989915, 0.13329751086990216; false),
-            measure!(3.8896028985935134, 0.15825404095614476; false)
+            ScalarMeasure::new(0.9963598861989915, 0.13329751086990216),
+            ScalarMeasure::new(3.8896028985935134, 0.15825404095614476)
         )
     );
     assert_eq!(
@@ -177,8 +697,2035 @@ fn fit_test() {
         .initial_zeros(2)
         .fit(),
         vec![
-            measure!(1.8368313871324062, 0.1339378128643651; false),
-            measure!(2.4591460197698325, 0.35963907104421394; false)
+            ScalarMeasure::new(1.8368313871324062, 0.1339378128643651),
+            ScalarMeasure::new(2.4591460197698325, 0.35963907104421394)
+        ]
+    )
+}
+
+#[test]
+fn power_law_fit_test() {
+    let x = [1.0, 2.0, 4.0, 8.0];
+    let y: Vec<f64> = x
+        .iter()
+        .zip([1.0, 0.98, 1.03, 0.99])
+        .map(|(x, noise): (&f64, f64)| 3.0 * x.powf(1.5) * noise)
+        .collect();
+
+    let fit = PowerLawFit::new(x, y.clone());
+    let (exponent, prefactor) = fit.fit();
+    let (slope, intercept) = LinearFit::new(
+        x.iter().map(|x| x.ln()).collect::<Vec<f64>>(),
+        y.iter().map(|y| y.ln()).collect::<Vec<f64>>(),
+    )
+    .fit();
+    assert_eq!(exponent, slope);
+    assert!((prefactor.value - intercept.value.exp()).abs() < 1e-12);
+    assert!((prefactor.error - prefactor.value * intercept.error).abs() < 1e-12);
+    assert!((exponent.value - 1.5).abs() < 0.05);
+
+    let yerr: Vec<f64> = y.iter().map(|y| 0.02 * y).collect();
+    let (weighted, _) = PowerLawFit::new(x, y.clone()).y_error(yerr).fit();
+    assert!((weighted.value - exponent.value).abs() < 1e-9);
+
+    let (curve_x, curve_y) = fit.curve(4);
+    assert!((curve_x[1] - 2.0).abs() < 1e-12 && (curve_x[3] - 8.0).abs() < 1e-12);
+    assert!((curve_y[0] - prefactor.value).abs() < 1e-12);
+
+    let commands = testing::record(|| fit.plot()).unwrap();
+    assert_eq!(commands[0].function, "pyplot.plot");
+    assert_eq!(commands[1].args, "[\"log\"]");
+    assert_eq!(commands[2].function, "pyplot.yscale");
+}
+
+#[test]
+fn statistical_tests() {
+    let a = measure!([19.1, 20.3, 18.7, 21.2, 19.8, 20.4]);
+    let b = measure!([21.4, 22.0, 20.9, 22.7, 21.8, 21.1]);
+
+    let welch = t_test(&a, &b, TTestKind::Welch);
+    assert!((welch.statistic + 3.7636).abs() < 1e-4);
+    assert!((welch.dof.0 - 9.0900).abs() < 1e-4);
+    assert!((welch.p_value - 0.004382).abs() < 1e-5);
+
+    let pooled = t_test(&a, &b, TTestKind::Pooled);
+    assert_eq!(pooled.dof, (10.0, None));
+    assert!((pooled.statistic - welch.statistic).abs() < 1e-12);
+
+    let paired = t_test(&a, &b, TTestKind::Paired);
+    assert_eq!(paired.dof, (5.0, None));
+    assert!(paired.is_significant(0.05));
+
+    let f = f_test(&a, &b);
+    assert_eq!(f.dof, (5.0, Some(5.0)));
+    assert!(f.p_value > 0.05 && f.p_value <= 1.0);
+}
+
+#[test]
+fn anova_test() {
+    let result = anova(&[
+        measure!([4.2, 4.5, 4.1, 4.4]),
+        measure!([4.8, 5.1, 4.9, 5.0]),
+        measure!([4.3, 4.6, 4.4, 4.2]),
+    ]);
+
+    assert_eq!(result.test.dof, (2.0, Some(9.0)));
+    assert!((result.test.statistic - 19.1684).abs() < 1e-4);
+    assert!((result.test.p_value - 0.00056976).abs() < 1e-7);
+    assert_eq!(result.group_means.len(), 3);
+    assert!((result.group_means[1].value - 4.95).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "at least one value in every group")]
+fn anova_empty_group_test() {
+    anova(&[
+        measure!([4.2, 4.5, 4.1]),
+        Measure::new(vec![], vec![], false).unwrap(),
+    ]);
+}
+
+#[test]
+fn combine_test() {
+    let result = combine(&[
+        ScalarMeasure::new(10.0, 1.0),
+        ScalarMeasure::new(12.0, 2.0),
+        ScalarMeasure::new(11.0, 1.0),
+    ]);
+    // Weights 1, 1/4 and 1, adding 9/4.
+    assert!((result.mean - 32.0 / 3.0).abs() < 1e-12);
+    assert!((result.internal_error - 2.0 / 3.0).abs() < 1e-12);
+    let chi_squared = (10.0 - 32.0 / 3.0_f64).powi(2)
+        + (12.0 - 32.0 / 3.0_f64).powi(2) / 4.0
+        + (11.0 - 32.0 / 3.0_f64).powi(2);
+    assert!((result.reduced_chi_squared - chi_squared / 2.0).abs() < 1e-12);
+    assert!((result.external_error - (chi_squared / 2.0 / 2.25).sqrt()).abs() < 1e-12);
+    assert_eq!(result.estimation().error, result.internal_error);
+
+    // Results far apart compared with their errors.
+    let spread = combine(&[ScalarMeasure::new(1.0, 0.1), ScalarMeasure::new(2.0, 0.1)]);
+    assert!(spread.reduced_chi_squared > 1.0);
+    assert_eq!(spread.estimation().error, spread.external_error);
+
+    let single = combine(&[ScalarMeasure::new(3.0, 0.5)]);
+    assert_eq!(single.estimation(), ScalarMeasure::new(3.0, 0.5));
+    assert!(single.external_error.is_nan());
+}
+
+#[test]
+fn units_test() {
+    let time = measure!([1, 2], [0.1, 0.1]).with_unit(Unit::second());
+    let position = measure!([3, 5], [0.2, 0.2]).with_unit(Unit::meter());
+    let speed = &position / &time;
+
+    assert_eq!(speed.unit(), &(&Unit::meter() / &Unit::second()));
+    assert_eq!(format!("{}", time), "1 ± 0.1 s, 2 ± 0.1 s");
+    assert!(Table::new(vec![speed.clone()], vec!["v"])
+        .latex()
+        .contains("\\ \\mathrm{m}\\,\\mathrm{s}^{-1}$"));
+    assert!((&speed * &time).unit().is_compatible(&Unit::meter()));
+    assert!(time.sin().unit().is_dimensionless());
+
+    let result = std::panic::catch_unwind(|| &position + &time);
+    assert!(result.is_err());
+}
+
+#[test]
+fn normality_tests() {
+    let heights = measure!([148, 154, 158, 160, 161, 162, 166, 170, 182, 195, 236]);
+    let sw = shapiro_wilk(&heights);
+    assert!((sw.statistic - 0.788815).abs() < 1e-6);
+    assert!((sw.p_value - 0.006704).abs() < 1e-6);
+
+    let ks = ks_normality(&heights);
+    assert!((ks.statistic - 0.259215).abs() < 1e-6);
+    assert!(!ks.is_significant(0.05));
+
+    let small = shapiro_wilk(&measure!([1.0, 2.0, 4.0]));
+    assert!(small.p_value > 0.0 && small.p_value < 1.0);
+}
+
+#[test]
+fn lag_test() {
+    let signal = |i: i32| ((i as f64 / 5.0).sin() + (i as f64 / 2.3).cos(), 0.01);
+    let a: Measure = (0..80).map(signal).collect();
+    let b: Measure = (0..80).map(|i| signal(i - 4)).collect();
+
+    let correlation = cross_correlate(&a, &a);
+    assert_eq!(correlation.len(), 159);
+    assert_eq!(correlation[79].0, 0);
+    assert!((correlation[79].1 - 1.0).abs() < 1e-12);
+
+    let lag = estimate_lag(&a, &b);
+    assert!((lag.value - 4.0).abs() < 0.2);
+    assert!(lag.error > 0.0 && lag.error < 0.5);
+    assert!((estimate_lag(&b, &a).value + 4.0).abs() < 0.2);
+
+    let constant = measure!([0.0, 0.0, 0.0], [0.1, 0.1, 0.1]; false);
+    assert!(cross_correlate(&constant, &a.get_range(0..3).unwrap())
+        .iter()
+        .all(|(_, c)| c.is_nan()));
+    let lag = estimate_lag(&constant, &constant);
+    assert!(lag.value.is_nan() && lag.error.is_nan());
+}
+
+#[test]
+fn resample_test() {
+    let line = Dataset::new(
+        measure!([0, 1, 2, 3], 0.01),
+        measure!([1, 3, 5, 7], [0.1, 0.1, 0.3, 0.3]),
+    );
+    let new_x = measure!([0.5, 2.0, 2.75], 0.01);
+
+    let linear = line.resample_to(&new_x, ResampleMethod::Linear);
+    assert_eq!(linear.x(), &new_x);
+    assert_eq!(linear.y().value(), &vec![2.0, 5.0, 6.5]);
+    assert!((linear.y().error()[0] - 0.1 / 2_f64.sqrt()).abs() < 1e-12);
+
+    let nearest = line.resample_to(&new_x, ResampleMethod::Nearest);
+    assert_eq!(nearest.y(), &measure!([3, 5, 7], [0.1, 0.3, 0.3]));
+
+    let x: Measure = (0..=20).map(|i| (i as f64 * 0.3, 0.0)).collect();
+    let sine = Dataset::new(x.clone(), x.sin());
+    let new_x: Measure = (0..10).map(|i| (0.1 + i as f64 * 0.55, 0.0)).collect();
+    let spline = sine.resample_to(&new_x, ResampleMethod::Spline);
+    spline
+        .y()
+        .value()
+        .iter()
+        .zip(new_x.value().iter())
+        .for_each(|(val, x)| assert!((val - x.sin()).abs() < 2e-3));
+}
+
+#[test]
+fn dataset_consumers_test() {
+    let data = Dataset::new(
+        measure!([0, 1, 2, 3], 0.01; false)
+            .with_name("t")
+            .with_unit(Unit::second()),
+        measure!([1, 3, 5, 7], 0.1; false)
+            .with_name("x")
+            .with_unit(Unit::meter()),
+    );
+
+    let (slope, intercept) = LinearFit::from_dataset(&data).fit();
+    let (expected_slope, expected_intercept) = LinearFit::new(data.x(), data.y())
+        .y_error(vec![0.1; 4])
+        .fit();
+    assert_eq!((slope, intercept), (expected_slope, expected_intercept));
+
+    let parameters = CurveFit::from_dataset(|x: &f64, p: &[f64]| p[0] * x + p[1], &data)
+        .initial_zeros(2)
+        .fit();
+    assert!((parameters[0].value - 2.0).abs() < 1e-3);
+    assert!((parameters[1].value - 1.0).abs() < 1e-3);
+
+    let table = Table::from_dataset(&data).typst();
+    assert!(table.contains("[$t$ / $upright(\"s\")$], [$x$ / $upright(\"m\")$]"));
+
+    let _ = Scatter::from_dataset(&data).label_from(data.y());
+}
+
+#[test]
+fn label_test() {
+    let time = measure!([0.2, 0.3], 0.01)
+        .with_name("t")
+        .with_unit(Unit::second());
+    let position = measure!([2.4, 3.4], 0.2).with_unit(Unit::meter());
+    let ratio = measure!([1, 2], 0.1).with_name("r");
+
+    assert_eq!(time.label(), "t / s");
+    assert_eq!(position.label(), "m");
+    assert_eq!(ratio.label(), "r");
+    assert_eq!(measure!([1]).label(), "");
+    assert_eq!(time.get_range(..1).unwrap().name(), Some("t"));
+
+    let table = Table::new(vec![time, position, ratio], vec![])
+        .header_from_measures()
+        .latex();
+    assert!(table.contains("$t$ / $\\mathrm{s}$ & $\\mathrm{m}$ & $r$\\\\"));
+    assert!(table.contains("$0.2 \\pm 0.01$ & $2.4 \\pm 0.2$ & $1 \\pm 0.1$"));
+}
+
+#[test]
+fn table_options_test() {
+    let time = measure!([0.2, 0.3], 0.01);
+    let table = || Table::new(vec![time.clone()], vec!["t/s"]);
+
+    let plain = table().typst();
+    assert!(plain.starts_with("\t table("));
+    assert!(!plain.contains("figure"));
+    let latex = table().latex();
+    assert!(latex.contains("\\caption{caption}\n\\label{label}"));
+
+    let figure = table()
+        .caption("Times")
+        .label("tab:times")
+        .metadata(Metadata::new().instrument("Stopwatch"))
+        .typst();
+    assert!(figure.starts_with("// instrument: Stopwatch\n#figure(\n\t table("));
+    assert!(figure.ends_with(",\n\t caption: [Times (instrument: Stopwatch)],\n) <tab:times>"));
+    assert!(table()
+        .label("tab:times")
+        .typst()
+        .ends_with("\n),\n) <tab:times>"));
+    assert!(table()
+        .label("tab:times")
+        .latex()
+        .contains("\\caption{caption}\n\\label{tab:times}"));
+}
+
+#[test]
+fn manifest_test() {
+    use ferrilab::export::{Artifact, ArtifactKind, Manifest};
+
+    let dir = std::env::temp_dir().join("ferrilab_manifest_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let time = measure!([0.2, 0.3], 0.01);
+    let mut manifest = Manifest::new();
+    assert_eq!(manifest.json(), "[]\n");
+
+    let table = Table::new(vec![time.clone()], vec!["t/s"])
+        .caption("Times \"raw\"")
+        .label("tab:times");
+    manifest.write_table(dir.join("times.typ"), table).unwrap();
+    let typst = std::fs::read_to_string(dir.join("times.typ")).unwrap();
+    assert!(typst.starts_with("#figure("));
+    manifest
+        .write_table(
+            dir.join("plain.tex"),
+            Table::new(vec![time.clone()], vec![]),
+        )
+        .unwrap();
+    assert!(std::fs::read_to_string(dir.join("plain.tex"))
+        .unwrap()
+        .contains("\\begin{table}"));
+
+    testing::record(|| {
+        Scatter::from_measures(&time, &time).scatter()?;
+        manifest.save_figure("times.png", "Times", "fig:times")?;
+        Ok(())
+    })
+    .unwrap();
+
+    let kinds: Vec<ArtifactKind> = manifest.artifacts().iter().map(|a| a.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ArtifactKind::Table,
+            ArtifactKind::Table,
+            ArtifactKind::Figure
+        ]
+    );
+    assert_eq!(
+        manifest.artifacts()[2],
+        Artifact {
+            kind: ArtifactKind::Figure,
+            path: PathBuf::from("times.png"),
+            caption: Some("Times".to_string()),
+            label: Some("fig:times".to_string()),
+        }
+    );
+
+    let json = manifest.json();
+    assert!(json.contains("\"caption\": \"Times \\\"raw\\\"\", \"label\": \"tab:times\"}"));
+    assert!(json.contains("\"kind\": \"table\", \"path\": \""));
+    assert!(json.contains("\"caption\": null, \"label\": null"));
+    let latex = manifest.latex();
+    assert!(latex.contains("times.typ}\n"));
+    assert!(latex.ends_with(
+        "\\includegraphics[width=\\linewidth]{times.png}\n \\caption{Times}\n \\label{fig:times}\n\\end{figure}\n"
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn plot_recording_test() {
+    let x = measure!([1, 2, 3], 0.1);
+    let y = measure!([2, 4, 6], 0.2).with_name("y");
+
+    let commands = testing::record(|| {
+        Scatter::from_measures(&x, &y)
+            .label_from(&y)
+            .uplims(vec![false, false, true])
+            .scatter()?;
+        Plot::new(&x, &y)
+            .color("black")
+            .extra_kwarg("alpha", 0.5)
+            .extra_kwarg("color", "gray")
+            .plot()?;
+        let figure = save("figure.png")?;
+        assert_eq!(figure.path, PathBuf::from("figure.png"));
+        assert_eq!(figure.pixels(), (640, 480));
+        Ok(())
+    })
+    .unwrap();
+    assert!(!Path::new("figure.png").exists());
+
+    let functions: Vec<&str> = commands
+        .iter()
+        .map(|command| command.function.as_str())
+        .collect();
+    assert_eq!(
+        functions,
+        vec![
+            "pyplot.errorbar",
+            "pyplot.scatter",
+            "pyplot.plot",
+            "pyplot.ticklabel_format",
+            "pyplot.ticklabel_format",
+            "pyplot.savefig",
+            "pyplot.gcf",
+            "pyplot.gcf().get_size_inches"
         ]
+    );
+    assert_eq!(commands[0].args, "[[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]]");
+    assert!(commands[0]
+        .kwargs
+        .contains("\"uplims\": [false, false, true]"));
+    assert!(commands[1].kwargs.contains("\"label\": \"y\""));
+    assert!(commands[2].kwargs.contains("\"color\": \"gray\""));
+    assert!(commands[2].kwargs.contains("\"alpha\": 0.5"));
+    assert_eq!(commands[5].args, "[\"figure.png\"]");
+    assert!(testing::to_json(&commands).starts_with("[\n    {\"function\": \"pyplot.errorbar\""));
+
+    let samples = [0.5, 1.5, 2.5];
+    let commands = testing::record(|| {
+        Plot::new(&samples[..], samples.iter().map(|x| x * x)).plot()?;
+        Plot::new(samples, samples.iter().copied()).plot()?;
+        Plot::new(x.skip(1), y.skip(1)).plot()
+    })
+    .unwrap();
+    assert_eq!(commands[0].args, "[[0.5, 1.5, 2.5], [0.25, 2.25, 6.25]]");
+    assert_eq!(commands[1].args, "[[0.5, 1.5, 2.5], [0.5, 1.5, 2.5]]");
+    assert_eq!(commands[2].args, "[[2.0, 3.0], [4.0, 6.0]]");
+
+    let commands = testing::record(|| {
+        Hexbin::new(&samples[..], &samples[..])
+            .gridsize(20)
+            .log(true)
+            .mincount(1)
+            .hexbin()?;
+        Hexbin::new(&samples[..], &samples[..])
+            .colormap("inferno")
+            .colorbar(false)
+            .hexbin()
+    })
+    .unwrap();
+    let functions: Vec<&str> = commands
+        .iter()
+        .map(|command| command.function.as_str())
+        .collect();
+    assert_eq!(
+        functions,
+        vec!["pyplot.hexbin", "pyplot.colorbar", "pyplot.hexbin"]
+    );
+    assert!(commands[0].kwargs.contains("\"gridsize\": 20"));
+    assert!(commands[0].kwargs.contains("\"bins\": \"log\""));
+    assert!(commands[0].kwargs.contains("\"mincnt\": 1"));
+    assert_eq!(commands[1].args, "[\"<pyplot.hexbin()>\"]");
+    assert!(commands[1].kwargs.contains("\"label\": \"log10(N)\""));
+    assert!(commands[2].kwargs.contains("\"cmap\": \"inferno\""));
+    assert!(!commands[2].kwargs.contains("bins"));
+
+    let commands = testing::record(|| set_backend("Agg")).unwrap();
+    assert_eq!(commands[0].function, "matplotlib.use");
+    assert_eq!(commands[0].args, "[\"Agg\"]");
+}
+
+#[test]
+fn plot_error_test() {
+    let error = execute_python("1 / 0").unwrap_err();
+    assert!(matches!(&error, PlotError::Python(message) if message.contains("ZeroDivisionError")));
+
+    let error = PlotError::Call {
+        call: String::from("xlabel"),
+        arguments: String::from("'t / s', fontsize=18"),
+        message: String::from("TypeError"),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Call to xlabel('t / s', fontsize=18) failed: TypeError"
+    );
+}
+
+#[test]
+fn chunked_reader_test() {
+    let path = std::env::temp_dir().join("ferrilab_chunked_reader.txt");
+    let rows: Vec<String> = (0..25)
+        .map(|i| format!("{},5\t0,1\t{}\t1", i, 2 * i))
+        .collect();
+    std::fs::write(
+        &path,
+        format!("# instrument: logger\nt\tet\tx\tex\n{}", rows.join("\n")),
     )
+    .unwrap();
+
+    let calls = Mutex::new(Vec::new());
+    let progress = |parsed: usize, total: usize| calls.lock().unwrap().push((parsed, total));
+    let measures = Reader::new(path.to_str().unwrap(), 1)
+        .comment("#")
+        .chunk_size(10)
+        .progress(&progress)
+        .read_to_measures();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(measures.len(), 2);
+    assert_eq!(measures[0].len(), 25);
+    assert_eq!(measures[0].value()[24], 24.5);
+    assert_eq!(measures[1].value()[3], 6.0);
+    assert_eq!(measures[1].metadata().get("instrument"), Some("logger"));
+
+    let mut calls = calls.into_inner().unwrap();
+    calls.sort();
+    assert_eq!(calls, vec![(10, 25), (20, 25), (25, 25)]);
+}
+
+#[test]
+fn commented_header_test() {
+    let path = std::env::temp_dir().join("ferrilab_commented_header.txt");
+    std::fs::write(&path, "# t\tx\n1\t2\n3\t4\n5\t6").unwrap();
+    let data = Reader::new(path.to_str().unwrap(), 1).read_file().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let column = |values: [f64; 3]| values.map(Some).to_vec();
+    assert_eq!(data, vec![column([1.0, 3.0, 5.0]), column([2.0, 4.0, 6.0])]);
+}
+
+#[test]
+fn binary_test() {
+    let path = std::env::temp_dir().join("ferrilab_binary_test.flab");
+    let time = measure!([0.2, 0.3, 0.4], 0.01).with_unit(Unit::second());
+    let position = measure!([2.4, 3.4], [0.2, 0.3]).with_unit(Unit::meter());
+    let empty = Measure::new(vec![], vec![], false).unwrap();
+    write_binary(&path, &[time.clone(), position.clone(), empty]).unwrap();
+
+    let measures = read_binary(&path).unwrap();
+    assert_eq!(measures.len(), 3);
+    assert_eq!(measures[0].unpack(), time.unpack());
+    assert!(measures[0].unit().is_compatible(&Unit::second()));
+    assert_eq!(measures[1].unpack(), position.unpack());
+    assert!(measures[2].is_empty());
+
+    #[cfg(feature = "mmap")]
+    {
+        let mapped = ferrilab::MappedMeasures::open(&path).unwrap();
+        assert_eq!(mapped.len(), 3);
+        assert_eq!(mapped.value(1), &[2.4, 3.4]);
+        assert_eq!(mapped.error(0), &[0.01; 3]);
+        assert_eq!(mapped.to_measure(1).unpack(), position.unpack());
+    }
+
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+    let error = read_binary(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    std::fs::write(&path, b"not a binary file").unwrap();
+    assert!(read_binary(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn session_test() {
+    let path = std::env::temp_dir().join("ferrilab_session_test.session");
+    let time = measure!([0.1, 0.2, 0.30000000000000004], 0.01)
+        .with_unit(Unit::second())
+        .with_name("t")
+        .with_metadata(Metadata::new().instrument("Stopwatch: digital"));
+    let volt = Unit::new("V", [2.0, 1.0, -3.0, -1.0, 0.0, 0.0, 0.0]);
+    let signal = Dataset::new(
+        time.clone(),
+        measure!([1.5, f64::INFINITY, -2e-12], [0.1, 0.2, 0.0])
+            .with_unit(volt)
+            .change_style(Style::SI),
+    );
+
+    let mut session = Session::new();
+    assert!(session.is_empty());
+    session.insert_measure("time", time.clone());
+    session.insert_dataset("signal", signal.clone());
+    session.insert_fit(
+        "line",
+        vec![
+            ScalarMeasure::new(1.0 / 3.0, 0.01),
+            ScalarMeasure::new(-2.0, 0.5),
+        ],
+    );
+    session.insert_measure("empty", Measure::new(vec![], vec![], false).unwrap());
+    session.save(&path).unwrap();
+
+    let restored = Session::load(&path).unwrap();
+    assert_eq!(restored, session);
+    assert_eq!(restored.measure("time"), Some(&time));
+    assert_eq!(restored.dataset("signal").unwrap().y().unit().symbol(), "V");
+    assert_eq!(restored.fit("line").unwrap()[0].value, 1.0 / 3.0);
+    assert_eq!(restored.fit("missing"), None);
+
+    std::fs::write(
+        &path,
+        "# FerriLab session 1\n[fit] line\nvalue: 1 x\nerror: 1 1\n",
+    )
+    .unwrap();
+    let error = Session::load(&path).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid session file, line 2: \"x\" is not a number."
+    );
+
+    let mut tricky = Session::new();
+    let note = Metadata::new().set("site: lab\\2", "first\nsecond: \\n");
+    tricky.insert_measure(
+        "a\n[fit] b",
+        measure!([1.0], 0.1)
+            .with_name("x:\ny")
+            .with_metadata(note)
+            .with_unit(Unit::new("N\nm", [1.0, 1.0, -2.0, 0.0, 0.0, 0.0, 0.0])),
+    );
+    tricky.insert_fit("c: d\\", vec![ScalarMeasure::new(1.0, 0.1)]);
+    tricky.save(&path).unwrap();
+    assert_eq!(Session::load(&path).unwrap(), tricky);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn metadata_test() {
+    let metadata = Metadata::new()
+        .instrument("Caliper")
+        .date("2024-03-01")
+        .set("temperature", "21 ºC");
+    let length = measure!([1.2, 1.3], 0.05).with_metadata(metadata.clone());
+
+    assert_eq!(length.metadata().get("instrument"), Some("Caliper"));
+    assert_eq!(length.aprox().metadata(), &metadata);
+
+    let header = metadata.to_header("#");
+    assert_eq!(header.lines().next(), Some("# date: 2024-03-01"));
+    assert_eq!(Metadata::from_header(&header, "#"), metadata);
+
+    let table = Table::new(
+        vec![measure!([1.2], 0.05).with_metadata(metadata)],
+        vec!["l/m"],
+    )
+    .caption("Lengths")
+    .metadata(Metadata::new().operator("G. Gancedo"))
+    .latex();
+    assert!(table.starts_with("% date: 2024-03-01\n"));
+    assert!(table.contains("% operator: G. Gancedo\n"));
+    assert!(table.contains(
+        "\\caption{Lengths (date: 2024-03-01; instrument: Caliper; operator: G. Gancedo; temperature: 21 ºC)}"
+    ));
+}
+
+#[test]
+fn check_display_test() {
+    let styles = [
+        Style::List,
+        Style::PM,
+        Style::Table,
+        Style::LatexTable,
+        Style::TypstTable,
+        Style::SI,
+        Style::Aligned,
+    ];
+    let volt = Unit::new("V", [2.0, 1.0, -3.0, -1.0, 0.0, 0.0, 0.0]);
+    let measures = [
+        measure!([0.227, 0.312, 4019.0], [0.012, 0.023, 25.0]),
+        measure!([0.00123, 4520.0, 0.0], [0.00004, 30.0, 0.002]).with_unit(volt),
+        measure!([1.5, -2.25], 0.0; false).with_unit(Unit::kilogram()),
+        Measure::new(vec![f64::NAN, 1.0], vec![0.1, f64::INFINITY], false).unwrap(),
+    ];
+    for measure in &measures {
+        for style in styles {
+            assert_eq!(measure.check_display(style), vec![], "{:?}", style);
+        }
+    }
+
+    // The SI style keeps 12 significative figures, less than the error needs.
+    let length = measure!(123456.7890126, 1e-10; false).with_unit(Unit::meter());
+    assert!(length.check_display(Style::PM).is_empty());
+    let mismatches = length.check_display(Style::SI);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].index, 0);
+    assert_eq!(mismatches[0].expected, (123456.7890126, 1e-10));
+    assert!((mismatches[0].parsed.unwrap().0 - 123456.789013).abs() < 1e-6);
+    assert!(mismatches[0]
+        .to_string()
+        .starts_with("Element 0 printed as \"123.456789013 km ± 0.0000000000001 km\""));
+}
+
+#[test]
+fn si_display_test() {
+    let volt = Unit::new("V", [2.0, 1.0, -3.0, -1.0, 0.0, 0.0, 0.0]);
+    let voltage = measure!([0.00123, 4520.0], [0.00004, 30.0]; false)
+        .with_unit(volt)
+        .change_style(Style::SI);
+    assert_eq!(
+        format!("{}", voltage),
+        "1.23 mV ± 0.04 mV, 4.52 kV ± 0.03 kV"
+    );
+
+    let mass = measure!(0.0025, 0.0001; false)
+        .with_unit(Unit::kilogram())
+        .change_style(Style::SI);
+    assert_eq!(format!("{}", mass), "2.5 g ± 0.1 g");
+
+    let ratio = measure!(0.00123, 0.00004; false).change_style(Style::SI);
+    assert_eq!(format!("{}", ratio), "(1.23 ± 0.04)e-3");
+}
+
+#[test]
+fn si_compound_unit_test() {
+    let area = measure!(2500.0, 10.0; false)
+        .with_unit(Unit::meter().powf(2.0))
+        .change_style(Style::SI);
+    assert_eq!(format!("{}", area), "(2.5 ± 0.01)e3 m^2");
+    assert!(area.check_display(Style::SI).is_empty());
+
+    let speed = measure!(3.5, 0.2; false)
+        .with_unit(&Unit::meter() / &Unit::second())
+        .change_style(Style::SI);
+    assert_eq!(format!("{}", speed), "3.5 ± 0.2 m·s^-1");
+    assert!(speed.check_display(Style::SI).is_empty());
+}
+
+#[test]
+fn aligned_display_test() {
+    let current = measure!([1.2, 1520, -340], [0.1, 20, 5]; false)
+        .with_unit(Unit::ampere())
+        .change_style(Style::Aligned);
+    assert_eq!(
+        format!("{}", current),
+        "( 0.0012 ± 0.0001)e3 A\n( 1.5200 ± 0.0200)e3 A\n(-0.3400 ± 0.0050)e3 A"
+    );
+
+    let small = measure!([2.5, 0.25], [0.5, 0.05]; false).change_style(Style::Aligned);
+    assert_eq!(format!("{}", small), "2.50 ± 0.50\n0.25 ± 0.05");
+}
+
+#[cfg(feature = "shell")]
+#[test]
+fn shell_test() {
+    use ferrilab::shell::{Shell, ShellError};
+
+    let mut shell = Shell::new();
+    assert_eq!(
+        shell.execute("load examples/data.txt").unwrap(),
+        "Loaded c0, c1."
+    );
+    assert_eq!(shell.measure("c0").unwrap().len(), 8);
+    shell.execute("let t = c0 * 2").unwrap();
+    assert_eq!(shell.measure("t").unwrap().value()[0], 3.0);
+    assert!(shell.execute("fit c1 c0").unwrap().starts_with("slope = "));
+    assert!(shell.execute("table c0 t").unwrap().contains("[c0], [t]"));
+    assert!(matches!(
+        shell.execute("print x"),
+        Err(ShellError::UnknownMeasure(name)) if name == "x"
+    ));
+    assert!(matches!(
+        shell.execute("load missing.txt"),
+        Err(ShellError::Io(_))
+    ));
+
+    let commands = testing::record(|| {
+        shell.execute("plot c0 c1 fit").unwrap();
+        Ok(())
+    })
+    .unwrap();
+    assert!(commands
+        .iter()
+        .any(|command| command.function == "pyplot.plot"));
+
+    let mut output = Vec::new();
+    shell
+        .run(
+            "let y = 1 + 2\nprint y\nquit\nprint y\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "> > 3 ± 0\n> ");
+}
+
+#[cfg(feature = "console")]
+#[test]
+fn console_test() {
+    use ferrilab::console::Console;
+
+    let position = measure!([2.4, 13.4, 5.1], [0.2, 0.4, 0.9]; false)
+        .with_name("x")
+        .with_unit(Unit::meter());
+    let console = Console::new().color(false);
+    assert_eq!(
+        console.measure(&position),
+        "   x / m\n0   2.4 ± 0.2\n1  13.4 ± 0.4\n2   5.1 ± 0.9"
+    );
+    assert_eq!(
+        console.fit(
+            &["slope", "intercept"],
+            &[
+                ScalarMeasure::new(1.5, 0.25),
+                ScalarMeasure::new(-10.0, 1.0)
+            ]
+        ),
+        "parameter  value\n    slope  1.5 ± 0.25\nintercept  -10 ± 1"
+    );
+
+    let time = measure!([0.2, 0.3], 0.01; false);
+    let table = Table::new(vec![time, position.clone()], vec!["t", "x"]);
+    assert_eq!(
+        console.table(&table),
+        "t           x\n0.2 ± 0.01   2.4 ± 0.2\n0.3 ± 0.01  13.4 ± 0.4\n             5.1 ± 0.9"
+    );
+
+    let colored = Console::new().color(true).measure(&position);
+    assert!(colored.contains("\x1b[36m0.2\x1b[0m"));
+    assert!(colored.contains("\x1b[33m0.9\x1b[0m"));
+}
+
+#[test]
+fn provenance_test() {
+    let time = measure!([1, 2], 0.1).track("time");
+    let position = measure!([3, 5], 0.2).track("position");
+    let speed = (&position / &time * 2).pow(2);
+
+    assert_eq!(
+        speed.provenance().unwrap().to_text(),
+        "pow (exponent = 2)\n  mul (operand = 2)\n    div\n      position\n      time\n"
+    );
+    assert_eq!(
+        (&time + &measure!([1, 1], 0.1)).provenance().unwrap().to_dot(),
+        "digraph provenance {\n    n0 [label=\"add\"];\n    n1 [label=\"time\"];\n    n1 -> n0;\n    n2 [label=\"untracked\"];\n    n2 -> n0;\n}\n"
+    );
+    assert!(measure!([1, 2], 0.1).sqrt().provenance().is_none());
+}
+
+#[test]
+fn scalar_measure_test() {
+    let length = ScalarMeasure::new(2.0, 0.1);
+    let width = ScalarMeasure::new(3.0, 0.2);
+
+    let area = length * width;
+    assert_eq!(area.value, 6.0);
+    assert!((area.error - 0.5).abs() < 1e-12);
+    assert_eq!(2 * length, ScalarMeasure::new(4.0, 0.2));
+    assert_eq!(length.pow(2), ScalarMeasure::new(4.0, 0.4));
+    assert_eq!(format!("{}", width.aprox()), "3 ± 0.2");
+
+    let measure = Measure::from(length);
+    assert_eq!(ScalarMeasure::try_from(&measure).unwrap(), length);
+    assert!(ScalarMeasure::try_from(measure!([1, 2], 0.1)).is_err());
+
+    let times = measure!([1, 2], [0.1, 0.1]).with_unit(Unit::second());
+    assert_eq!(
+        (&times + ScalarMeasure::new(1.0, 0.0)).value(),
+        &vec![2.0, 3.0]
+    );
+    assert_eq!((length / &times).value(), &vec![2.0, 1.0]);
+
+    let estimation = measure!([9.7, 9.9, 9.8], [0.1, 0.2, 0.1]).estimation();
+    assert!((estimation.value - 9.8).abs() < 1e-12);
+    assert!((estimation.error - (0.0033333333333333 + 0.04_f64).sqrt()).abs() < 1e-9);
+
+    // sqrt((4·0.1)² + (3·0.2)²) / (3² + 4²) = sqrt(0.52) / 25.
+    let angle = ScalarMeasure::new(3.0, 0.1).atan2(&ScalarMeasure::new(4.0, 0.2));
+    assert!((angle.value - 0.6435011087932844).abs() < 1e-12);
+    assert!((angle.error - 0.028844410203711916).abs() < 1e-12);
+    let angles = measure!(3.0, 0.1; false).atan2(&measure!(4.0, 0.2; false));
+    assert!((angles.error()[0] - angle.error).abs() < 1e-15);
+    let growth = ScalarMeasure::new(2.0, 0.1).exp();
+    assert!((growth.error - 2.0_f64.exp() * 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn tagged_test() {
+    let readings = measure!([3.0, 4.0], [0.3, 0.4]; false);
+    let tagged = TaggedMeasure::new(
+        readings.clone(),
+        vec![ErrorTag::Statistical, ErrorTag::Systematic],
+    )
+    .unwrap();
+    assert_eq!(tagged.statistical(), &vec![0.3, 0.0]);
+    assert_eq!(tagged.systematic(), &vec![0.0, 0.4]);
+    assert!(TaggedMeasure::new(readings.clone(), vec![ErrorTag::Combined; 3]).is_err());
+
+    let length = TaggedMeasure::from_errors(vec![3.0], vec![0.3], vec![0.4]).unwrap();
+    assert_eq!(length.total().error(), &vec![0.5]);
+    let area = &length * &length;
+    assert!((area.statistical()[0] - 2.0_f64.sqrt() * 0.9).abs() < 1e-12);
+    assert!((area.systematic()[0] - 2.0_f64.sqrt() * 1.2).abs() < 1e-12);
+    assert_eq!(area.total().value(), &vec![9.0]);
+
+    let sum = length.clone() + TaggedMeasure::from(measure!([1.0], 1.2; false));
+    assert_eq!(
+        sum.to_string(),
+        "4 ± 0.3 (stat) ± 0.4 (sys) ± 1.2 (combined)"
+    );
+    assert_eq!(
+        sum.sourced().source_names(),
+        vec!["stat", "sys", "combined"]
+    );
+    assert!((sum.total().error()[0] - 1.3).abs() < 1e-12);
+    assert_eq!(length.map(|m| m.sqrt()).value(), &vec![3.0_f64.sqrt()]);
+}
+
+#[test]
+fn relative_error_test() {
+    let voltage = Measure::with_percent_error(vec![10.0, -20.0, 0.0], vec![2.0]).unwrap();
+    assert_eq!(voltage.error(), &vec![0.2, 0.4, 0.0]);
+    let relative = voltage.relative_error();
+    assert_eq!(&relative[..2], &[0.02, 0.02]);
+    assert!(relative[2].is_nan());
+
+    let current = Measure::with_relative_error(vec![1.0, 2.0], vec![0.1, 0.05]).unwrap();
+    assert_eq!(current.error(), &vec![0.1, 0.1]);
+    assert_eq!(measure!([0.0], 0.1).relative_error(), vec![f64::INFINITY]);
+    assert!(Measure::with_relative_error(vec![1.0, 2.0, 3.0], vec![0.1, 0.2]).is_err());
+}
+
+#[test]
+fn sensitivity_scan_test() {
+    let length = TaggedMeasure::from_errors(vec![2.0, 4.0], vec![0.1], vec![0.2]).unwrap();
+    let width = TaggedMeasure::from_errors(vec![3.0], vec![0.1], vec![0.0]).unwrap();
+    let scan = sensitivity_scan(
+        &[&length, &width],
+        &[Shift::Systematic, Shift::Absolute(0.5)],
+        |inputs| &inputs[0] * &inputs[1],
+    );
+    assert_eq!(scan.nominal.value(), &vec![6.0, 12.0]);
+    assert_eq!(scan.sources.len(), 2);
+    assert_eq!(scan.sources[1].source, 1);
+
+    let length_shift = &scan.sources[0];
+    for i in 0..2 {
+        assert!((length_shift.up[i] - 0.6).abs() < 1e-12);
+        assert!((length_shift.down[i] + 0.6).abs() < 1e-12);
+    }
+    assert!((scan.sources[1].symmetric()[1] - 2.0).abs() < 1e-12);
+    assert!((scan.total()[0] - 0.6_f64.hypot(1.0)).abs() < 1e-12);
+}
+
+#[test]
+fn autoscale_test() {
+    let mass = measure!([0.0025, 0.004], 0.0001; false).with_unit(Unit::kilogram());
+    let (scaled, suffix) = autoscale_prefix(&mass);
+    assert_eq!(suffix, "g");
+    assert_eq!(scaled.value(), &vec![2.5, 4.0]);
+    assert_eq!(scaled.error(), &vec![0.1, 0.1]);
+    assert!(scaled.unit().is_compatible(&Unit::kilogram()));
+
+    let speed = measure!([1500.0, 2500.0], 10.0; false)
+        .with_name("v")
+        .with_unit(&Unit::meter() / &Unit::second());
+    let (scaled, suffix) = autoscale_prefix(&speed);
+    assert_eq!(suffix, "10^3 m·s^-1");
+    assert_eq!(scaled.value(), &vec![1.5, 2.5]);
+    assert_eq!(scaled.label(), "v / 10^3 m·s^-1");
+
+    let ratio = measure!([2e-6], 1e-7; false).with_name("r");
+    let (scaled, suffix) = autoscale_prefix(&ratio);
+    assert_eq!(suffix, "10^-6");
+    assert_eq!(scaled.label(), "r / 10^-6");
+
+    let length = measure!([1.0, 20.0], 0.1).with_unit(Unit::meter());
+    assert_eq!(
+        autoscale_prefix(&length),
+        (length.clone(), String::from("m"))
+    );
+    let table = Table::new(vec![mass], vec!["m"]).autoscale(true).typst();
+    assert!(table.contains("[$2.5 plus.minus 0.1 upright(\"g\")$]"));
+}
+
+#[test]
+fn split_test() {
+    let data = Dataset::new(
+        measure!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 0.1),
+        measure!([0, 2, 4, 6, 8, 10, 12, 14, 16, 18], 0.1),
+    );
+    let (calibration, validation) = data.split(0.7, 3);
+    assert_eq!((calibration.len(), validation.len()), (7, 3));
+    assert_eq!(
+        data.split(0.7, 3),
+        (calibration.clone(), validation.clone())
+    );
+    let mut x: Vec<f64> = calibration.x().value().clone();
+    assert!(x.windows(2).all(|pair| pair[0] < pair[1]));
+    x.extend(validation.x().value());
+    x.sort_by(f64::total_cmp);
+    assert_eq!(&x, data.x().value());
+
+    let folds: Vec<(Dataset, Dataset)> = data.k_folds(3, 5).collect();
+    assert_eq!(folds.len(), 3);
+    let mut held_out: Vec<f64> = folds
+        .iter()
+        .flat_map(|(calibration, validation)| {
+            assert_eq!(calibration.len() + validation.len(), 10);
+            validation.x().value().clone()
+        })
+        .collect();
+    held_out.sort_by(f64::total_cmp);
+    assert_eq!(&held_out, data.x().value());
+
+    let error = data.cross_validate(5, 5, |calibration| {
+        let (slope, intercept) = LinearFit::new(
+            calibration.x().value().clone(),
+            calibration.y().value().clone(),
+        )
+        .fit();
+        move |x| slope.value * x + intercept.value
+    });
+    assert!(error < 1e-9);
+    let error = data.cross_validate(5, 5, |_| |_| 0.0);
+    assert!((error - (1140.0_f64 / 10.0).sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn instrument_test() {
+    let caliper = InstrumentProfile::new(0.05);
+    let lengths = Measure::with_instrument(vec![12.35, 40.1], &caliper);
+    assert_eq!(lengths.error(), &vec![0.05, 0.05]);
+
+    let ammeter = InstrumentProfile::new(0.001)
+        .gain_error(1.0)
+        .offset_error(0.003);
+    assert!((ammeter.error(-0.2) - 0.005_f64.hypot(0.001)).abs() < 1e-12);
+
+    let truth = [0.1, 0.2, 0.3, 0.4];
+    let readings = ammeter.simulate(&truth, 11);
+    assert_eq!(readings, ammeter.simulate(&truth, 11));
+    assert_ne!(readings, ammeter.simulate(&truth, 12));
+    for ((reading, error), value) in readings.iter().zip(truth) {
+        assert!((reading - value).abs() < 5.0 * error);
+        assert!(((reading * 1000.0).round() - reading * 1000.0).abs() < 1e-6);
+        assert_eq!(*error, ammeter.error(*reading));
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra_test() {
+    use nalgebra::{DMatrix, DVector};
+
+    let measure = measure!([1.0, 2.0, 3.0], [0.1, 0.2, 0.3]; false);
+    let values = DVector::from(&measure);
+    assert_eq!(values, DVector::from_vec(vec![1.0, 2.0, 3.0]));
+    assert_eq!(
+        measure.covariance_dmatrix(),
+        DMatrix::from_diagonal(&DVector::from_vec(vec![
+            0.1_f64.powi(2),
+            0.2_f64.powi(2),
+            0.3_f64.powi(2)
+        ]))
+    );
+    let back = Measure::from_dvectors(&values, &measure.error_dvector()).unwrap();
+    assert_eq!(back, measure);
+    assert_eq!(Measure::from(values.clone()).error(), &vec![0.0; 3]);
+    assert!(Measure::from_dvectors(&values, &DVector::from_vec(vec![0.1, 0.2])).is_err());
+
+    let x = DVector::from_vec(vec![0.0, 1.0, 2.0, 3.0]);
+    let y = x.map(|x| 3.0 * x - 1.0);
+    let (parameters, errors) = LinearFit::from_dvectors(&x, &y).fit_dvectors();
+    assert_eq!(parameters.len(), 2);
+    assert!((parameters[0] - 3.0).abs() < 1e-12 && (parameters[1] + 1.0).abs() < 1e-12);
+    assert!(errors.iter().all(|err| err.abs() < 1e-9));
+
+    let model = |x: &f64, p: &[f64]| p[0] * x + p[1];
+    let (parameters, errors) = CurveFit::from_dvectors(model, &x, &y)
+        .initial_zeros(2)
+        .fit_dvectors();
+    let expected = CurveFit::new(model, x.as_slice(), y.as_slice())
+        .initial_zeros(2)
+        .fit();
+    assert_eq!(parameters[1], expected[1].value);
+    assert_eq!(errors[0], expected[0].error);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_test() {
+    use rayon::prelude::*;
+
+    let n = 100_000;
+    let x = Measure::new(
+        (0..n).map(|i| i as f64 / 1000.0).collect(),
+        vec![0.01],
+        false,
+    )
+    .unwrap();
+    let y = Measure::new((0..n).map(|i| 1.0 + i as f64).collect(), vec![0.5], false).unwrap();
+
+    let product = &x * &y;
+    let sine = x.sin();
+    for i in (0..n).step_by(997) {
+        let ((xval, xerr), (yval, yerr)) = (x.get(i).unwrap(), y.get(i).unwrap());
+        assert_eq!(product.value()[i], xval * yval);
+        assert_eq!(
+            product.error()[i],
+            ((yval * xerr).powi(2) + (xval * yerr).powi(2)).sqrt()
+        );
+        assert_eq!(sine.value()[i], xval.sin());
+    }
+    assert_eq!((&x * 2.0).value()[n - 1], 2.0 * x.value()[n - 1]);
+
+    let total: f64 = x.par_iter().map(|(val, _)| val).sum();
+    assert!((total - x.value().iter().sum::<f64>()).abs() < 1e-6);
+    let mut z = x.clone();
+    z.par_iter_mut().for_each(|(_, err)| *err *= 2.0);
+    assert!(z.error().iter().all(|err| *err == 0.02));
+}
+
+#[test]
+fn time_series_test() {
+    let voltage = measure!([1.0, 1.1, 1.2, 1.3, 2.0, 2.1, 2.2, 3.0], 0.05).with_unit(Unit::meter());
+    let series = TimeSeries::new(
+        vec![10.0, 10.5, 11.0, 11.5, 20.0, 20.5, 21.0, 40.0],
+        voltage,
+    );
+    assert_eq!(series.median_step(), 0.5);
+
+    let gaps = series.gaps(None);
+    assert_eq!(gaps.len(), 2);
+    assert_eq!((gaps[0].index, gaps[0].start, gaps[0].end), (4, 11.5, 20.0));
+    assert_eq!(gaps[1].duration(), 19.0);
+    assert_eq!(series.gaps(10.0).len(), 1);
+
+    let segments = series.segments(None);
+    assert_eq!(
+        segments.iter().map(TimeSeries::len).collect::<Vec<_>>(),
+        vec![4, 3, 1]
+    );
+    assert_eq!(segments[1].measure().value(), &vec![2.0, 2.1, 2.2]);
+    assert_eq!(segments[1].measure().unit(), &Unit::meter());
+    assert_eq!(segments[1].elapsed(), vec![0.0, 0.5, 1.0]);
+
+    let dataset = segments[0].to_dataset();
+    assert_eq!(dataset.x().value(), &vec![0.0, 0.5, 1.0, 1.5]);
+    assert_eq!(dataset.x().unit(), &Unit::second());
+    assert_eq!(series.elapsed()[7], 30.0);
+}
+
+#[test]
+fn circular_test() {
+    let directions = measure!([350, 10, 5, 355], 1).rad();
+    assert!(directions.circular_mean().abs() < 1e-12);
+    let spread = directions.circular_std();
+    assert!((spread - 0.138161).abs() < 1e-5);
+
+    let same = measure!([1.0, 1.0, 1.0], 0.1);
+    assert!((same.circular_mean() - 1.0).abs() < 1e-12);
+    assert!(same.circular_std() < 1e-7);
+    assert_eq!(measure!([3.0], 0.1).circular_mean(), 3.0);
+
+    let opposite = measure!([0.0, std::f64::consts::PI], 0.1; false);
+    assert!(opposite.circular_mean().is_nan());
+    assert_eq!(opposite.circular_std(), f64::INFINITY);
+}
+
+#[test]
+fn ratio_test() {
+    let a = measure!([2.0, 0.0], 0.2; false);
+    let b = measure!([4.0], 0.4; false);
+    let independent = ratio(&a, &b, 0.0);
+    assert_eq!(independent.value(), &vec![0.5, 0.0]);
+    assert!((independent.error()[0] - (0.05_f64).hypot(0.05)).abs() < 1e-12);
+    assert!((independent.error()[1] - 0.05).abs() < 1e-12);
+    assert_eq!(independent, &a / &b);
+
+    let correlated = ratio(&a, &b, 1.0);
+    assert!(correlated.error()[0].abs() < 1e-12);
+    let anticorrelated = ratio(&a, &b, -1.0);
+    assert!((anticorrelated.error()[0] - 0.1).abs() < 1e-12);
+
+    let length = measure!([3.0], 0.1).with_unit(Unit::meter());
+    let time = measure!([2.0], 0.1).with_unit(Unit::second());
+    assert_eq!(
+        ratio(&length, &time, 0.5).unit(),
+        &(&Unit::meter() / &Unit::second())
+    );
+}
+
+#[test]
+fn owned_operations_test() {
+    let a = measure!([1.0, 2.0, 3.0], 0.1; false);
+    let b = measure!([2.0], 0.2; false);
+    let borrowed = &(&a * &b) + &a;
+    let owned = a.clone() * b.clone() + a.clone();
+    assert_eq!(borrowed.value(), owned.value());
+    assert_eq!(borrowed.error(), owned.error());
+
+    // The owned operand of length 1 is broadcast, not reused.
+    let broadcast = b.clone() - a.clone();
+    assert_eq!(broadcast.value(), (&b - &a).value());
+    assert_eq!(broadcast.error(), (&b - &a).error());
+
+    let mut assigned = a.clone();
+    assigned *= b.clone();
+    assigned /= 2.0;
+    assert_eq!(assigned.value(), &vec![1.0, 2.0, 3.0]);
+    assert_eq!(assigned.error(), (&(&a * &b) / 2.0).error());
+}
+
+#[test]
+fn f32_storage_test() {
+    let acquisition = GenericMeasure::<f32>::new(vec![0.25, 0.5, 1.0], vec![0.125], false)
+        .unwrap()
+        .with_unit(Unit::meter());
+    assert_eq!(acquisition.error(), &vec![0.125_f32; 3]);
+    assert_eq!(acquisition.unit(), &Unit::meter());
+
+    let voltage = Measure::from(acquisition.clone());
+    assert_eq!(voltage.value(), &vec![0.25, 0.5, 1.0]);
+    assert_eq!(voltage.to_string(), acquisition.to_string());
+    let stored: GenericMeasure<f32> = (&voltage * 2.0).into();
+    assert_eq!(stored.value(), &vec![0.5_f32, 1.0, 2.0]);
+}
+
+#[test]
+fn f32_arithmetic_test() {
+    let a = GenericMeasure::<f32>::new(vec![0.25, 0.5, 1.0], vec![0.125], false).unwrap();
+    let b = GenericMeasure::<f32>::new(vec![2.0, 4.0, 8.0], vec![0.0], false).unwrap();
+
+    let sum = &a + &b;
+    assert_eq!(sum.value(), &vec![2.25_f32, 4.5, 9.0]);
+    assert_eq!(sum.error(), &vec![0.125_f32; 3]);
+    assert_eq!((b.clone() - a.clone()).value(), &vec![1.75_f32, 3.5, 7.0]);
+
+    let product = &a * &b;
+    assert_eq!(product.value(), &vec![0.5_f32, 2.0, 8.0]);
+    assert_eq!(product.error(), &vec![0.25_f32, 0.5, 1.0]);
+    let ratio = &a / &b;
+    assert_eq!(ratio.value(), &vec![0.125_f32; 3]);
+    assert_eq!(ratio.error(), &vec![0.0625_f32, 0.03125, 0.015625]);
+
+    assert_eq!((&a * 2.0).error(), &vec![0.25_f32; 3]);
+    assert_eq!((a + 1.0).value(), &vec![1.25_f32, 1.5, 2.0]);
+}
+
+#[test]
+fn weighted_std_test() {
+    let homoscedastic = measure!([1.0, 2.0, 4.0], 0.5; false);
+    assert!((homoscedastic.weighted_std() - homoscedastic.standard_deviation()).abs() < 1e-12);
+    assert!((homoscedastic.effective_n() - 3.0).abs() < 1e-12);
+
+    let heteroscedastic = measure!([1.0, 3.0], [1.0, 0.5]; false);
+    // Weights 1 and 4: mean 2.6, effective n 25/17.
+    let n: f64 = 25.0 / 17.0;
+    assert!((heteroscedastic.effective_n() - n).abs() < 1e-12);
+    let variance: f64 = (1.6_f64.powi(2) + 4.0 * 0.4_f64.powi(2)) / 5.0;
+    assert!((heteroscedastic.weighted_std() - (variance * n / (n - 1.0)).sqrt()).abs() < 1e-12);
+
+    assert!(measure!([1.0, 2.0], [0.1, 0.0]; false)
+        .weighted_std()
+        .is_nan());
+}
+
+#[test]
+fn fit_evaluate_test() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let fit = LinearFit::new(x, [0.9, 3.1, 5.0, 7.2, 8.9])
+        .y_error(vec![0.2; 5])
+        .fit_result();
+    let (slope, intercept) = (fit.parameters()[0], fit.parameters()[1]);
+    let covariance = fit.covariance()[0][1];
+    assert!((covariance + 2.0 * slope.error.powi(2)).abs() < 1e-12);
+
+    let curve = fit.evaluate(&[2.0, 10.0]);
+    for (x, (val, err)) in [2.0, 10.0].iter().zip(curve.iter()) {
+        assert!((val - (slope.value * x + intercept.value)).abs() < 1e-9);
+        let variance = (slope.error * x).powi(2) + intercept.error.powi(2) + 2.0 * x * covariance;
+        assert!((err - variance.sqrt()).abs() < 1e-6);
+    }
+    // The error is smallest at the mean of x.
+    assert!((curve.error()[0] - 0.2 / 5.0_f64.sqrt()).abs() < 1e-6);
+
+    let model = |x: &f64, par: &[f64]| par[0] * (-x / par[1]).exp();
+    let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let y: Vec<f64> = x
+        .iter()
+        .map(|x| 5.0 * (-x / 3.0).exp() + 0.01 * (x * 7.0).sin())
+        .collect();
+    let curve_fit = CurveFit::new(model, x.clone(), y).initial_point([4.0, 2.0]);
+    let parameters = curve_fit.fit();
+    let result = curve_fit.fit_result();
+    assert_eq!(result.parameters(), parameters.as_slice());
+    let curve = result.evaluate(&x);
+    assert_eq!(curve.len(), 10);
+    assert!(curve.error().iter().all(|err| err.is_finite()));
+    assert!(!result.singular_covariance());
+
+    // The model doesn't depend on the second parameter.
+    let degenerate =
+        CurveFit::new(|x: &f64, par: &[f64]| par[0] * x, x.clone(), x).initial_point([2.0, 1.0]);
+    let result = degenerate.fit_result();
+    assert!(result.singular_covariance());
+    assert!(result.parameters().iter().all(|par| par.error.is_nan()));
+}
+
+#[test]
+fn fit_goodness_test() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let y = [0.9, 3.1, 5.0, 7.2, 8.9];
+    let fit = LinearFit::new(x, y).y_error(vec![0.2; 5]).fit_result();
+    let (slope, intercept) = (fit.parameters()[0], fit.parameters()[1]);
+    assert_eq!(
+        LinearFit::new(x, y).y_error(vec![0.2; 5]).fit(),
+        (slope, intercept)
+    );
+
+    assert_eq!(fit.dof(), 3);
+    assert_eq!(fit.residuals().len(), 5);
+    for ((x, y), res) in x.iter().zip(y).zip(fit.residuals()) {
+        assert!((res - (y - slope.value * x - intercept.value)).abs() < 1e-12);
+    }
+    let chi2: f64 = fit.residuals().iter().map(|res| (res / 0.2).powi(2)).sum();
+    assert!((fit.chi2() - chi2).abs() < 1e-9);
+    assert_eq!(fit.rounded().chi2(), fit.chi2());
+
+    let prediction = fit.predict(10.0);
+    assert_eq!(prediction.value, fit.evaluate(&[10.0]).value()[0]);
+    assert_eq!(prediction.error, fit.evaluate(&[10.0]).error()[0]);
+
+    let unweighted = LinearFit::new(x, y).fit_result();
+    let squares: f64 = unweighted.residuals().iter().map(|res| res.powi(2)).sum();
+    assert!((unweighted.chi2() - squares).abs() < 1e-12);
+}
+
+#[test]
+fn york_fit_test() {
+    // Pearson's data with York's weights, the standard test of the method.
+    let x = [0.0, 0.9, 1.8, 2.6, 3.3, 4.4, 5.2, 6.1, 6.5, 7.4];
+    let y = [5.9, 5.4, 4.4, 4.6, 3.5, 3.7, 2.8, 2.8, 2.4, 1.5];
+    let weight_x = [
+        1000.0, 1000.0, 500.0, 800.0, 200.0, 80.0, 60.0, 20.0, 1.8, 1.0,
+    ];
+    let weight_y = [1.0, 1.8, 4.0, 8.0, 20.0, 20.0, 70.0, 70.0, 100.0, 500.0];
+    let error = |weights: &[f64]| weights.iter().map(|w| w.powf(-0.5)).collect::<Vec<_>>();
+    let fit = LinearFit::new(x, y)
+        .x_error(error(&weight_x))
+        .y_error(error(&weight_y));
+    let (slope, intercept) = fit.fit();
+    assert!((slope.value + 0.48053).abs() < 1e-5);
+    assert!((intercept.value - 5.47991).abs() < 1e-5);
+    assert!((slope.error - 0.05799).abs() < 1e-4);
+    assert!((intercept.error - 0.2950).abs() < 1e-4);
+    assert_eq!(fit.fit_result().dof(), 8);
+
+    // Without x errors it is the weighted fit.
+    let weighted = LinearFit::new(x, y).y_error(error(&weight_y)).fit();
+    let york = LinearFit::new(x, y)
+        .x_error(vec![0.0; 10])
+        .y_error(error(&weight_y))
+        .fit();
+    assert!((york.0.value - weighted.0.value).abs() < 1e-9);
+    assert!((york.1.error - weighted.1.error).abs() < 1e-9);
+
+    // The effective variance widens the errors of the points by the slope.
+    let model = |x: &f64, p: &[f64]| p[0] * x + p[1];
+    let curve = CurveFit::new(model, x, y)
+        .y_error(error(&weight_y))
+        .x_error(error(&weight_x))
+        .initial_ones(2);
+    let curve = curve.fit_result();
+    assert!((curve.parameters()[0].value - slope.value).abs() < 0.02);
+    assert!((curve.parameters()[1].value - intercept.value).abs() < 0.1);
+}
+
+#[test]
+fn fix_parameter_test() {
+    let model = |x: &f64, p: &[f64]| p[0] * (-(x - p[1]).powi(2) / 2.0).exp() + p[2];
+    let x: Vec<f64> = (0..21).map(|i| i as f64 * 0.5 - 5.0).collect();
+    let y: Vec<f64> = x
+        .iter()
+        .map(|x| 3.0 * (-(x - 0.5).powi(2) / 2.0).exp() + 0.2 + 0.01 * (x * 13.0).sin())
+        .collect();
+    let fit = CurveFit::new(model, x, y)
+        .initial_point([2.0, 0.0, 0.0])
+        .fix_parameter(2, 0.5)
+        .fix_parameter(2, 0.2);
+    let result = fit.fit_result();
+
+    let parameters = result.parameters();
+    assert_eq!(parameters[2], ScalarMeasure::new(0.2, 0.0));
+    assert!((parameters[0].value - 3.0).abs() < 0.02);
+    assert!((parameters[1].value - 0.5).abs() < 0.02);
+    assert!(parameters[0].error > 0.0);
+    assert!(result.covariance()[2].iter().all(|cov| *cov == 0.0));
+    assert!(result.covariance().iter().all(|row| row[2] == 0.0));
+    assert_eq!(result.dof(), 19);
+    assert_eq!(result.residuals().len(), 21);
+}
+
+#[test]
+fn model_library_test() {
+    let x: Vec<f64> = (0..40).map(|i| 0.25 * i as f64 + 0.25).collect();
+    let noisy = |model: models::Model, p: &[f64]| -> Vec<f64> {
+        x.iter()
+            .map(|x| model(x, p) * (1.0 + 0.002 * (x * 17.0).sin()))
+            .collect()
+    };
+    let close = |fitted: Vec<ScalarMeasure>, expected: &[f64]| {
+        for (par, exp) in fitted.iter().zip(expected) {
+            assert!(
+                (par.value - exp).abs() < 0.02 * exp.abs(),
+                "{:?} {:?}",
+                fitted,
+                expected
+            );
+        }
+    };
+
+    let gaussian = [4.0, 5.5, 1.2];
+    close(
+        CurveFit::gaussian(x.clone(), noisy(models::gaussian, &gaussian)).fit(),
+        &gaussian,
+    );
+    let lorentzian = [2.0, 3.0, 0.8];
+    close(
+        CurveFit::lorentzian(x.clone(), noisy(models::lorentzian, &lorentzian)).fit(),
+        &lorentzian,
+    );
+    let exponential = [10.0, 2.5];
+    let y = noisy(models::exponential, &exponential);
+    let guess = models::exponential_guess(&x, &y);
+    assert!((guess[1] - 2.5).abs() < 0.1);
+    close(CurveFit::exponential(x.clone(), y).fit(), &exponential);
+    let power_law = [0.5, 1.5];
+    close(
+        CurveFit::power_law(x.clone(), noisy(models::power_law, &power_law)).fit(),
+        &power_law,
+    );
+}
+
+#[test]
+fn ransac_test() {
+    let x: Vec<f64> = (0..20).map(|i| i as f64).collect();
+    let mut y: Vec<f64> = x
+        .iter()
+        .map(|x| 0.5 * x + 2.0 + 0.05 * (x * 3.0).sin())
+        .collect();
+    for outlier in [3, 11, 17] {
+        y[outlier] += 15.0;
+    }
+    let (line, inliers) = LinearFit::new(x.clone(), y.clone()).ransac(0.3, 100, 5);
+    assert_eq!(inliers.iter().filter(|inlier| !**inlier).count(), 3);
+    assert!(!inliers[3] && !inliers[11] && !inliers[17]);
+    assert_eq!(line.residuals().len(), 17);
+    assert!((line.parameters()[0].value - 0.5).abs() < 0.01);
+    assert_eq!(
+        LinearFit::new(x.clone(), y.clone()).ransac(0.3, 100, 5).1,
+        inliers
+    );
+
+    let model = |x: &f64, p: &[f64]| p[0] * x + p[1];
+    let curve = CurveFit::new(model, x, y).initial_ones(2);
+    let (result, curve_inliers) = curve.ransac(0.3, 100, 5);
+    assert_eq!(curve_inliers, inliers);
+    assert!((result.parameters()[0].value - 0.5).abs() < 0.01);
+    assert!((result.parameters()[1].value - 2.0).abs() < 0.05);
+}
+
+#[test]
+fn fit_p_value_test() {
+    let fit = LinearFit::new([0.0, 1.0, 2.0, 3.0], [1.0, 3.3, 4.8, 7.1])
+        .y_error(vec![0.1; 4])
+        .fit_result();
+    assert_eq!(fit.dof(), 2);
+    assert!((fit.reduced_chi2() - fit.chi2() / 2.0).abs() < 1e-12);
+    // With 2 degrees of freedom the χ² distribution is exponential.
+    assert!((fit.p_value() - (-fit.chi2() / 2.0).exp()).abs() < 1e-12);
+    assert!(fit.p_value() < 0.05);
+
+    let exact = LinearFit::new([0.0, 1.0], [1.0, 3.0]).fit_result();
+    assert_eq!(exact.dof(), 0);
+    assert!(exact.p_value().is_nan());
+}
+
+#[test]
+fn model_comparison_test() {
+    let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let y: Vec<f64> = x
+        .iter()
+        .map(|x| 0.3 * x * x + x + 1.0 + 0.02 * (x * 5.0).sin())
+        .collect();
+    let yerr = vec![0.05; 10];
+
+    let linear = LinearFit::new(x.clone(), y.clone())
+        .y_error(yerr.clone())
+        .fit_result();
+    assert!((linear.aic() - (linear.chi2() + 4.0)).abs() < 1e-12);
+    assert!((linear.bic() - (linear.chi2() + 2.0 * 10_f64.ln())).abs() < 1e-12);
+
+    let model = |x: &f64, p: &[f64]| p[0] * x * x + p[1] * x + p[2];
+    let quadratic = CurveFit::new(model, x.clone(), y.clone())
+        .y_error(yerr.clone())
+        .initial_ones(3);
+    let quadratic = quadratic.fit_result();
+    let fixed = CurveFit::new(model, x, y)
+        .y_error(yerr)
+        .initial_ones(3)
+        .fix_parameter(2, 1.0);
+    let fixed = fixed.fit_result();
+    assert!((fixed.aic() - (fixed.chi2() + 4.0)).abs() < 1e-12);
+
+    let comparison = FitResult::compare(&[linear, quadratic, fixed]);
+    assert!(comparison[0].delta_aic > 100.0 && comparison[0].delta_bic > 100.0);
+    assert!(comparison[0].weight < 1e-12);
+    let total: f64 = comparison.iter().map(|model| model.weight).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+    assert!(comparison
+        .iter()
+        .any(|model| model.delta_aic == 0.0 && model.weight > 0.5));
+}
+
+#[test]
+fn bfgs_test() {
+    let model = |x: &f64, p: &[f64]| {
+        p[0] * (-(x - p[1]).powi(2) / (2.0 * p[2].powi(2))).exp() + p[3] * x + p[4]
+    };
+    let expected = [5.0, 2.0, 0.7, 0.3, 1.0];
+    let x: Vec<f64> = (0..60).map(|i| i as f64 * 0.1).collect();
+    let y: Vec<f64> = x
+        .iter()
+        .map(|x| model(x, &expected) + 0.01 * (x * 23.0).sin())
+        .collect();
+
+    let fit = CurveFit::new(model, x, y)
+        .y_error(vec![0.01; 60])
+        .initial_point([4.0, 1.8, 1.0, 0.0, 0.5])
+        .tolerance(1e-10)
+        .optimizer(Optimizer::Bfgs);
+    let result = fit.fit_result();
+    for (par, exp) in result.parameters().iter().zip(expected) {
+        assert!((par.value - exp).abs() < 0.01, "{:?}", result.parameters());
+        assert!(par.error > 0.0 && par.error < 0.01);
+    }
+    assert!(result.reduced_chi2() < 1.0);
+}
+
+#[test]
+fn measure2d_test() {
+    let a = Measure2D::from_rows(&[
+        measure!([1.0, 2.0], 0.1; false).with_unit(Unit::meter()),
+        measure!([3.0, 4.0], 0.2; false),
+    ]);
+    assert_eq!(a.shape(), (2, 2));
+    assert_eq!(a.unit(), &Unit::meter());
+    assert_eq!(
+        a.column(0),
+        measure!([1.0, 3.0], [0.1, 0.2]; false).with_unit(Unit::meter())
+    );
+    assert_eq!(a.get(2, 0), None);
+
+    let transposed = a.transpose();
+    assert_eq!(transposed.row(1).value(), &vec![2.0, 4.0]);
+    assert_eq!(transposed.transpose(), a);
+
+    let sum = &a + &a;
+    assert_eq!(sum.row(1).value(), &vec![6.0, 8.0]);
+    assert!((sum.get(0, 0).unwrap().1 - 0.1 * 2.0_f64.sqrt()).abs() < 1e-12);
+    assert_eq!(
+        Measure::from(a.clone() / 2.0).value(),
+        &vec![0.5, 1.0, 1.5, 2.0]
+    );
+    assert_eq!(a.to_string().lines().count(), 2);
+}
+
+#[test]
+#[should_panic]
+fn measure2d_shape_test() {
+    let a = Measure2D::new(vec![1.0; 6], vec![0.1], 2).unwrap();
+    let _ = &a + &a.transpose();
+}
+
+#[test]
+fn fit_rounded_test() {
+    let fit = LinearFit::new([1.0, 2.0, 3.0, 4.0, 5.0], [2.12, 3.87, 6.15, 8.02, 9.91])
+        .y_error(vec![0.31; 5])
+        .fit_result();
+    let rounded = fit.rounded();
+    for (par, rounded_par) in fit.parameters().iter().zip(rounded.parameters()) {
+        assert_eq!(*rounded_par, par.aprox());
+    }
+    for i in 0..2 {
+        assert!((rounded.covariance()[i][i] - rounded.parameters()[i].error.powi(2)).abs() < 1e-15);
+        for j in 0..2 {
+            let rho = rounded.correlation()[i][j];
+            assert!((rho - (fit.correlation()[i][j] * 100.0).round() / 100.0).abs() < 1e-12);
+        }
+    }
+    assert_eq!(
+        rounded.evaluate(&[0.0]).value(),
+        &vec![rounded.parameters()[1].value]
+    );
+}
+
+#[test]
+fn vector_test() {
+    let a = measure!([1.0, 2.0, 2.0], [0.1, 0.2, 0.3]; false);
+    let b = measure!([4.0, 0.0, -1.0], 0.1; false);
+
+    let dot = a.dot(&b);
+    assert_eq!(dot.value(), &vec![2.0]);
+    let variance: f64 = [0.4, 0.0, 0.3, 0.1, 0.2, 0.2]
+        .iter()
+        .map(|x: &f64| x.powi(2))
+        .sum();
+    assert!((dot.error()[0] - variance.sqrt()).abs() < 1e-12);
+
+    let norm = a.norm();
+    assert_eq!(norm.value(), &vec![3.0]);
+    assert!((norm.error()[0] - (0.01_f64 + 0.16 + 0.36).sqrt() / 3.0).abs() < 1e-12);
+    assert!(measure!([0.0, 0.0], 0.1).norm().error()[0].is_nan());
+
+    let cross = a.cross(&b);
+    assert_eq!(cross.value(), &vec![-2.0, 9.0, -8.0]);
+    // The cross product is perpendicular to both vectors.
+    assert_eq!(cross.dot(&a).value(), &vec![0.0]);
+    assert_eq!(cross.dot(&b).value(), &vec![0.0]);
+    // b_z σa_y, a_y σb_z, b_y σa_z and a_z σb_y.
+    let x_variance: f64 = 0.2_f64.powi(2) + 0.2_f64.powi(2) + 0.0 + 0.2_f64.powi(2);
+    assert!((cross.error()[0] - x_variance.sqrt()).abs() < 1e-12);
+}
+
+#[cfg(feature = "perf-stats")]
+#[test]
+fn perf_stats_test() {
+    let before = ferrilab::perf_stats();
+    let x = vec![0.0, 1.0, 2.0, 3.0];
+    let y = vec![1.0, 2.9, 5.1, 7.0];
+    CurveFit::new(
+        |x: &f64, par: &[f64]| par[0] * x + par[1],
+        x.clone(),
+        y.clone(),
+    )
+    .initial_point([1.0, 1.0])
+    .fit();
+    let a = measure!([1.0, 2.0], 0.1);
+    let _ = &a * &a;
+    let after = ferrilab::perf_stats();
+    assert!(after.fit_evaluations > before.fit_evaluations + 10);
+    assert!(after.allocations >= before.allocations + 2);
+    assert!(after.passes > before.passes);
+}
+
+#[test]
+fn formatter_flags_test() {
+    let length = measure!([1.23456, 20.6], [0.012, 0.3]; false).with_unit(Unit::meter());
+    assert_eq!(format!("{}", length), "1.23456 ± 0.012 m, 20.6 ± 0.3 m");
+    assert_eq!(format!("{:.2}", length), "1.23 ± 0.01 m, 20.60 ± 0.30 m");
+    assert_eq!(
+        format!("{:.1E}", length),
+        "1.2E0 ± 1.2E-2 m, 2.1E1 ± 3.0E-1 m"
+    );
+    assert_eq!(
+        format!("{:.1}", length.clone().change_style(Style::List)),
+        "[1.2, 20.6] ± [0.0, 0.3] m"
+    );
+    assert_eq!(
+        format!("{:.3}", length.clone().change_style(Style::Aligned)),
+        "(0.123 ± 0.001)e1 m\n(2.060 ± 0.030)e1 m"
+    );
+
+    let single = measure!([2.5], 0.1; false);
+    assert_eq!(format!("{:*^13.2}", single), "*2.50 ± 0.10*");
+    assert_eq!(format!("{:<12}", single), "2.5 ± 0.1   ");
+    assert_eq!(
+        format!("{:.1}", single.change_style(Style::LatexTable)),
+        "$2.5 \\pm 0.1$"
+    );
+}
+
+#[test]
+fn scientific_style_test() {
+    let current = measure!([0.00123, -4560.0, 0.0], [0.00004, 20.0, 0.003]; false)
+        .with_unit(Unit::ampere())
+        .change_style(Style::Scientific);
+    assert_eq!(
+        current.to_string(),
+        "(1.23 ± 0.04)×10⁻³ A, (-4.56 ± 0.02)×10³ A, (0 ± 3)×10⁻³ A"
+    );
+    assert_eq!(
+        format!("{:.1}", current.get_range(1..2).unwrap()),
+        "(-4.6 ± 0.0)×10³ A"
+    );
+    assert!(current.check_display(Style::Scientific).is_empty());
+
+    let single = measure!([12.5], 0.3; false);
+    assert_eq!(
+        single.clone().change_style(Style::Scientific).to_string(),
+        "(1.25 ± 0.03)×10¹"
+    );
+    assert_eq!(
+        single
+            .clone()
+            .change_style(Style::LatexScientific)
+            .to_string(),
+        "$(1.25 \\pm 0.03) \\times 10^{1}$"
+    );
+    assert_eq!(
+        single
+            .clone()
+            .change_style(Style::TypstScientific)
+            .to_string(),
+        "$(1.25 plus.minus 0.03) times 10^(1)$"
+    );
+    assert_eq!(
+        measure!([2.5], 0.1; false)
+            .change_style(Style::LatexScientific)
+            .to_string(),
+        "$2.5 \\pm 0.1$"
+    );
+    for style in [Style::LatexScientific, Style::TypstScientific] {
+        assert!(single.check_display(style).is_empty());
+    }
+
+    let latex = Table::new(vec![measure!([0.00123], 0.00004)], vec!["I/A"])
+        .scientific(true)
+        .latex();
+    assert!(latex.contains("$(1.23 \\pm 0.04) \\times 10^{-3}$"));
+}
+
+#[test]
+fn compact_style_test() {
+    let length = measure!([1.234, 1250.0, 2.0], [0.005, 20.0, 0.0]; false)
+        .with_unit(Unit::meter())
+        .change_style(Style::Compact);
+    assert_eq!(length.to_string(), "1.234(5) m, 1250(20) m, 2(0) m");
+    assert_eq!(
+        format!("{:.2}", length.get_range(0..1).unwrap()),
+        "1.23(1) m"
+    );
+    assert_eq!(
+        format!("{:e}", length.get_range(1..2).unwrap()),
+        "1.25(2)e3 m"
+    );
+    assert!(length.check_display(Style::Compact).is_empty());
+
+    let single = measure!([0.0123], 0.0004; false);
+    assert_eq!(
+        single.clone().change_style(Style::LatexCompact).to_string(),
+        "$0.0123(4)$"
+    );
+    assert_eq!(
+        single.clone().change_style(Style::TypstCompact).to_string(),
+        "$0.0123(4)$"
+    );
+    for style in [Style::LatexCompact, Style::TypstCompact] {
+        assert!(single.check_display(style).is_empty());
+    }
+
+    let typst = Table::new(vec![single], vec!["x"])
+        .compact(true)
+        .scientific(true)
+        .typst();
+    assert!(typst.contains("[$0.0123(4)$]"));
+}
+
+#[cfg(feature = "approx")]
+#[test]
+fn approx_eq_test() {
+    use approx::{assert_abs_diff_eq, assert_relative_eq, relative_eq};
+
+    let angle = measure!([0.3, 1.2], 0.01);
+    let identity = angle.sin().powi(2) + angle.cos().powi(2);
+    assert_relative_eq!(identity.value()[0], 1.0);
+    assert_relative_eq!(angle.sqrt().powi(2), angle, max_relative = 1e-12);
+    assert_abs_diff_eq!(
+        &angle * 2.0,
+        measure!([0.6, 2.4], 0.02; false),
+        epsilon = 1e-12
+    );
+
+    let length = angle.clone().with_unit(Unit::meter());
+    assert!(!relative_eq!(length, angle));
+    assert!(!relative_eq!(angle, measure!([0.3], 0.01)));
+}
+
+#[test]
+fn correlation_test() {
+    let x = measure!([1.0, 2.0, 3.0, 4.0], 0.1);
+    let y = measure!([2.0, 4.0, 6.0, 8.0], 0.1);
+    assert_eq!(x.pearson(&y), ScalarMeasure::new(1.0, 0.0));
+    assert_eq!(x.pearson(&(&y * -1.0)).value, -1.0);
+
+    let noisy = measure!([1.0, 3.0, 2.0, 5.0, 4.0], 0.1);
+    let ordered = measure!([1.0, 2.0, 3.0, 4.0, 5.0], 0.1);
+    let r = ordered.pearson(&noisy);
+    assert!((r.value - 0.8).abs() < 1e-12);
+    assert!((r.error - (0.36_f64 / 3.0).sqrt()).abs() < 1e-12);
+    assert!((ordered.spearman(&noisy).value - 0.8).abs() < 1e-12);
+
+    let tied = measure!([1.0, 2.0, 2.0, 3.0], 0.1);
+    assert!((tied.spearman(&x).value - 0.948_683_298_050_513_8).abs() < 1e-12);
+    assert!(measure!([1.0, 2.0], 0.1)
+        .pearson(&measure!([1.0, 2.0], 0.1))
+        .error
+        .is_nan());
+}
+
+#[test]
+fn range_constructors_test() {
+    let length = Measure::linspace(1.0, 2.0, 3)
+        .with_error(0.1)
+        .with_unit(Unit::meter());
+    assert_eq!(length.value(), &vec![1.0, 1.5, 2.0]);
+    assert_eq!(length.error(), &vec![0.1; 3]);
+    assert_eq!(length.unit(), &Unit::meter());
+    assert_eq!(Measure::linspace(0.0, 1.0, 10).value()[9], 1.0);
+    assert_eq!(Measure::linspace(2.0, 5.0, 1).value(), &vec![2.0]);
+    assert!(Measure::linspace(0.0, 1.0, 0).is_empty());
+
+    assert_eq!(Measure::arange(0.0, 1.0, 0.1).len(), 10);
+    assert_eq!(Measure::arange(0.0, 1.05, 0.1).len(), 11);
+    assert!(Measure::arange(1.0, 1.0, 0.5).is_empty());
+
+    let zeros = Measure::zeros(4);
+    assert_eq!(zeros.value(), &vec![0.0; 4]);
+    assert_eq!(zeros.error(), &vec![0.0; 4]);
+}
+
+#[test]
+#[should_panic]
+fn arange_direction_test() {
+    Measure::arange(0.0, 1.0, -0.1);
+}
+
+#[test]
+fn from_samples_test() {
+    let length = Measure::from_samples(&[10.2, 10.4, 10.3], 0.05);
+    assert_eq!(length.len(), 1);
+    assert!((length.value()[0] - 10.3).abs() < 1e-12);
+    let standard_error = 0.1 / 3.0_f64.sqrt();
+    assert!((length.error()[0] - standard_error.hypot(0.05)).abs() < 1e-12);
+
+    assert_eq!(
+        Measure::from_samples(&[2.5], 0.1),
+        measure!([2.5], 0.1; false)
+    );
+}
+
+#[test]
+fn group_by_test() {
+    let x = measure!(
+        [2.0, 1.0, 1.05, 2.01, 3.0, 1.02],
+        [0.01, 0.01, 0.02, 0.01, 0.01, 0.01]
+    )
+    .with_unit(Unit::second());
+    let y = measure!([4.0, 2.0, 2.2, 4.2, 6.0, 2.4], 0.1; false).with_unit(Unit::meter());
+
+    let (x, y) = y.group_by(&x, 0.06);
+    assert_eq!(x.len(), 3);
+    assert_eq!(x.unit(), &Unit::second());
+    assert_eq!(y.unit(), &Unit::meter());
+    assert!((x.value()[0] - 1.0233333333333334).abs() < 1e-12);
+    assert_eq!(x.error(), &vec![0.02, 0.01, 0.01]);
+    assert!((y.value()[0] - 2.2).abs() < 1e-12);
+    assert!((y.error()[0] - 0.2 / 3.0_f64.sqrt()).abs() < 1e-12);
+    assert!((y.value()[1] - 4.1).abs() < 1e-12);
+    assert_eq!((y.value()[2], y.error()[2]), (6.0, 0.1));
+
+    let (x, _) = y.group_by(&x, 0.0);
+    assert_eq!(x.len(), 3);
+}
+
+#[test]
+fn chunks_test() {
+    let x = measure!([1.0, 2.0, 3.0, 4.0, 5.0], [0.1, 0.2, 0.3, 0.4, 0.5]).with_unit(Unit::meter());
+
+    let chunks = x.chunks(2);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        chunks[1],
+        measure!([3.0, 4.0], [0.3, 0.4]).with_unit(Unit::meter())
+    );
+    assert_eq!(chunks[2].value(), &vec![5.0]);
+    assert_eq!(x.chunks(5).len(), 1);
+    assert!(Measure::zeros(0).chunks(3).is_empty());
+
+    let (left, right) = x.split_at(2);
+    assert_eq!(left.value(), &vec![1.0, 2.0]);
+    assert_eq!(right.error(), &vec![0.3, 0.4, 0.5]);
+    assert_eq!(right.unit(), &Unit::meter());
+    assert!(x.split_at(5).1.is_empty());
+
+    let (precise, rest) = x.partition(|_, err| err < 0.25);
+    assert_eq!(precise.value(), &vec![1.0, 2.0]);
+    assert_eq!(rest.value(), &vec![3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn retain_reverse_test() {
+    let mut x = measure!([1.0, -2.0, 3.0, -4.0], [0.1, 0.2, 0.3, 0.4]).with_unit(Unit::meter());
+    x.retain(|val, _| val > 0.0);
+    assert_eq!(x, measure!([1.0, 3.0], [0.1, 0.3]).with_unit(Unit::meter()));
+
+    x.reverse();
+    assert_eq!(x.value(), &vec![3.0, 1.0]);
+    assert_eq!(x.error(), &vec![0.3, 0.1]);
+
+    x.retain(|_, _| false);
+    assert!(x.is_empty());
+}
+
+#[test]
+fn dedup_test() {
+    let mut x = measure!(
+        [1.0, 1.02, 0.99, 2.0, 1.0, 1.0],
+        [0.1, 0.2, 0.3, 0.1, 0.1, 0.3]; false
+    );
+    x.dedup_by_value(0.05);
+    assert_eq!(x.len(), 3);
+    assert!((x.value()[0] - 1.0033333333333334).abs() < 1e-12);
+    assert!((x.error()[0] - 0.2).abs() < 1e-12);
+    assert_eq!(x.value()[1..], [2.0, 1.0]);
+    assert_eq!(x.error()[1..], [0.1, 0.2]);
+
+    let mut empty = Measure::zeros(0);
+    empty.dedup_by_value(1.0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn statistic_measures_test() {
+    let length = measure!([1, 2, 3, 4, 5], 0.01; false).with_unit(Unit::meter());
+
+    let mean = length.mean_measure();
+    assert_eq!(mean.value(), &vec![3.0]);
+    assert!((mean.error()[0] - 0.5_f64.sqrt()).abs() < 1e-12);
+    assert_eq!(mean.unit(), &Unit::meter());
+
+    let stderr = length.stderr_measure();
+    assert!((stderr.value()[0] - length.standard_error()).abs() < 1e-12);
+    assert!((stderr.error()[0] - 0.5_f64.sqrt() / 8_f64.sqrt()).abs() < 1e-12);
+
+    let double = &mean * 2.0;
+    assert_eq!(double.value(), &vec![6.0]);
+    assert!((double.error()[0] - 2.0 * 0.5_f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn nan_test() {
+    let x = Measure::from_options(
+        &[Some(1.0), None, Some(3.0), Some(5.0)],
+        &[Some(0.1), Some(0.1), None, Some(0.1)],
+    )
+    .unwrap();
+    assert!(x.has_nan());
+    assert!(x.mean().is_nan());
+    assert_eq!(x.nan_mean(), 3.0);
+    assert_eq!(x.nan_standard_deviation(), 2.0);
+    assert_eq!(x.nan_standard_error(), 2.0 / 3.0_f64.sqrt());
+
+    let dropped = x.clone().drop_nan();
+    assert_eq!(dropped, measure!([1.0, 5.0], 0.1; false));
+    assert!(!dropped.has_nan());
+
+    let filled = x.fill_nan(0.0, 1.0);
+    assert_eq!(filled.value(), &vec![1.0, 0.0, 3.0, 5.0]);
+    assert_eq!(filled.error(), &vec![0.1, 1.0, 1.0, 0.1]);
+
+    assert!(Measure::from_options(&[Some(1.0), None], &[Some(0.1); 3]).is_err());
+}
+
+#[test]
+fn error_budget_test() {
+    let voltage = measure!([2.0, 4.0], 0.03; false)
+        .with_unit(Unit::meter())
+        .source("statistical")
+        .with_source("calibration", vec![0.04])
+        .unwrap()
+        .with_source("calibration", vec![0.03, 0.0])
+        .unwrap();
+    assert_eq!(voltage.source_names(), vec!["statistical", "calibration"]);
+    assert_eq!(
+        voltage.component("calibration").unwrap().error(),
+        &vec![0.05, 0.04]
+    );
+    assert!(voltage.component("resolution").is_none());
+    assert!(voltage.clone().with_source("offset", vec![0.1; 3]).is_err());
+
+    let current = SourcedMeasure::new(measure!([1.0, 2.0], 0.5))
+        .with_source("resolution", vec![0.01])
+        .unwrap();
+    assert_eq!(current.total().error(), &vec![0.01, 0.01]);
+
+    let power = &voltage * &current;
+    assert_eq!(
+        power.source_names(),
+        vec!["statistical", "calibration", "resolution"]
+    );
+    assert_eq!(power.value(), &vec![2.0, 8.0]);
+    assert!((power.component("statistical").unwrap().error()[1] - 0.06).abs() < 1e-12);
+    assert!((power.component("resolution").unwrap().error()[0] - 0.02).abs() < 1e-12);
+    let total = power.total().error()[0];
+    assert!(
+        (total - (0.03_f64.powi(2) + 0.05_f64.powi(2) + 0.02_f64.powi(2)).sqrt()).abs() < 1e-12
+    );
+
+    let budget = power.error_budget();
+    assert_eq!(budget.entries.len(), 3);
+    assert_eq!(budget.entries[2].source, "resolution");
+    let fractions: f64 = budget
+        .entries
+        .iter()
+        .map(|entry| entry.contribution[0])
+        .sum();
+    assert!((fractions - 1.0).abs() < 1e-12);
+    assert_eq!(budget.to_string().lines().count(), 4);
+}
+
+#[test]
+fn map_with_test() {
+    let x = measure!([1.0, 4.0, -2.0], 0.2; false).with_unit(Unit::meter());
+    let cube = x.map_with(|v| v.powi(3), |v| 3.0 * v.powi(2));
+    assert_eq!(cube.value(), &vec![1.0, 64.0, -8.0]);
+    assert_eq!(cube.error(), x.powi(3).error());
+    assert!(cube.unit().is_dimensionless());
+    assert_eq!(x.map_with(|v| -v, |_| -1.0).error(), x.error());
+}
+
+#[test]
+fn fallible_operations_test() {
+    let length = measure!([1.0, 2.0], 0.1; false).with_unit(Unit::meter());
+    let time = measure!([0.5], 0.01; false).with_unit(Unit::second());
+
+    assert_eq!(length.try_mul(&time).unwrap(), &length * &time);
+    assert_eq!(length.try_div(&time).unwrap(), &length / &time);
+    assert_eq!(length.try_sub(&length).unwrap(), &length - &length);
+    assert!(matches!(
+        length.try_add(&time),
+        Err(MyError::IncompatibleUnits(_, _))
+    ));
+
+    let error = length.try_mul(&measure!([1.0, 2.0, 3.0])).unwrap_err();
+    assert!(matches!(error, MyError::LengthMismatch(2, 3)));
+    assert_eq!(
+        error.to_string(),
+        "Measures lengths must be equals, obtained 2 and 3."
+    );
+}
+
+#[test]
+fn welford_moments_test() {
+    let values: Vec<f64> = (0..1000).map(|i| 1e9 + (i % 7) as f64).collect();
+    let shifted: Vec<f64> = values.iter().map(|val| val - 1e9).collect();
+    let (large, small) = (
+        Measure::new(values, vec![0.1], false).unwrap(),
+        Measure::new(shifted, vec![0.1], false).unwrap(),
+    );
+    assert!((large.variance() - small.variance()).abs() < 1e-7);
+    assert!((large.skewness() - small.skewness()).abs() < 1e-5);
+
+    let mut first: Moments = small.value()[..300].iter().copied().collect();
+    let second: Moments = small.value()[300..].iter().copied().collect();
+    first.merge(&second);
+    let whole = small.moments();
+    assert_eq!(first.len(), 1000);
+    assert!((first.mean() - whole.mean()).abs() < 1e-6);
+    assert!((first.variance() - whole.variance()).abs() < 1e-9);
+    assert!((first.kurtosis() - whole.kurtosis()).abs() < 1e-6);
+    assert!((whole.standard_error() - small.standard_error()).abs() < 1e-15);
+
+    let mut empty = Moments::new();
+    assert!(empty.mean().is_nan());
+    empty.merge(&whole);
+    assert_eq!(empty, whole);
 }